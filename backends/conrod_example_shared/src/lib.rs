@@ -51,6 +51,7 @@ pub fn theme() -> conrod_core::Theme {
         border_width: 0.0,
         label_color: conrod_core::color::WHITE,
         font_id: None,
+        font_family: None,
         font_size_large: 26,
         font_size_medium: 18,
         font_size_small: 12,
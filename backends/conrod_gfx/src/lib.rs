@@ -12,7 +12,8 @@ use gfx::{
 use conrod_core::{
     color, image, render,
     text::{self, rt, GlyphCache},
-    Rect, Scalar,
+    widget::crop,
+    Point, Rect, Scalar,
 };
 
 /// A `Command` describing a step in the drawing process.
@@ -151,7 +152,7 @@ pub struct Renderer<'a, R: Resources> {
     data: pipe::Data<R>,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
-    positioned_glyphs: Vec<text::PositionedGlyph>,
+    positioned_glyphs: Vec<(text::font::Id, text::PositionedGlyph)>,
 }
 
 impl<'a, R: Resources> Renderer<'a, R> {
@@ -328,6 +329,8 @@ impl<'a, R: Resources> Renderer<'a, R> {
             let render::Primitive {
                 kind,
                 scizzor,
+                crop_polygon,
+                transform,
                 rect,
                 ..
             } = primitive;
@@ -355,6 +358,29 @@ impl<'a, R: Resources> Renderer<'a, R> {
                 };
             }
 
+            // Keep track of where this primitive's vertices begin in case they must be placed.
+            let primitive_start = vertices.len();
+
+            // Maps the vertices from `start` to the window if the primitive is rotated or scaled
+            // and crops them to the crop polygon if there is one.
+            let crop_polygon: Option<Vec<Point>> = crop_polygon.map(|polygon| {
+                polygon
+                    .iter()
+                    .map(|p| [vx(p[0]) as Scalar, vy(p[1]) as Scalar])
+                    .collect()
+            });
+            let place = |vertices: &mut Vec<Vertex>, start: usize| {
+                if !transform.is_identity() {
+                    let half_window = [half_win_w / dpi_factor, half_win_h / dpi_factor];
+                    for vertex in &mut vertices[start..] {
+                        vertex.pos = transform.apply_ndc(vertex.pos, half_window);
+                    }
+                }
+                if let Some(ref polygon) = crop_polygon {
+                    crop_vertices(vertices, start, polygon);
+                }
+            };
+
             match kind {
                 render::PrimitiveKind::Rectangle { color } => {
                     switch_to_plain_state!();
@@ -429,18 +455,15 @@ impl<'a, R: Resources> Renderer<'a, R> {
                     }
                 }
 
-                render::PrimitiveKind::Text {
-                    color,
-                    text,
-                    font_id,
-                } => {
+                render::PrimitiveKind::Text { color, text, .. } => {
                     switch_to_plain_state!();
 
                     positioned_glyphs.clear();
-                    positioned_glyphs.extend(text.positioned_glyphs(dpi_factor as f32));
+                    positioned_glyphs
+                        .extend(text.positioned_glyphs_with_font_ids(dpi_factor as f32));
 
-                    // Queue the glyphs to be cached
-                    for glyph in positioned_glyphs.iter() {
+                    // Queue the glyphs to be cached, each for the font that produced it.
+                    for &(font_id, ref glyph) in positioned_glyphs.iter() {
                         glyph_cache.queue_glyph(font_id.index(), glyph.clone());
                     }
 
@@ -457,7 +480,6 @@ impl<'a, R: Resources> Renderer<'a, R> {
                         .unwrap();
 
                     let color = gamma_srgb_to_linear(color.to_fsa());
-                    let cache_id = font_id.index();
                     let origin = rt::point(0.0, 0.0);
 
                     // A closure to convert RustType rects to GL rects
@@ -474,7 +496,8 @@ impl<'a, R: Resources> Renderer<'a, R> {
                             )) * 2.0,
                     };
 
-                    for g in positioned_glyphs.drain(..) {
+                    for (font_id, g) in positioned_glyphs.drain(..) {
+                        let cache_id = font_id.index();
                         if let Ok(Some((uv_rect, screen_rect))) = glyph_cache.rect_for(cache_id, &g)
                         {
                             let gl_rect = to_gl_rect(screen_rect);
@@ -517,6 +540,9 @@ impl<'a, R: Resources> Renderer<'a, R> {
                     image_id,
                     color,
                     source_rect,
+                    fit,
+                    x_align,
+                    y_align,
                 } => {
                     // Switch to the `Image` state for this image if we're not in it already.
                     let new_image_id = image_id;
@@ -548,24 +574,11 @@ impl<'a, R: Resources> Renderer<'a, R> {
                     let (image_w, image_h) = image_map.get(&image_id).unwrap().1;
                     let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
 
-                    // Get the sides of the source rectangle as uv coordinates.
-                    //
-                    // Texture coordinates range:
-                    // - left to right: 0.0 to 1.0
-                    // - bottom to top: 1.0 to 0.0
-                    // Note bottom and top are flipped in comparison to glium so that we don't need to flip images when loading
-                    let (uv_l, uv_r, uv_t, uv_b) = match source_rect {
-                        Some(src_rect) => {
-                            let (l, r, b, t) = src_rect.l_r_b_t();
-                            (
-                                (l / image_w) as f32,
-                                (r / image_w) as f32,
-                                (b / image_h) as f32,
-                                (t / image_h) as f32,
-                            )
-                        }
-                        None => (0.0, 1.0, 0.0, 1.0),
-                    };
+                    // Divide the image into the quads required by its `Fit`.
+                    let src_rect = source_rect
+                        .unwrap_or_else(|| Rect::from_corners([0.0, 0.0], [image_w, image_h]));
+                    let mut quads = Vec::new();
+                    fit.quads(rect, src_rect, x_align, y_align, &mut quads);
 
                     let v = |x, y, t| {
                         // Convert from conrod Scalar range to GL range -1.0 to 1.0.
@@ -581,22 +594,40 @@ impl<'a, R: Resources> Renderer<'a, R> {
 
                     let mut push_v = |x, y, t| vertices.push(v(x, y, t));
 
-                    let (l, r, b, t) = rect.l_r_b_t();
-
-                    // Bottom left triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(l, b, [uv_l, uv_b]);
-
-                    // Top right triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(r, t, [uv_r, uv_t]);
+                    for &(dst_rect, src_rect) in &quads {
+                        // Get the sides of the source rectangle as uv coordinates.
+                        //
+                        // Texture coordinates range:
+                        // - left to right: 0.0 to 1.0
+                        // - bottom to top: 1.0 to 0.0
+                        // Note bottom and top are flipped in comparison to glium so that we don't need to flip images when loading
+                        let (l, r, b, t) = src_rect.l_r_b_t();
+                        let (uv_l, uv_r, uv_t, uv_b) = (
+                            (l / image_w) as f32,
+                            (r / image_w) as f32,
+                            (b / image_h) as f32,
+                            (t / image_h) as f32,
+                        );
+
+                        let (l, r, b, t) = dst_rect.l_r_b_t();
+
+                        // Bottom left triangle.
+                        push_v(l, t, [uv_l, uv_t]);
+                        push_v(r, b, [uv_r, uv_b]);
+                        push_v(l, b, [uv_l, uv_b]);
+
+                        // Top right triangle.
+                        push_v(l, t, [uv_l, uv_t]);
+                        push_v(r, b, [uv_r, uv_b]);
+                        push_v(r, t, [uv_r, uv_t]);
+                    }
                 }
 
                 // We have no special case widgets to handle.
                 render::PrimitiveKind::Other(_) => (),
             }
+
+            place(vertices, primitive_start);
         }
 
         // Enter the final command.
@@ -683,6 +714,40 @@ impl<'a, R: Resources> Renderer<'a, R> {
     }
 }
 
+/// Crop the triangles described by `vertices[start..]` to the given convex `polygon`.
+///
+/// The parts of each triangle that lie outside of the polygon are removed, while the position,
+/// texture coordinates and color of the new vertices are interpolated from the original vertices.
+fn crop_vertices(vertices: &mut Vec<Vertex>, start: usize, polygon: &[Point]) {
+    let position = |v: &Vertex| [v.pos[0] as Scalar, v.pos[1] as Scalar];
+    let lerp = |a: &Vertex, b: &Vertex, t: Scalar| {
+        let t = t as f32;
+        let f = |a: f32, b: f32| a + (b - a) * t;
+        Vertex {
+            pos: [f(a.pos[0], b.pos[0]), f(a.pos[1], b.pos[1])],
+            uv: [f(a.uv[0], b.uv[0]), f(a.uv[1], b.uv[1])],
+            color: [
+                f(a.color[0], b.color[0]),
+                f(a.color[1], b.color[1]),
+                f(a.color[2], b.color[2]),
+                f(a.color[3], b.color[3]),
+            ],
+            mode: a.mode,
+        }
+    };
+    let triangles: Vec<Vertex> = vertices.drain(start..).collect();
+    let mut cropped = Vec::new();
+    for triangle in triangles.chunks(3) {
+        crop::clip_convex(triangle, polygon, position, lerp, &mut cropped);
+        // The cropped triangle is a convex polygon, so we can triangulate it as a fan.
+        for i in 1..cropped.len().saturating_sub(1) {
+            vertices.push(cropped[0]);
+            vertices.push(cropped[i]);
+            vertices.push(cropped[i + 1]);
+        }
+    }
+}
+
 fn gamma_srgb_to_linear(c: [f32; 4]) -> [f32; 4] {
     fn component(f: f32) -> f32 {
         // Taken from https://github.com/PistonDevelopers/graphics/src/color.rs#L42
@@ -4,7 +4,9 @@ extern crate conrod_core;
 #[macro_use]
 extern crate glium;
 
-use conrod_core::{color, image, render, text, Rect, Scalar};
+use conrod_core::widget::crop;
+use conrod_core::{color, image, render, text, Point, Rect, Scalar};
+use glium::CapabilitiesSource;
 
 /// A `Command` describing a step in the drawing process.
 #[derive(Clone, Debug)]
@@ -37,6 +39,8 @@ enum PreparedCommand {
 pub struct GlyphCache {
     cache: text::GlyphCache<'static>,
     texture: glium::texture::Texture2d,
+    // The context with which the texture is reallocated should the cache grow.
+    context: std::rc::Rc<glium::backend::Context>,
 }
 
 /// A type used for translating `render::Primitives` into `Command`s that indicate how to draw the
@@ -46,7 +50,13 @@ pub struct Renderer {
     glyph_cache: GlyphCache,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
-    positioned_glyphs: Vec<text::PositionedGlyph>,
+    positioned_glyphs: Vec<(text::font::Id, text::PositionedGlyph)>,
+    // The glyphs drawn during `fill` along with the range of vertices describing their quad.
+    text_glyphs: Vec<(
+        text::font::Id,
+        text::PositionedGlyph,
+        std::ops::Range<usize>,
+    )>,
 }
 
 /// An iterator yielding `Command`s, produced by the `Renderer::commands` method.
@@ -304,6 +314,89 @@ pub fn draw_parameters() -> glium::DrawParameters<'static> {
     }
 }
 
+/// Crop the triangles described by `vertices[start..]` to the given convex `polygon`.
+///
+/// The parts of each triangle that lie outside of the polygon are removed, while the position,
+/// texture coordinates and color of the new vertices are interpolated from the original vertices.
+fn crop_vertices(vertices: &mut Vec<Vertex>, start: usize, polygon: &[Point]) {
+    let position = |v: &Vertex| [v.position[0] as Scalar, v.position[1] as Scalar];
+    let lerp = |a: &Vertex, b: &Vertex, t: Scalar| {
+        let t = t as f32;
+        let f = |a: f32, b: f32| a + (b - a) * t;
+        Vertex {
+            position: [
+                f(a.position[0], b.position[0]),
+                f(a.position[1], b.position[1]),
+            ],
+            tex_coords: [
+                f(a.tex_coords[0], b.tex_coords[0]),
+                f(a.tex_coords[1], b.tex_coords[1]),
+            ],
+            color: [
+                f(a.color[0], b.color[0]),
+                f(a.color[1], b.color[1]),
+                f(a.color[2], b.color[2]),
+                f(a.color[3], b.color[3]),
+            ],
+            mode: a.mode,
+        }
+    };
+    let triangles: Vec<Vertex> = vertices.drain(start..).collect();
+    let mut cropped = Vec::new();
+    for triangle in triangles.chunks(3) {
+        crop::clip_convex(triangle, polygon, position, lerp, &mut cropped);
+        // The cropped triangle is a convex polygon, so we can triangulate it as a fan.
+        for i in 1..cropped.len().saturating_sub(1) {
+            vertices.push(cropped[0]);
+            vertices.push(cropped[i]);
+            vertices.push(cropped[i + 1]);
+        }
+    }
+}
+
+// Remove the given ranges of `vertices`, shifting the ranges of the `commands` that follow them.
+//
+// The ranges must be in order and must not overlap, nor may any span the boundary of a command.
+fn remove_vertices(
+    vertices: &mut Vec<Vertex>,
+    commands: &mut [PreparedCommand],
+    removed: &[std::ops::Range<usize>],
+) {
+    if removed.is_empty() {
+        return;
+    }
+    let shift = |index: usize| {
+        let removed_before: usize = removed
+            .iter()
+            .take_while(|range| range.end <= index)
+            .map(|range| range.len())
+            .sum();
+        index - removed_before
+    };
+    for command in commands.iter_mut() {
+        match *command {
+            PreparedCommand::Image(_, ref mut range) | PreparedCommand::Plain(ref mut range) => {
+                *range = shift(range.start)..shift(range.end);
+            }
+            PreparedCommand::Scizzor(_) => (),
+        }
+    }
+    let mut index = 0;
+    let mut removed = removed.iter().peekable();
+    vertices.retain(|_| {
+        let keep = loop {
+            match removed.peek() {
+                Some(range) if range.end <= index => (),
+                Some(range) => break index < range.start,
+                None => break true,
+            }
+            removed.next();
+        };
+        index += 1;
+        keep
+    });
+}
+
 /// Converts gamma (brightness) from sRGB to linear color space.
 ///
 /// sRGB is the default color space for image editors, pictures, internet etc.
@@ -406,6 +499,7 @@ impl GlyphCache {
         Ok(GlyphCache {
             cache: cache,
             texture: texture,
+            context: facade.get_context().clone(),
         })
     }
 
@@ -483,6 +577,7 @@ impl Renderer {
             commands: Vec::new(),
             vertices: Vec::new(),
             positioned_glyphs: Vec::new(),
+            text_glyphs: Vec::new(),
         })
     }
 
@@ -511,11 +606,13 @@ impl Renderer {
             ref mut vertices,
             ref mut glyph_cache,
             ref mut positioned_glyphs,
+            ref mut text_glyphs,
             ..
         } = *self;
 
         commands.clear();
         vertices.clear();
+        text_glyphs.clear();
 
         // This is necessary for supporting rusttype's GPU cache with OpenGL versions older than GL
         // 3.0 and GL ES 3.0. It is used to convert from the `U8` data format given by `rusttype`
@@ -589,6 +686,8 @@ impl Renderer {
             let render::Primitive {
                 kind,
                 scizzor,
+                crop_polygon,
+                transform,
                 rect,
                 ..
             } = primitive;
@@ -616,6 +715,29 @@ impl Renderer {
                 };
             }
 
+            // Keep track of where this primitive's vertices begin in case they must be placed.
+            let primitive_start = vertices.len();
+
+            // Maps the vertices from `start` to the window if the primitive is rotated or scaled
+            // and crops them to the crop polygon if there is one.
+            let crop_polygon: Option<Vec<Point>> = crop_polygon.map(|polygon| {
+                polygon
+                    .iter()
+                    .map(|p| [vx(p[0]) as Scalar, vy(p[1]) as Scalar])
+                    .collect()
+            });
+            let place = |vertices: &mut Vec<Vertex>, start: usize| {
+                if !transform.is_identity() {
+                    let half_window = [half_win_w / dpi_factor, half_win_h / dpi_factor];
+                    for vertex in &mut vertices[start..] {
+                        vertex.position = transform.apply_ndc(vertex.position, half_window);
+                    }
+                }
+                if let Some(ref polygon) = crop_polygon {
+                    crop_vertices(vertices, start, polygon);
+                }
+            };
+
             match kind {
                 render::PrimitiveKind::Rectangle { color } => {
                     switch_to_plain_state!();
@@ -690,71 +812,15 @@ impl Renderer {
                     }
                 }
 
-                render::PrimitiveKind::Text {
-                    color,
-                    text,
-                    font_id,
-                } => {
+                render::PrimitiveKind::Text { color, text, .. } => {
                     switch_to_plain_state!();
 
                     positioned_glyphs.clear();
-                    positioned_glyphs.extend(text.positioned_glyphs(dpi_factor as f32));
-
-                    let GlyphCache {
-                        ref mut cache,
-                        ref mut texture,
-                    } = *glyph_cache;
-
-                    // Queue the glyphs to be cached.
-                    for glyph in positioned_glyphs.iter() {
-                        cache.queue_glyph(font_id.index(), glyph.clone());
-                    }
-
-                    // Cache the glyphs on the GPU.
-                    cache
-                        .cache_queued(|rect, data| {
-                            let w = rect.width();
-                            let h = rect.height();
-                            let glium_rect = glium::Rect {
-                                left: rect.min.x,
-                                bottom: rect.min.y,
-                                width: w,
-                                height: h,
-                            };
-
-                            let data = match client_format {
-                                // `rusttype` gives data in the `U8` format so we can use it directly.
-                                glium::texture::ClientFormat::U8 => {
-                                    std::borrow::Cow::Borrowed(data)
-                                }
-                                // Otherwise we have to convert to the supported format.
-                                glium::texture::ClientFormat::U8U8U8 => {
-                                    text_data_u8u8u8.clear();
-                                    for &b in data.iter() {
-                                        text_data_u8u8u8.push(b);
-                                        text_data_u8u8u8.push(b);
-                                        text_data_u8u8u8.push(b);
-                                    }
-                                    std::borrow::Cow::Borrowed(&text_data_u8u8u8[..])
-                                }
-                                // The text cache is only ever created with U8 or U8U8U8 formats.
-                                _ => unreachable!(),
-                            };
-
-                            let image = glium::texture::RawImage2d {
-                                data: data,
-                                width: w,
-                                height: h,
-                                format: client_format,
-                            };
-                            texture.main_level().write(glium_rect, image);
-                        })
-                        .unwrap();
+                    positioned_glyphs
+                        .extend(text.positioned_glyphs_with_font_ids(dpi_factor as f32));
 
                     let color = gamma_srgb_to_linear(color.to_fsa());
 
-                    let cache_id = font_id.index();
-
                     let origin = text::rt::point(0.0, 0.0);
                     let to_gl_rect = |screen_rect: text::rt::Rect<i32>| text::rt::Rect {
                         min: origin
@@ -769,48 +835,44 @@ impl Renderer {
                             )) * 2.0,
                     };
 
-                    for g in positioned_glyphs.drain(..) {
-                        if let Ok(Some((uv_rect, screen_rect))) = cache.rect_for(cache_id, &g) {
-                            let gl_rect = to_gl_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                position: p,
-                                tex_coords: t,
-                                color: color,
-                                mode: MODE_TEXT,
-                            };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v(
-                                [gl_rect.min.x, gl_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [gl_rect.min.x, gl_rect.min.y],
-                                [uv_rect.min.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [gl_rect.max.x, gl_rect.max.y],
-                                [uv_rect.max.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [gl_rect.min.x, gl_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                        }
+                    // The glyphs are cached once all text within the frame is known, so for now
+                    // each quad's texture coordinates describe the position within its glyph.
+                    for (font_id, g) in positioned_glyphs.drain(..) {
+                        let screen_rect = match g.pixel_bounding_box() {
+                            Some(rect) => rect,
+                            None => continue,
+                        };
+                        let gl_rect = to_gl_rect(screen_rect);
+                        let v = |p, t| Vertex {
+                            position: p,
+                            tex_coords: t,
+                            color: color,
+                            mode: MODE_TEXT,
+                        };
+                        let start = vertices.len();
+                        let mut push_v = |p, t| vertices.push(v(p, t));
+                        push_v([gl_rect.min.x, gl_rect.max.y], [0.0, 1.0]);
+                        push_v([gl_rect.min.x, gl_rect.min.y], [0.0, 0.0]);
+                        push_v([gl_rect.max.x, gl_rect.min.y], [1.0, 0.0]);
+                        push_v([gl_rect.max.x, gl_rect.min.y], [1.0, 0.0]);
+                        push_v([gl_rect.max.x, gl_rect.max.y], [1.0, 1.0]);
+                        push_v([gl_rect.min.x, gl_rect.max.y], [0.0, 1.0]);
+
+                        // Place each quad separately so that its vertex range remains known.
+                        place(vertices, start);
+                        text_glyphs.push((font_id, g, start..vertices.len()));
                     }
+
+                    continue;
                 }
 
                 render::PrimitiveKind::Image {
                     image_id,
                     color,
                     source_rect,
+                    fit,
+                    x_align,
+                    y_align,
                 } => {
                     // Switch to the `Image` state for this image if we're not in it already.
                     let new_image_id = image_id;
@@ -843,23 +905,11 @@ impl Renderer {
                         let (image_w, image_h) = image.dimensions();
                         let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
 
-                        // Get the sides of the source rectangle as uv coordinates.
-                        //
-                        // Texture coordinates range:
-                        // - left to right: 0.0 to 1.0
-                        // - bottom to top: 0.0 to 1.0
-                        let (uv_l, uv_r, uv_b, uv_t) = match source_rect {
-                            Some(src_rect) => {
-                                let (l, r, b, t) = src_rect.l_r_b_t();
-                                (
-                                    (l / image_w) as f32,
-                                    (r / image_w) as f32,
-                                    (b / image_h) as f32,
-                                    (t / image_h) as f32,
-                                )
-                            }
-                            None => (0.0, 1.0, 0.0, 1.0),
-                        };
+                        // Divide the image into the quads required by its `Fit`.
+                        let src_rect = source_rect
+                            .unwrap_or_else(|| Rect::from_corners([0.0, 0.0], [image_w, image_h]));
+                        let mut quads = Vec::new();
+                        fit.quads(rect, src_rect, x_align, y_align, &mut quads);
 
                         let v = |x, y, t| {
                             // Convert from conrod Scalar range to GL range -1.0 to 1.0.
@@ -875,23 +925,40 @@ impl Renderer {
 
                         let mut push_v = |x, y, t| vertices.push(v(x, y, t));
 
-                        let (l, r, b, t) = rect.l_r_b_t();
+                        for &(dst_rect, src_rect) in &quads {
+                            // Get the sides of the source rectangle as uv coordinates.
+                            //
+                            // Texture coordinates range:
+                            // - left to right: 0.0 to 1.0
+                            // - bottom to top: 0.0 to 1.0
+                            let (l, r, b, t) = src_rect.l_r_b_t();
+                            let (uv_l, uv_r, uv_b, uv_t) = (
+                                (l / image_w) as f32,
+                                (r / image_w) as f32,
+                                (b / image_h) as f32,
+                                (t / image_h) as f32,
+                            );
 
-                        // Bottom left triangle.
-                        push_v(l, t, [uv_l, uv_t]);
-                        push_v(r, b, [uv_r, uv_b]);
-                        push_v(l, b, [uv_l, uv_b]);
+                            let (l, r, b, t) = dst_rect.l_r_b_t();
 
-                        // Top right triangle.
-                        push_v(l, t, [uv_l, uv_t]);
-                        push_v(r, b, [uv_r, uv_b]);
-                        push_v(r, t, [uv_r, uv_t]);
+                            // Bottom left triangle.
+                            push_v(l, t, [uv_l, uv_t]);
+                            push_v(r, b, [uv_r, uv_b]);
+                            push_v(l, b, [uv_l, uv_b]);
+
+                            // Top right triangle.
+                            push_v(l, t, [uv_l, uv_t]);
+                            push_v(r, b, [uv_r, uv_b]);
+                            push_v(r, t, [uv_r, uv_t]);
+                        }
                     }
                 }
 
                 // We have no special case widgets to handle.
                 render::PrimitiveKind::Other(_) => (),
             }
+
+            place(vertices, primitive_start);
         }
 
         // Enter the final command.
@@ -901,6 +968,99 @@ impl Renderer {
                 commands.push(PreparedCommand::Image(image_id, start..vertices.len()))
             }
         }
+
+        // Cache the glyphs of all text within the frame at once so that none of them may be
+        // evicted by another, growing the cache until they fit.
+        let GlyphCache {
+            ref mut cache,
+            ref mut texture,
+            ref context,
+        } = *glyph_cache;
+        for &(font_id, ref g, _) in text_glyphs.iter() {
+            cache.queue_glyph(font_id.index(), g.clone());
+        }
+        loop {
+            // Cache the glyphs on the GPU.
+            let cached = cache.cache_queued(|rect, data| {
+                let w = rect.width();
+                let h = rect.height();
+                let glium_rect = glium::Rect {
+                    left: rect.min.x,
+                    bottom: rect.min.y,
+                    width: w,
+                    height: h,
+                };
+
+                let data = match client_format {
+                    // `rusttype` gives data in the `U8` format so we can use it directly.
+                    glium::texture::ClientFormat::U8 => std::borrow::Cow::Borrowed(data),
+                    // Otherwise we have to convert to the supported format.
+                    glium::texture::ClientFormat::U8U8U8 => {
+                        text_data_u8u8u8.clear();
+                        for &b in data.iter() {
+                            text_data_u8u8u8.push(b);
+                            text_data_u8u8u8.push(b);
+                            text_data_u8u8u8.push(b);
+                        }
+                        std::borrow::Cow::Borrowed(&text_data_u8u8u8[..])
+                    }
+                    // The text cache is only ever created with U8 or U8U8U8 formats.
+                    _ => unreachable!(),
+                };
+
+                let image = glium::texture::RawImage2d {
+                    data: data,
+                    width: w,
+                    height: h,
+                    format: client_format,
+                };
+                texture.main_level().write(glium_rect, image);
+            });
+
+            // If the glyphs do not fit, double the shorter side of the cache and its texture up to
+            // the largest texture size supported. The queue is left intact, so we may retry.
+            if cached.is_err() {
+                let max_dim = context.get_capabilities().max_texture_size as u32;
+                let (w, h) = cache.dimensions();
+                let grown = if w <= h && w < max_dim {
+                    Some(((w * 2).min(max_dim), h))
+                } else if h < max_dim {
+                    Some((w, (h * 2).min(max_dim)))
+                } else if w < max_dim {
+                    Some(((w * 2).min(max_dim), h))
+                } else {
+                    None
+                };
+                // If the cache cannot grow any further, the glyphs that did not fit are skipped.
+                if let Some((w, h)) = grown {
+                    cache.to_builder().dimensions(w, h).rebuild(cache);
+                    *texture = glyph_cache_texture(context, w, h)
+                        .expect("failed to reallocate the glyph cache texture");
+                    continue;
+                }
+                cache.clear_queue();
+            }
+            break;
+        }
+
+        // Now that the glyphs are cached, map each quad's texture coordinates into the cache.
+        //
+        // Any glyph that cannot be found within the cache is skipped rather than drawn from
+        // whatever happens to lie at the quad's placeholder coordinates.
+        let mut skipped = vec![];
+        for (font_id, g, range) in text_glyphs.drain(..) {
+            match cache.rect_for(font_id.index(), &g) {
+                Ok(Some((uv_rect, _))) => {
+                    let [w, h] = [uv_rect.width(), uv_rect.height()];
+                    for vertex in &mut vertices[range] {
+                        let [x, y] = vertex.tex_coords;
+                        vertex.tex_coords = [uv_rect.min.x + x * w, uv_rect.min.y + y * h];
+                    }
+                }
+                _ => skipped.push(range),
+            }
+        }
+        remove_vertices(vertices, commands, &skipped);
     }
 
     /// Draws using the inner list of `Command`s to the given `display`.
@@ -67,6 +67,9 @@ pub fn primitives<'a, P, G, T, Img, C, F>(
 /// - `cache_queue_glyphs` - A function for caching glyphs within the given texture cache.
 /// - `texture_from_image` - A function that borrows a drawable texture `T` from an `Img`. In many
 ///   cases, `Img` may be the same type as `T`, however we provide this to allow for flexibility.
+///
+/// The `source_rect` of an `Image` primitive is in pixels from the bottom left corner of the
+/// image, as with the other backends.
 pub fn primitive<'a, Img, G, T, C, F>(
     primitive: render::Primitive,
     context: Context,
@@ -86,6 +89,7 @@ pub fn primitive<'a, Img, G, T, C, F>(
     let render::Primitive {
         kind,
         scizzor,
+        transform,
         rect,
         ..
     } = primitive;
@@ -95,6 +99,8 @@ pub fn primitive<'a, Img, G, T, C, F>(
         .trans(view_size[0] / 2.0, view_size[1] / 2.0)
         .scale(1.0, -1.0);
     let context = crop_context(context, scizzor);
+    // Apply the rotation and scale of the primitive and its parents, if any.
+    let context = context.append_transform(transform.matrix);
 
     match kind {
         render::PrimitiveKind::Rectangle { color } => {
@@ -130,11 +136,7 @@ pub fn primitive<'a, Img, G, T, C, F>(
             }
         }
 
-        render::PrimitiveKind::Text {
-            color,
-            text,
-            font_id,
-        } => {
+        render::PrimitiveKind::Text { color, text, .. } => {
             // Retrieve the "dots per inch" factor by dividing the draw width by the window width.
             //
             // TODO: Perhaps this should be a method on the `Context` type?
@@ -142,15 +144,16 @@ pub fn primitive<'a, Img, G, T, C, F>(
                 .viewport
                 .map(|v| v.draw_size[0] as f32 / v.window_size[0] as f32)
                 .unwrap_or(1.0);
-            let positioned_glyphs: Vec<_> = text.positioned_glyphs(dpi_factor).collect();
+            let positioned_glyphs: Vec<_> =
+                text.positioned_glyphs_with_font_ids(dpi_factor).collect();
             // Re-orient the context to top-left origin with *y* facing downwards, as the
             // `positioned_glyphs` yield pixel positioning.
             let context = context
                 .scale(1.0, -1.0)
                 .trans(-view_size[0] / 2.0, -view_size[1] / 2.0);
 
-            // Queue the glyphs to be cached.
-            for glyph in positioned_glyphs.iter() {
+            // Queue the glyphs to be cached, each for the font that produced it.
+            for &(font_id, ref glyph) in positioned_glyphs.iter() {
                 glyph_cache.queue_glyph(font_id.index(), glyph.clone());
             }
 
@@ -161,13 +164,17 @@ pub fn primitive<'a, Img, G, T, C, F>(
                 })
                 .unwrap();
 
-            let cache_id = font_id.index();
             let (tex_w, tex_h) = text_texture_cache.get_size();
             let color = color.to_fsa();
 
             let rectangles = positioned_glyphs
                 .into_iter()
-                .filter_map(|g| glyph_cache.rect_for(cache_id, &g).ok().unwrap_or(None))
+                .filter_map(|(font_id, g)| {
+                    glyph_cache
+                        .rect_for(font_id.index(), &g)
+                        .ok()
+                        .unwrap_or(None)
+                })
                 .map(|(uv_rect, screen_rect)| {
                     let rectangle = {
                         let div_dpi_factor = |s| (s as f32 / dpi_factor as f32) as f64;
@@ -204,21 +211,31 @@ pub fn primitive<'a, Img, G, T, C, F>(
             image_id,
             color,
             source_rect,
+            fit,
+            x_align,
+            y_align,
         } => {
             if let Some(img) = image_map.get(&image_id) {
-                let mut image = piston_graphics::image::Image::new();
-                image.color = color.map(|c| c.to_fsa());
-                if let Some(source_rect) = source_rect {
-                    let (x, y, w, h) = source_rect.x_y_w_h();
-                    image.source_rectangle = Some([x, y, w, h]);
-                }
-                let (left, top, w, h) = rect.l_t_w_h();
-                image.rectangle = Some([0.0, 0.0, w, h]);
-                let context = context.trans(left, top).scale(1.0, -1.0);
-                let transform = context.transform;
-                let draw_state = &context.draw_state;
                 let tex = texture_from_image(img);
-                image.draw(tex, draw_state, transform, graphics);
+                let (image_w, image_h) = tex.get_size();
+                let (image_w, image_h) = (image_w as f64, image_h as f64);
+                let src_rect = source_rect
+                    .unwrap_or_else(|| Rect::from_corners([0.0, 0.0], [image_w, image_h]));
+                let mut quads = Vec::new();
+                fit.quads(rect, src_rect, x_align, y_align, &mut quads);
+                for (dst_rect, src_rect) in quads {
+                    let mut image = piston_graphics::image::Image::new();
+                    image.color = color.map(|c| c.to_fsa());
+                    // Piston expects the source rectangle relative to the top left of the image.
+                    let (l, t, w, h) = src_rect.l_t_w_h();
+                    image.source_rectangle = Some([l, image_h - t, w, h]);
+                    let (left, top, w, h) = dst_rect.l_t_w_h();
+                    image.rectangle = Some([0.0, 0.0, w, h]);
+                    let context = context.trans(left, top).scale(1.0, -1.0);
+                    let transform = context.transform;
+                    let draw_state = &context.draw_state;
+                    image.draw(tex, draw_state, transform, graphics);
+                }
             }
         }
 
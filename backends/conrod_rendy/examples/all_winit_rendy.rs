@@ -87,7 +87,12 @@ fn main() {
             let rust_logo = image_map.insert(image);
             let app = conrod_example_shared::DemoApp::new(rust_logo);
             let dpi_factor = window.hidpi_factor();
-            let aux = SimpleUiAux { ui, image_map, dpi_factor };
+            let aux = SimpleUiAux {
+                ui,
+                image_map,
+                dpi_factor,
+                image_atlas: None,
+            };
             let size = window.inner_size().to_physical(dpi_factor);
             let win_size = [size.width as u32, size.height as u32];
             let graph = create_graph(win_size, &mut factory, &mut families, surface, &aux);
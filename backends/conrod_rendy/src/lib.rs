@@ -1,9 +1,9 @@
-use conrod_core::image::{Id as ImageId, Map as ImageMap};
+use conrod_core::image::{Atlas, Id as ImageId, Map as ImageMap};
 use conrod_core::mesh::{self, Mesh};
 use conrod_core::{Rect, Ui};
 use rendy::command::{QueueId, RenderPassEncoder};
 use rendy::core::{hal, hal::pso::CreationError, types::Layout};
-use rendy::factory::{Factory, ImageState};
+use rendy::factory::{Factory, ImageState, UploadError};
 use rendy::graph::{render::*, GraphContext, NodeBuffer, NodeImage};
 use rendy::hal::{
     device::Device,
@@ -19,6 +19,7 @@ use rendy::resource::{
 };
 use rendy::shader::{ShaderSet, ShaderSetBuilder, SpirvShader};
 use rendy::texture::{Texture, TextureBuilder};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use vertex::Vertex;
@@ -45,6 +46,14 @@ pub trait UiAux {
     /// The DPI factor for translating from conrod's pixel-agnostic coordinates to pixel
     /// coordinates for the underlying surface.
     fn dpi_factor(&self) -> f64;
+
+    /// An optional atlas from whose pages inserted images are drawn.
+    ///
+    /// The `UiPipeline` creates the texture for each of the atlas' pages when it is built and
+    /// uploads the pages each frame, so the page ids need not be inserted into the `image_map`.
+    fn image_atlas(&self) -> Option<&RefCell<Atlas>> {
+        None
+    }
 }
 
 lazy_static::lazy_static! {
@@ -91,6 +100,8 @@ pub struct UiPipeline<B: Backend> {
     descriptor_sets: HashMap<ImageId, Escape<DescriptorSet<B>>>,
     buffer: Option<Escape<Buffer<B>>>,
     glyph_cache_texture: Texture<B>,
+    // A texture for each page of the `UiAux::image_atlas`.
+    atlas_textures: HashMap<ImageId, Texture<B>>,
 }
 
 /// A simple, provided implementation of the `UiAux` trait.
@@ -104,6 +115,7 @@ where
     pub ui: Ui,
     pub image_map: ImageMap<UiTexture<B>>,
     pub dpi_factor: f64,
+    pub image_atlas: Option<RefCell<Atlas>>,
 }
 
 impl<B> UiTexture<B>
@@ -206,6 +218,10 @@ where
     fn dpi_factor(&self) -> f64 {
         self.dpi_factor
     }
+
+    fn image_atlas(&self) -> Option<&RefCell<Atlas>> {
+        self.image_atlas.as_ref()
+    }
 }
 
 impl<B, T> SimpleGraphicsPipelineDesc<B, T> for UiPipelineDesc
@@ -256,7 +272,7 @@ where
         _ctx: &GraphContext<B>,
         factory: &mut Factory<B>,
         queue: QueueId,
-        _aux: &T,
+        aux: &T,
         _buffers: Vec<NodeBuffer>,
         _images: Vec<NodeImage>,
         set_layouts: &[Handle<DescriptorSetLayout<B>>],
@@ -264,7 +280,9 @@ where
         // TODO: Consider using `Mesh::with_glyph_cache_dimensions` and allowing user to specify
         // glyph cache dimensions. Currently we just use the default size, but this is not always
         // enough for large GUIs with lots of text.
-        let mesh = Mesh::with_glyph_cache_dimensions(self.glyph_cache_dimensions);
+        // The glyph cache texture is never reallocated, so the glyph cache must not grow.
+        let mut mesh = Mesh::with_glyph_cache_dimensions(self.glyph_cache_dimensions);
+        mesh.set_max_glyph_cache_dimensions(self.glyph_cache_dimensions);
 
         // Create the texture used for caching glyphs on the GPU.
         let sampler_img_state = sampler_img_state(queue);
@@ -296,12 +314,28 @@ where
 
         let descriptor_sets = HashMap::new();
 
+        // Create a texture for each page of the image atlas. The pages are uploaded as they are
+        // modified during `prepare`.
+        let mut atlas_textures = HashMap::new();
+        if let Some(atlas) = aux.image_atlas() {
+            let atlas = atlas.borrow();
+            let [page_w, page_h] = atlas.page_dimensions();
+            let page_bytes = vec![0u8; page_w as usize * page_h as usize * 4];
+            for &page_id in atlas.page_ids() {
+                let texture =
+                    UiTexture::from_rgba_bytes(&page_bytes, [page_w, page_h], factory, queue)
+                        .expect("failed to create atlas page texture");
+                atlas_textures.insert(page_id, texture.into());
+            }
+        }
+
         Ok(UiPipeline {
             mesh,
             default_descriptor_set,
             descriptor_sets,
             buffer: None,
             glyph_cache_texture,
+            atlas_textures,
         })
     }
 }
@@ -333,50 +367,54 @@ where
         };
 
         // Fill the mesh from the given primitives.
-        let fill = self
-            .mesh
-            .fill(viewport, dpi_factor, image_map, primitives)
-            .expect("failed to fill mesh");
+        let fill = match aux.image_atlas() {
+            Some(atlas) => {
+                let mut atlas = atlas.borrow_mut();
+                self.mesh
+                    .fill_with_atlas(viewport, dpi_factor, image_map, &mut atlas, primitives)
+            }
+            None => self.mesh.fill(viewport, dpi_factor, image_map, primitives),
+        }
+        .expect("failed to fill mesh");
 
         // If fill indicates the glyph cache needs updating, do so.
         if fill.glyph_cache_requires_upload {
             let (gc_width, gc_height) = self.mesh.glyph_cache().dimensions();
-            let img_layers = rendy::resource::SubresourceLayers {
-                aspects: Aspects::COLOR,
-                level: 0,
-                layers: 0..1,
-            };
-            let img_offset = Offset::ZERO;
-            let img_extent = Extent {
-                width: gc_width,
-                height: gc_height,
-                depth: 1,
-            };
-            let img_state = sampler_img_state(queue);
-            let (last, next) = (img_state, img_state);
             unsafe {
-                factory
-                    .upload_image(
-                        self.glyph_cache_texture.image().clone(),
-                        gc_width,
-                        gc_height,
-                        img_layers,
-                        img_offset,
-                        img_extent,
-                        self.mesh.glyph_cache_pixel_buffer(),
-                        last,
-                        next,
-                    )
-                    .expect("failed to update glyph cache texture");
+                upload_texture(
+                    factory,
+                    queue,
+                    &self.glyph_cache_texture,
+                    [gc_width, gc_height],
+                    self.mesh.glyph_cache_pixel_buffer(),
+                )
+                .expect("failed to update glyph cache texture");
             }
         }
 
+        // Upload any atlas pages that were modified during the fill.
+        if let Some(atlas) = aux.image_atlas() {
+            let atlas_textures = &self.atlas_textures;
+            atlas.borrow_mut().update_pages(|update| unsafe {
+                upload_texture(
+                    factory,
+                    queue,
+                    &atlas_textures[&update.page_id],
+                    update.dimensions,
+                    update.rgba,
+                )
+                .expect("failed to update atlas page texture");
+            });
+        }
+
         // Create new descriptor sets for new images.
         let image_map = aux.image_map();
         let glyph_cache_texture = &self.glyph_cache_texture;
         let descriptor_sets = &mut self.descriptor_sets;
         let new_textures: HashMap<_, _> = image_map
             .iter()
+            .map(|(img_id, texture)| (img_id, &texture.texture))
+            .chain(self.atlas_textures.iter())
             .filter(|&(img_id, _)| !descriptor_sets.contains_key(img_id))
             .collect();
         let new_descriptors: HashMap<_, _> = new_textures
@@ -529,6 +567,45 @@ fn sampler_img_state(queue_id: QueueId) -> ImageState {
     }
 }
 
+// Upload the given pixels to the whole of the given texture.
+unsafe fn upload_texture<B, T>(
+    factory: &Factory<B>,
+    queue: QueueId,
+    texture: &Texture<B>,
+    dimensions: [u32; 2],
+    pixels: &[T],
+) -> Result<(), UploadError>
+where
+    B: Backend,
+    T: 'static + Copy,
+{
+    let [width, height] = dimensions;
+    let img_layers = rendy::resource::SubresourceLayers {
+        aspects: Aspects::COLOR,
+        level: 0,
+        layers: 0..1,
+    };
+    let img_offset = Offset::ZERO;
+    let img_extent = Extent {
+        width,
+        height,
+        depth: 1,
+    };
+    let img_state = sampler_img_state(queue);
+    let (last, next) = (img_state, img_state);
+    factory.upload_image(
+        texture.image().clone(),
+        width,
+        height,
+        img_layers,
+        img_offset,
+        img_extent,
+        pixels,
+        last,
+        next,
+    )
+}
+
 // Create the glyph cache texture sampler descriptor.
 fn create_glyph_cache_descriptor<B>(glyph_cache_texture: &Texture<B>) -> hal::pso::Descriptor<B>
 where
@@ -300,7 +300,9 @@ impl Renderer {
                 .render_pass(subpass)
                 .with_pipeline_layout(device.clone(), layout)?,
         );
-        let mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims);
+        // The glyph cache texture is never reallocated, so the glyph cache must not grow.
+        let mut mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims);
+        mesh.set_max_glyph_cache_dimensions(glyph_cache_dims);
 
         let glyph_cache_tex = {
             let [width, height] = glyph_cache_dims;
@@ -361,6 +363,34 @@ impl Renderer {
         dpi_factor: f64,
         primitives: P,
     ) -> Result<Option<GlyphCacheCommand>, rt::gpu_cache::CacheWriteErr> {
+        self.fill_inner(image_map, None, viewport, dpi_factor, primitives)
+    }
+
+    /// Fill the inner vertex and command buffers by translating the given `primitives`, drawing
+    /// images that were inserted into the given `image::Atlas` from its pages.
+    ///
+    /// See `fill` for details on the returned `GlyphCacheCommand`. Afterwards, call
+    /// `Atlas::update_pages` to insert an `Image` for each modified page into the `image_map`
+    /// before calling `draw`.
+    pub fn fill_with_atlas<P: render::PrimitiveWalker>(
+        &mut self,
+        image_map: &image::Map<Image>,
+        atlas: &mut image::Atlas,
+        viewport: [f32; 4],
+        dpi_factor: f64,
+        primitives: P,
+    ) -> Result<Option<GlyphCacheCommand<'_>>, rt::gpu_cache::CacheWriteErr> {
+        self.fill_inner(image_map, Some(atlas), viewport, dpi_factor, primitives)
+    }
+
+    fn fill_inner<P: render::PrimitiveWalker>(
+        &mut self,
+        image_map: &image::Map<Image>,
+        atlas: Option<&mut image::Atlas>,
+        viewport: [f32; 4],
+        dpi_factor: f64,
+        primitives: P,
+    ) -> Result<Option<GlyphCacheCommand<'_>>, rt::gpu_cache::CacheWriteErr> {
         let Renderer {
             ref glyph_uploads,
             ref glyph_cache_tex,
@@ -371,7 +401,12 @@ impl Renderer {
         let lt = [vp_l as Scalar, vp_t as Scalar];
         let rb = [vp_r as Scalar, vp_b as Scalar];
         let viewport = Rect::from_corners(lt, rb);
-        let fill = mesh.fill(viewport, dpi_factor, image_map, primitives)?;
+        let fill = match atlas {
+            Some(atlas) => {
+                mesh.fill_with_atlas(viewport, dpi_factor, image_map, atlas, primitives)?
+            }
+            None => mesh.fill(viewport, dpi_factor, image_map, primitives)?,
+        };
         let glyph_cache_cmd = match fill.glyph_cache_requires_upload {
             false => None,
             true => Some(GlyphCacheCommand {
@@ -191,7 +191,7 @@ fn main() {
                 let [win_w, win_h]: [f32; 2] = [size.width as f32, size.height as f32];
                 let viewport = [0.0, 0.0, win_w, win_h];
                 if let Some(cmd) = renderer
-                    .fill(&image_map, viewport, scale_factor, primitives)
+                    .fill(&device, &image_map, viewport, scale_factor, primitives)
                     .unwrap()
                 {
                     cmd.load_buffer_and_encode(&device, &mut encoder);
@@ -157,8 +157,14 @@ impl Renderer {
             "wgpu glyph cache width must be multiple of 256"
         );
 
-        // The mesh for converting primitives into vertices.
-        let mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims);
+        // The mesh for converting primitives into vertices. The glyph cache may grow up to the
+        // largest texture supported by the device.
+        let mut mesh = Mesh::with_glyph_cache_dimensions(glyph_cache_dims);
+        let max_tex_dim = device.limits().max_texture_dimension_2d;
+        mesh.set_max_glyph_cache_dimensions([
+            max_tex_dim.max(glyph_cache_dims[0]),
+            max_tex_dim.max(glyph_cache_dims[1]),
+        ]);
 
         // Load shader modules.
         let vs_mod = device.create_shader_module(&wgpu::include_wgsl!("shaders/vert.wgsl"));
@@ -232,9 +238,56 @@ impl Renderer {
     /// This method may return an `Option<GlyphCacheCommand>`, in which case the user should use
     /// the contained `glyph_cpu_buffer_pool` to write the pixel data to the GPU, and then use a
     /// `copy_buffer_to_image` command to write the data to the given `glyph_cache_texture` image.
+    ///
+    /// If the text within the frame does not fit within the glyph cache, the cache grows and its
+    /// texture is reallocated using the given `device`.
     pub fn fill<'a, P>(
         &'a mut self,
+        device: &wgpu::Device,
+        image_map: &image::Map<Image>,
+        viewport: [f32; 4],
+        scale_factor: f64,
+        primitives: P,
+    ) -> Result<Option<GlyphCacheCommand<'a>>, rt::gpu_cache::CacheWriteErr>
+    where
+        P: render::PrimitiveWalker,
+    {
+        self.fill_inner(device, image_map, None, viewport, scale_factor, primitives)
+    }
+
+    /// Fill the inner vertex and command buffers by translating the given `primitives`, drawing
+    /// images that were inserted into the given `image::Atlas` from its pages.
+    ///
+    /// See `fill` for details on the returned `GlyphCacheCommand`. Afterwards, call
+    /// `Atlas::update_pages` to create each new page's `Image` within the `image_map` and to write
+    /// the pixels of each modified page to its texture before calling `render`.
+    pub fn fill_with_atlas<'a, P>(
+        &'a mut self,
+        device: &wgpu::Device,
+        image_map: &image::Map<Image>,
+        atlas: &mut image::Atlas,
+        viewport: [f32; 4],
+        scale_factor: f64,
+        primitives: P,
+    ) -> Result<Option<GlyphCacheCommand<'a>>, rt::gpu_cache::CacheWriteErr>
+    where
+        P: render::PrimitiveWalker,
+    {
+        self.fill_inner(
+            device,
+            image_map,
+            Some(atlas),
+            viewport,
+            scale_factor,
+            primitives,
+        )
+    }
+
+    fn fill_inner<'a, P>(
+        &'a mut self,
+        device: &wgpu::Device,
         image_map: &image::Map<Image>,
+        atlas: Option<&mut image::Atlas>,
         viewport: [f32; 4],
         scale_factor: f64,
         primitives: P,
@@ -247,9 +300,31 @@ impl Renderer {
         let lt = [vp_l as Scalar, vp_t as Scalar];
         let rb = [vp_r as Scalar, vp_b as Scalar];
         let viewport = Rect::from_corners(lt, rb);
-        let fill = self
-            .mesh
-            .fill(viewport, scale_factor, image_map, primitives)?;
+        let fill = match atlas {
+            Some(atlas) => {
+                self.mesh
+                    .fill_with_atlas(viewport, scale_factor, image_map, atlas, primitives)?
+            }
+            None => self
+                .mesh
+                .fill(viewport, scale_factor, image_map, primitives)?,
+        };
+
+        // If the glyph cache grew, reallocate its texture along with the bind groups using it.
+        if fill.glyph_cache_resized {
+            let (width, height) = self.mesh.glyph_cache().dimensions();
+            let glyph_cache_tex_desc = glyph_cache_tex_desc([width, height]);
+            self.glyph_cache_tex = device.create_texture(&glyph_cache_tex_desc);
+            let default_tct = DEFAULT_IMAGE_TEX_FORMAT.describe().sample_type;
+            self.default_bind_group = bind_group(
+                device,
+                &self.render_pipelines[&default_tct].bind_group_layout,
+                &self.glyph_cache_tex,
+                &self.sampler,
+                &self._default_image_tex,
+            );
+            self.bind_groups.clear();
+        }
 
         // Check whether or not we need a glyph cache update.
         let glyph_cache_cmd = match fill.glyph_cache_requires_upload {
@@ -36,7 +36,7 @@
 //! types (`Button`, `ControllerAxisArgs`, `Key`, etc).
 
 use input;
-use position::{Dimensions, Point};
+use position::{Dimensions, Point, Transform};
 use utils::vec2_sub;
 use widget;
 
@@ -345,6 +345,14 @@ impl Button {
             button => button,
         }
     }
+
+    /// Returns a copy of the Button with its position mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Button {
+        match *self {
+            Button::Mouse(m_button, self_xy) => Button::Mouse(m_button, transform.apply(self_xy)),
+            button => button,
+        }
+    }
 }
 
 impl Press {
@@ -356,6 +364,14 @@ impl Press {
         }
     }
 
+    /// Returns a copy of the Press with its position mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Press {
+        Press {
+            button: self.button.transformed(transform),
+            ..*self
+        }
+    }
+
     /// If the `Press` event represents the pressing of a mouse button, return `Some`.
     pub fn mouse(self) -> Option<MousePress> {
         match self.button {
@@ -389,6 +405,14 @@ impl Release {
         }
     }
 
+    /// Returns a copy of the Release with its position mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Release {
+        Release {
+            button: self.button.transformed(transform),
+            ..*self
+        }
+    }
+
     /// If the `Release` event represents the releasing of a mouse button, return `Some`.
     pub fn mouse(self) -> Option<MouseRelease> {
         match self.button {
@@ -421,6 +445,14 @@ impl Tap {
             ..*self
         }
     }
+
+    /// Returns a copy of the `Tap` with its position mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        Tap {
+            xy: transform.apply(self.xy),
+            ..*self
+        }
+    }
 }
 
 impl Click {
@@ -431,6 +463,14 @@ impl Click {
             ..*self
         }
     }
+
+    /// Returns a copy of the Click with its position mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Click {
+        Click {
+            xy: transform.apply(self.xy),
+            ..*self
+        }
+    }
 }
 
 impl DoubleClick {
@@ -441,6 +481,14 @@ impl DoubleClick {
             ..*self
         }
     }
+
+    /// Returns a copy of the DoubleClick with its position mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> DoubleClick {
+        DoubleClick {
+            xy: transform.apply(self.xy),
+            ..*self
+        }
+    }
 }
 
 impl Drag {
@@ -453,6 +501,18 @@ impl Drag {
             ..*self
         }
     }
+
+    /// Returns a copy of the Drag with its positions and deltas mapped by the given `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Drag {
+        Drag {
+            origin: transform.apply(self.origin),
+            from: transform.apply(self.from),
+            to: transform.apply(self.to),
+            delta_xy: transform.apply_vector(self.delta_xy),
+            total_delta_xy: transform.apply_vector(self.total_delta_xy),
+            ..*self
+        }
+    }
 }
 
 impl From<input::Motion> for Input {
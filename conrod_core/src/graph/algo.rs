@@ -7,7 +7,7 @@
 use super::{EdgeIndex, Graph};
 use daggy::Walker;
 use fnv;
-use position::{Point, Rect};
+use position::{Point, Rect, Transform};
 use theme::Theme;
 use widget;
 
@@ -47,22 +47,28 @@ impl PickWidgets {
                 None => break,
                 Some(&idx) => idx,
             };
+            // Map the `xy` into the widget's own coordinate space.
+            let xy = match transform_of_widget(graph, idx).inverse() {
+                None => continue,
+                Some(inverse) => inverse.apply(self.xy),
+            };
             let visible_rect = match cropped_area_of_widget(graph, idx) {
                 None => continue,
                 Some(rect) => rect,
             };
-            if !visible_rect.is_over(self.xy) {
+            if !visible_rect.is_over(xy) {
                 continue;
             }
             // Now that we know we're over the bounding box, we can check the more
             // detailed widget-specific `is_over` function.
             let mut id = idx;
+            let mut xy = xy;
             loop {
                 let container = match graph.widget(id) {
                     None => break,
                     Some(container) => container,
                 };
-                match (container.is_over.0)(&container, self.xy, theme) {
+                match (container.is_over.0)(&container, xy, theme) {
                     widget::IsOver::Bool(false) => break,
                     widget::IsOver::Bool(true) => return Some(id),
                     widget::IsOver::Widget(w_id) => {
@@ -72,6 +78,10 @@ impl PickWidgets {
                                              would cause an infinite loop"
                         );
                         id = w_id;
+                        xy = match transform_of_widget(graph, id).inverse() {
+                            None => break,
+                            Some(inverse) => inverse.apply(self.xy),
+                        };
                     }
                 }
             }
@@ -213,6 +223,49 @@ fn cropped_area_of_widget_maybe_within_depth(
     })
 }
 
+/// The opacity with which the widget with the given index is drawn.
+///
+/// This is the product of the widget's own opacity and the opacity of all of its depth parents.
+pub fn opacity_of_widget(graph: &Graph, idx: widget::Id) -> f32 {
+    let mut opacity = graph
+        .widget(idx)
+        .map(|widget| widget.opacity)
+        .unwrap_or(1.0);
+    let mut depth_parents = graph.depth_parent_recursion(idx);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        if let Some(depth_parent_widget) = graph.widget(depth_parent) {
+            opacity *= depth_parent_widget.opacity;
+        }
+    }
+    opacity
+}
+
+/// The transform of the widget with the given index.
+///
+/// This is the widget's own rotation and scale about the centre of its `Rect`, followed by that of
+/// each of its depth parents in turn, mapping points from the widget's own coordinate space to the
+/// window.
+pub fn transform_of_widget(graph: &Graph, idx: widget::Id) -> Transform {
+    let local_transform = |widget: &super::Container| {
+        if widget.rotation == 0.0 && widget.scale == [1.0, 1.0] {
+            Transform::IDENTITY
+        } else {
+            Transform::about(widget.rect.xy(), widget.rotation, widget.scale)
+        }
+    };
+    let mut transform = graph
+        .widget(idx)
+        .map(&local_transform)
+        .unwrap_or(Transform::IDENTITY);
+    let mut depth_parents = graph.depth_parent_recursion(idx);
+    while let Some(depth_parent) = depth_parents.next_node(graph) {
+        if let Some(depth_parent_widget) = graph.widget(depth_parent) {
+            transform = transform.then(local_transform(depth_parent_widget));
+        }
+    }
+    transform
+}
+
 /// Find the absolute `Rect` that bounds all widgets that are `Depth` children of the widget at the
 /// given `idx`.
 ///
@@ -4,7 +4,7 @@
 //! The primary type of interest in this module is the [**Graph**](./struct.Graph) type.
 
 use daggy;
-use position::{Axis, Depth, Point, Rect};
+use position::{Axis, Depth, Point, Rect, Scalar};
 use std;
 use std::any::Any;
 use std::ops::{Index, IndexMut};
@@ -87,6 +87,18 @@ pub struct Container {
     pub maybe_floating: Option<widget::Floating>,
     /// Whether or not children widgets should be cropped to the `kid_area`.
     pub crop_kids: bool,
+    /// The shape within the `kid_area` to which children widgets should be cropped.
+    pub crop_shape: widget::crop::Shape,
+    /// The opacity of the widget, not including the opacity of its depth parents.
+    ///
+    /// See `graph::algo::opacity_of_widget` for the opacity with which the widget is drawn.
+    pub opacity: f32,
+    /// The rotation of the widget in radians, not including the rotation of its depth parents.
+    ///
+    /// See `graph::algo::transform_of_widget` for the transform with which the widget is drawn.
+    pub rotation: Scalar,
+    /// The scale of the widget, not including the scale of its depth parents.
+    pub scale: [Scalar; 2],
     /// Scroll related state (is only `Some` if this axis is scrollable).
     pub maybe_x_scroll_state: Option<widget::scroll::StateX>,
     /// Scroll related state (is only `Some` if this axis is scrollable).
@@ -664,6 +676,10 @@ impl Graph {
             maybe_dragged_from,
             maybe_floating,
             crop_kids,
+            crop_shape,
+            opacity,
+            rotation,
+            scale,
             maybe_x_scroll_state,
             maybe_y_scroll_state,
             maybe_graphics_for,
@@ -686,6 +702,10 @@ impl Graph {
             maybe_dragged_from: maybe_dragged_from,
             maybe_floating: maybe_floating,
             crop_kids: crop_kids,
+            crop_shape: crop_shape,
+            opacity: opacity,
+            rotation: rotation,
+            scale: scale,
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_y_scroll_state: maybe_y_scroll_state,
             instantiation_order_idx: instantiation_order_idx,
@@ -750,6 +770,10 @@ impl Graph {
                 container.maybe_dragged_from = maybe_dragged_from;
                 container.maybe_floating = maybe_floating;
                 container.crop_kids = crop_kids;
+                container.crop_shape = crop_shape;
+                container.opacity = opacity;
+                container.rotation = rotation;
+                container.scale = scale;
                 container.maybe_x_scroll_state = maybe_x_scroll_state;
                 container.maybe_y_scroll_state = maybe_y_scroll_state;
                 container.instantiation_order_idx = instantiation_order_idx;
@@ -1,10 +1,15 @@
 //! A type used to manage a user's image data and map them to `Image` widgets:
 //!
 //! - [Map](./struct.Map.html)
+//! - [Atlas](./atlas/struct.Atlas.html)
 
 use fnv;
 use std;
 
+pub use self::atlas::Atlas;
+
+pub mod atlas;
+
 /// Unique image identifier.
 ///
 /// Throughout conrod, images are referred to via their unique `Id`. By referring to images via
@@ -68,9 +73,7 @@ impl<Img> Map<Img> {
     /// Note: Calling this will trigger a redraw the next time `Ui::draw_if_changed` is called.
     pub fn insert(&mut self, img: Img) -> Id {
         self.trigger_redraw.set(true);
-        let index = self.next_index;
-        self.next_index = index.wrapping_add(1);
-        let id = Id(index);
+        let id = self.next_id();
         self.map.insert(id, img);
         id
     }
@@ -112,6 +115,13 @@ impl<Img> Map<Img> {
             index_range: start_index..end_index,
         }
     }
+
+    // Produce a new unique `Id` without inserting an image, e.g. for images within an `Atlas`.
+    fn next_id(&mut self) -> Id {
+        let index = self.next_index;
+        self.next_index = index.wrapping_add(1);
+        Id(index)
+    }
 }
 
 impl Iterator for NewIds {
@@ -0,0 +1,387 @@
+//! An optional texture atlas for batching many small images into few textures.
+//!
+//! Each image inserted into an `image::Map` is usually its own texture, requiring a separate draw
+//! command whenever the image being drawn changes. The `Atlas` instead stores the RGBA pixels of
+//! many small images and packs them into shared *pages* on demand. Each page is a single texture
+//! referred to by an `image::Id` reserved within the user's `image::Map`, allowing the
+//! `mesh::Mesh` to batch all images resident within the same page into a single draw command.
+//!
+//! Typical usage looks like:
+//!
+//! 1. Construct the `Atlas`, reserving the `image::Id` of each page within the `image::Map`.
+//! 2. `insert` each small image, storing the returned `image::Id` for use with `Image` widgets.
+//! 3. Each frame, fill the `Mesh` via `Mesh::fill_with_atlas`, then call `Atlas::update_pages`
+//!    to create or update the texture of each modified page within the `image::Map`. The wgpu and
+//!    vulkano renderers provide a `fill_with_atlas` method for this, while the rendy `UiPipeline`
+//!    uses the atlas returned by `UiAux::image_atlas` and uploads its pages itself.
+//!
+//! The atlas grows by adding pages as they are required, up to a fixed maximum number of pages.
+//! Pages are never resized. Once the maximum is reached, room is made according to the `Eviction`
+//! policy.
+
+use super::{Id, Map};
+use fnv;
+use position::Rect;
+use std;
+
+/// The number of transparent pixels surrounding each image within a page.
+///
+/// The edges of each image are extruded into this padding so that neighbouring images do not
+/// bleed into one another when sampled with linear filtering.
+pub const PADDING: u32 = 1;
+
+/// The default dimensions of each page in pixels.
+pub const DEFAULT_PAGE_DIMENSIONS: [u32; 2] = [1_024; 2];
+
+/// The default maximum number of pages.
+pub const DEFAULT_MAX_PAGES: usize = 4;
+
+/// Packs many small RGBA images into shared texture pages.
+pub struct Atlas {
+    page_dimensions: [u32; 2],
+    page_ids: Vec<Id>,
+    pages: Vec<Page>,
+    eviction: Eviction,
+    entries: fnv::FnvHashMap<Id, Entry>,
+    frame: u64,
+}
+
+/// How the `Atlas` makes room for images when all of its pages are full.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Eviction {
+    /// Images that do not fit are not drawn.
+    None,
+    /// The page that was least recently drawn from is cleared, allowing its images to be packed
+    /// again the next time they are drawn. Pages drawn from during the current frame are never
+    /// evicted.
+    LeastRecentlyUsedPage,
+}
+
+/// The location of some image within a page of the `Atlas`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Location {
+    /// The `image::Id` of the page's texture within the `image::Map`.
+    pub page_id: Id,
+    /// The dimensions of the page in pixels.
+    pub page_dimensions: [u32; 2],
+    /// The area of the page occupied by the image in pixels, where `[0.0, 0.0]` is the bottom
+    /// left of the page.
+    pub rect: Rect,
+}
+
+/// Describes a page whose texture must be created or updated.
+#[derive(Copy, Clone, Debug)]
+pub struct PageUpdate<'a> {
+    /// The `image::Id` reserved for the page's texture within the `image::Map`.
+    pub page_id: Id,
+    /// The dimensions of the page in pixels.
+    pub dimensions: [u32; 2],
+    /// The RGBA8 pixels of the page, row by row from the top left.
+    pub rgba: &'a [u8],
+    /// Whether or not this is the first update for this page, i.e. its texture must be created.
+    pub is_new: bool,
+}
+
+/// The errors that might occur when inserting an image into the `Atlas`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InsertError {
+    /// The image, along with its padding, is larger than a page.
+    TooLarge,
+    /// The image has no area or the length of its pixel data does not match its dimensions.
+    InvalidLength,
+}
+
+// A registered image along with its location if it is resident within a page.
+struct Entry {
+    dimensions: [u32; 2],
+    rgba: Vec<u8>,
+    resident: Option<(usize, [u32; 2])>,
+}
+
+// A row of images within a page, all placed along the same top edge.
+struct Shelf {
+    y: u32,
+    height: u32,
+    x: u32,
+}
+
+struct Page {
+    rgba: Vec<u8>,
+    shelves: Vec<Shelf>,
+    last_used: u64,
+    requires_upload: bool,
+    is_new: bool,
+}
+
+impl Atlas {
+    /// Construct an `Atlas` with the default page dimensions and maximum number of pages.
+    ///
+    /// The `image::Id` for each page is reserved within the given `image::Map`.
+    pub fn new<Img>(image_map: &mut Map<Img>) -> Self {
+        Atlas::with_pages(image_map, DEFAULT_PAGE_DIMENSIONS, DEFAULT_MAX_PAGES)
+    }
+
+    /// Construct an `Atlas` whose pages have the given dimensions.
+    ///
+    /// Pages are only created as they are required, up to the given `max_pages`. Pages always have
+    /// the given dimensions and are never resized, so once `max_pages` pages exist, room is only
+    /// made by the atlas' `Eviction` policy.
+    pub fn with_pages<Img>(
+        image_map: &mut Map<Img>,
+        page_dimensions: [u32; 2],
+        max_pages: usize,
+    ) -> Self {
+        let page_ids = (0..max_pages).map(|_| image_map.next_id()).collect();
+        Atlas {
+            page_dimensions: page_dimensions,
+            page_ids: page_ids,
+            pages: Vec::new(),
+            eviction: Eviction::LeastRecentlyUsedPage,
+            entries: fnv::FnvHashMap::default(),
+            frame: 0,
+        }
+    }
+
+    /// Specify how room is made for images when all pages are full.
+    pub fn eviction(mut self, eviction: Eviction) -> Self {
+        self.eviction = eviction;
+        self
+    }
+
+    /// Insert an image with the given dimensions and RGBA8 pixels, row by row from the top left.
+    ///
+    /// The image is not packed into a page until it is first drawn. The returned `image::Id` is
+    /// unique within the given `image::Map` and may be used with the `Image` widget as usual.
+    pub fn insert<Img>(
+        &mut self,
+        image_map: &mut Map<Img>,
+        dimensions: [u32; 2],
+        rgba: Vec<u8>,
+    ) -> Result<Id, InsertError> {
+        let [w, h] = dimensions;
+        if w == 0 || h == 0 || rgba.len() != w as usize * h as usize * 4 {
+            return Err(InsertError::InvalidLength);
+        }
+        if w + PADDING * 2 > self.page_dimensions[0] || h + PADDING * 2 > self.page_dimensions[1] {
+            return Err(InsertError::TooLarge);
+        }
+        let id = image_map.next_id();
+        let entry = Entry {
+            dimensions: dimensions,
+            rgba: rgba,
+            resident: None,
+        };
+        self.entries.insert(id, entry);
+        Ok(id)
+    }
+
+    /// Remove the image with the given `image::Id`, returning `true` if it was within the atlas.
+    ///
+    /// The area of the page occupied by the image is only reclaimed when the page is evicted.
+    pub fn remove(&mut self, id: Id) -> bool {
+        self.entries.remove(&id).is_some()
+    }
+
+    /// Whether or not the image with the given `image::Id` was inserted into the atlas.
+    pub fn contains(&self, id: Id) -> bool {
+        self.entries.contains_key(&id)
+    }
+
+    /// The `image::Id`s reserved for each of the atlas' pages.
+    pub fn page_ids(&self) -> &[Id] {
+        &self.page_ids
+    }
+
+    /// The dimensions of each page in pixels.
+    pub fn page_dimensions(&self) -> [u32; 2] {
+        self.page_dimensions
+    }
+
+    /// The number of pages that have been created.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Begin a new frame. Pages used during previous frames become candidates for eviction.
+    ///
+    /// This is called by `Mesh::fill_with_atlas`.
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Locate the image with the given `image::Id`, packing it into a page if necessary.
+    ///
+    /// Returns `None` if the image is not within the atlas or if there is no room for it.
+    pub fn locate(&mut self, id: Id) -> Option<Location> {
+        let frame = self.frame;
+        let (page_index, top_left) = match self.entries.get(&id)?.resident {
+            Some(resident) => resident,
+            None => self.pack(id)?,
+        };
+        self.pages[page_index].last_used = frame;
+        let [w, h] = self.entries[&id].dimensions;
+        let page_h = self.page_dimensions[1] as f64;
+        let left = top_left[0] as f64;
+        let bottom = page_h - (top_left[1] + h) as f64;
+        Some(Location {
+            page_id: self.page_ids[page_index],
+            page_dimensions: self.page_dimensions,
+            rect: Rect::from_corners([left, bottom], [left + w as f64, bottom + h as f64]),
+        })
+    }
+
+    /// Call `f` for each page that has been created or modified since the last call.
+    ///
+    /// The user should create or update the texture for each page and insert it into the
+    /// `image::Map` with the page's `image::Id` via `image::Map::replace`.
+    pub fn update_pages<F>(&mut self, mut f: F)
+    where
+        F: FnMut(PageUpdate),
+    {
+        let Atlas {
+            ref mut pages,
+            ref page_ids,
+            page_dimensions,
+            ..
+        } = *self;
+        for (page, &page_id) in pages.iter_mut().zip(page_ids) {
+            if page.requires_upload {
+                f(PageUpdate {
+                    page_id: page_id,
+                    dimensions: page_dimensions,
+                    rgba: &page.rgba,
+                    is_new: page.is_new,
+                });
+                page.requires_upload = false;
+                page.is_new = false;
+            }
+        }
+    }
+
+    // Pack the image into a page, creating or evicting a page if necessary.
+    fn pack(&mut self, id: Id) -> Option<(usize, [u32; 2])> {
+        let [w, h] = self.entries[&id].dimensions;
+        let size = [w + PADDING * 2, h + PADDING * 2];
+        let page_dimensions = self.page_dimensions;
+
+        let mut placement = None;
+        for (i, page) in self.pages.iter_mut().enumerate() {
+            if let Some(top_left) = page.allocate(size, page_dimensions) {
+                placement = Some((i, top_left));
+                break;
+            }
+        }
+        if placement.is_none() && self.pages.len() < self.page_ids.len() {
+            let mut page = Page::new(page_dimensions);
+            let top_left = page.allocate(size, page_dimensions);
+            self.pages.push(page);
+            placement = top_left.map(|top_left| (self.pages.len() - 1, top_left));
+        }
+        if placement.is_none() && self.eviction == Eviction::LeastRecentlyUsedPage {
+            let frame = self.frame;
+            let lru = self
+                .pages
+                .iter()
+                .enumerate()
+                .filter(|&(_, page)| page.last_used < frame)
+                .min_by_key(|&(_, page)| page.last_used)
+                .map(|(i, _)| i);
+            if let Some(i) = lru {
+                self.evict(i);
+                placement = self.pages[i]
+                    .allocate(size, page_dimensions)
+                    .map(|top_left| (i, top_left));
+            }
+        }
+
+        let (page_index, [x, y]) = placement?;
+        let content = [x + PADDING, y + PADDING];
+        let entry = self.entries.get_mut(&id).expect("no entry for image");
+        self.pages[page_index].write(content, entry.dimensions, &entry.rgba, page_dimensions);
+        entry.resident = Some((page_index, content));
+        Some((page_index, content))
+    }
+
+    fn evict(&mut self, page_index: usize) {
+        for entry in self.entries.values_mut() {
+            if let Some((i, _)) = entry.resident {
+                if i == page_index {
+                    entry.resident = None;
+                }
+            }
+        }
+        let page = &mut self.pages[page_index];
+        page.shelves.clear();
+        for byte in page.rgba.iter_mut() {
+            *byte = 0;
+        }
+        page.requires_upload = true;
+    }
+}
+
+impl Page {
+    fn new([w, h]: [u32; 2]) -> Self {
+        Page {
+            rgba: vec![0; w as usize * h as usize * 4],
+            shelves: Vec::new(),
+            last_used: 0,
+            requires_upload: true,
+            is_new: true,
+        }
+    }
+
+    // Find room for an area of the given size, returning its top left corner.
+    fn allocate(&mut self, [w, h]: [u32; 2], [page_w, page_h]: [u32; 2]) -> Option<[u32; 2]> {
+        // Use the shortest shelf that fits to limit wasted space.
+        let best = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= h && page_w - shelf.x >= w)
+            .min_by_key(|shelf| shelf.height);
+        if let Some(shelf) = best {
+            let x = shelf.x;
+            shelf.x += w;
+            return Some([x, shelf.y]);
+        }
+        let y = self.shelves.last().map(|s| s.y + s.height).unwrap_or(0);
+        if page_h - y < h || page_w < w {
+            return None;
+        }
+        self.shelves.push(Shelf {
+            y: y,
+            height: h,
+            x: w,
+        });
+        Some([0, y])
+    }
+
+    // Copy the image into the page at the given top left corner, extruding its edges into the
+    // surrounding padding.
+    fn write(&mut self, [x, y]: [u32; 2], [w, h]: [u32; 2], rgba: &[u8], [page_w, _]: [u32; 2]) {
+        let pad = PADDING as i64;
+        for row in -pad..h as i64 + pad {
+            let src_row = row.max(0).min(h as i64 - 1) as usize;
+            for col in -pad..w as i64 + pad {
+                let src_col = col.max(0).min(w as i64 - 1) as usize;
+                let src = (src_row * w as usize + src_col) * 4;
+                let dst_x = (x as i64 + col) as usize;
+                let dst_y = (y as i64 + row) as usize;
+                let dst = (dst_y * page_w as usize + dst_x) * 4;
+                self.rgba[dst..dst + 4].copy_from_slice(&rgba[src..src + 4]);
+            }
+        }
+        self.requires_upload = true;
+    }
+}
+
+impl std::error::Error for InsertError {}
+
+impl std::fmt::Display for InsertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let s = match *self {
+            InsertError::TooLarge => "The image is too large to fit within a page of the atlas.",
+            InsertError::InvalidLength => "The length of the pixel data does not match the image.",
+        };
+        write!(f, "{}", s)
+    }
+}
@@ -5,6 +5,7 @@
 
 use event;
 use input;
+use position::Transform;
 use utils;
 use widget;
 use {Point, Rect};
@@ -20,6 +21,8 @@ use {Point, Rect};
 pub struct Widget<'a> {
     global: &'a input::Global,
     rect: Rect,
+    /// Maps window coordinates into the widget's own untransformed coordinate space.
+    inverse_transform: Transform,
     idx: widget::Id,
 }
 
@@ -27,6 +30,7 @@ pub struct Widget<'a> {
 #[derive(Copy, Clone, Debug)]
 pub struct Mouse<'a> {
     rect: Rect,
+    inverse_transform: Transform,
     mouse_abs_xy: Point,
     /// The state of each `MouseButton`.
     pub buttons: &'a input::state::mouse::ButtonMap,
@@ -45,6 +49,7 @@ pub struct Events<'a> {
     capturing_keyboard: Option<widget::Id>,
     capturing_mouse: Option<widget::Id>,
     rect: Rect,
+    inverse_transform: Transform,
     idx: widget::Id,
 }
 
@@ -164,9 +169,25 @@ impl<'a> Widget<'a> {
     ///
     /// All events will also be made relative to the widget's own (0, 0) origin.
     pub fn for_widget(idx: widget::Id, rect: Rect, global: &'a input::Global) -> Self {
+        Widget::for_transformed_widget(idx, rect, Transform::IDENTITY, global)
+    }
+
+    /// Returns a `Widget` with events specifically for the given widget, where the widget is
+    /// drawn with the given `transform` (see `graph::algo::transform_of_widget`).
+    ///
+    /// All events will be mapped through the inverse of the `transform` before being made relative
+    /// to the widget's own (0, 0) origin. If the `transform` is not invertible, events are left
+    /// untransformed.
+    pub fn for_transformed_widget(
+        idx: widget::Id,
+        rect: Rect,
+        transform: Transform,
+        global: &'a input::Global,
+    ) -> Self {
         Widget {
             global: global,
             rect: rect,
+            inverse_transform: transform.inverse().unwrap_or(Transform::IDENTITY),
             idx: idx,
         }
     }
@@ -180,6 +201,7 @@ impl<'a> Widget<'a> {
                 buttons: &self.global.current.mouse.buttons,
                 mouse_abs_xy: self.global.current.mouse.xy,
                 rect: self.rect,
+                inverse_transform: self.inverse_transform,
             };
             return Some(mouse);
         }
@@ -199,6 +221,7 @@ impl<'a> Widget<'a> {
             capturing_keyboard: self.global.start.widget_capturing_keyboard,
             capturing_mouse: self.global.start.widget_capturing_mouse,
             rect: self.rect,
+            inverse_transform: self.inverse_transform,
             idx: self.idx,
         }
     }
@@ -273,12 +296,14 @@ impl<'a> Mouse<'a> {
 
     /// The position of the mouse relative to the middle of the widget's `Rect`.
     pub fn rel_xy(&self) -> Point {
-        utils::vec2_sub(self.mouse_abs_xy, self.rect.xy())
+        let xy = self.inverse_transform.apply(self.mouse_abs_xy);
+        utils::vec2_sub(xy, self.rect.xy())
     }
 
     /// Is the mouse currently over the widget.
     pub fn is_over(&self) -> bool {
-        self.rect.is_over(self.mouse_abs_xy)
+        let xy = self.inverse_transform.apply(self.mouse_abs_xy);
+        self.rect.is_over(xy)
     }
 }
 
@@ -444,31 +469,71 @@ impl<'a> Iterator for Events<'a> {
                 }
 
                 event::Ui::Touch(idx, ref touch) if idx == Some(self.idx) => {
-                    return Some(touch.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        touch
+                            .clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::Press(idx, ref press) if idx == Some(self.idx) => {
-                    return Some(press.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        press
+                            .clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::Release(idx, ref release) if idx == Some(self.idx) => {
-                    return Some(release.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        release
+                            .clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::Click(idx, ref click) if idx == Some(self.idx) => {
-                    return Some(click.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        click
+                            .clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) => {
-                    return Some(double_click.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        double_click
+                            .clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::Tap(idx, ref tap) if idx == Some(self.idx) => {
-                    return Some(tap.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        tap.clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) => {
-                    return Some(drag.clone().relative_to(self.rect.xy()).into())
+                    return Some(
+                        drag.clone()
+                            .transformed(&self.inverse_transform)
+                            .relative_to(self.rect.xy())
+                            .into(),
+                    )
                 }
 
                 event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) => {
@@ -13,9 +13,19 @@ extern crate conrod_derive;
 extern crate copypasta;
 extern crate daggy;
 extern crate fnv;
+#[cfg(feature = "gif")]
+extern crate gif;
 extern crate input as piston_input;
 extern crate num;
+#[cfg(feature = "apng")]
+extern crate png;
 extern crate rusttype;
+#[cfg(feature = "shaping")]
+extern crate rustybuzz;
+extern crate unicode_bidi;
+extern crate unicode_linebreak;
+extern crate unicode_segmentation;
+extern crate xml;
 
 pub use border::{Borderable, Bordering};
 pub use color::{Color, Colorable};
@@ -6,8 +6,9 @@
 //! whether or not the `Scizzor` should be updated between draws.
 
 use crate::text::{self, rt};
+use crate::widget::crop;
 use crate::{color, image, render};
-use crate::{Rect, Scalar};
+use crate::{Point, Rect, Scalar};
 use std::{fmt, ops};
 
 /// Images within the given image map must know their dimensions in pixels.
@@ -23,9 +24,12 @@ pub trait ImageDimensions {
 pub struct Mesh {
     glyph_cache: GlyphCache,
     glyph_cache_pixel_buffer: Vec<u8>,
+    max_glyph_cache_dims: [u32; 2],
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
-    positioned_glyphs: Vec<text::PositionedGlyph>,
+    positioned_glyphs: Vec<(text::font::Id, text::PositionedGlyph)>,
+    text_glyphs: Vec<TextGlyph>,
+    image_quads: Vec<(Rect, Rect)>,
 }
 
 /// Represents the scizzor in pixel coordinates.
@@ -97,11 +101,30 @@ pub struct Vertex {
 pub struct Fill {
     /// Whether or not the glyph cache pixel data should be written to the GPU.
     pub glyph_cache_requires_upload: bool,
+    /// Whether or not some images within the `image::Atlas` could not be drawn as there was no
+    /// room for them within its pages.
+    pub atlas_overflow: bool,
+    /// Whether or not the glyph cache grew in order to fit all of the text within the frame.
+    ///
+    /// If `true`, the GPU texture must be reallocated with the new `glyph_cache().dimensions()`
+    /// before the pixel buffer is uploaded to it.
+    pub glyph_cache_resized: bool,
 }
 
 // A wrapper around an owned glyph cache, providing `Debug` and `Deref` impls.
 struct GlyphCache(text::GlyphCache<'static>);
 
+// A glyph drawn during `fill` along with the range of vertices describing its quad.
+//
+// Until the glyph is cached, the texture coordinates of these vertices describe the position
+// within the glyph's own rect rather than within the glyph cache.
+#[derive(Debug)]
+struct TextGlyph {
+    font_id: text::font::Id,
+    glyph: text::PositionedGlyph,
+    vertices: std::ops::Range<usize>,
+}
+
 #[derive(Debug)]
 enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
@@ -119,6 +142,9 @@ pub const MODE_GEOMETRY: u32 = 2;
 /// Default dimensions to use for the glyph cache.
 pub const DEFAULT_GLYPH_CACHE_DIMS: [u32; 2] = [1_024; 2];
 
+/// Default limit up to which the glyph cache may grow when the text within a frame does not fit.
+pub const DEFAULT_MAX_GLYPH_CACHE_DIMS: [u32; 2] = [8_192; 2];
+
 impl Mesh {
     /// Construct a new empty `Mesh` with default glyph cache dimensions.
     pub fn new() -> Self {
@@ -139,16 +165,40 @@ impl Mesh {
         let glyph_cache_pixel_buffer = vec![0u8; gc_width as usize * gc_height as usize];
         let commands = vec![];
         let vertices = vec![];
+        let max_glyph_cache_dims = [
+            gc_width.max(DEFAULT_MAX_GLYPH_CACHE_DIMS[0]),
+            gc_height.max(DEFAULT_MAX_GLYPH_CACHE_DIMS[1]),
+        ];
         let positioned_glyphs = vec![];
+        let text_glyphs = vec![];
+        let image_quads = vec![];
         Mesh {
             glyph_cache,
             glyph_cache_pixel_buffer,
+            max_glyph_cache_dims,
             commands,
             vertices,
             positioned_glyphs,
+            text_glyphs,
+            image_quads,
         }
     }
 
+    /// The dimensions up to which the glyph cache may grow.
+    pub fn max_glyph_cache_dimensions(&self) -> [u32; 2] {
+        self.max_glyph_cache_dims
+    }
+
+    /// Limit the dimensions up to which the glyph cache may grow.
+    ///
+    /// When the text within a frame does not fit within the glyph cache, the cache is doubled in
+    /// size until either the text fits or these dimensions are reached, in which case `fill`
+    /// returns the `CacheWriteErr`. Backends should set this to the largest texture size supported
+    /// by the GPU. Setting it to the current dimensions disables growth.
+    pub fn set_max_glyph_cache_dimensions(&mut self, dims: [u32; 2]) {
+        self.max_glyph_cache_dims = dims;
+    }
+
     /// Fill the inner vertex buffer from the given primitives.
     ///
     /// - `viewport`: the window in which the UI is drawn. The width and height should be the
@@ -162,6 +212,43 @@ impl Mesh {
         viewport: Rect,
         dpi_factor: f64,
         image_map: &image::Map<I>,
+        primitives: P,
+    ) -> Result<Fill, rt::gpu_cache::CacheWriteErr>
+    where
+        P: render::PrimitiveWalker,
+        I: ImageDimensions,
+    {
+        self.fill_inner(viewport, dpi_factor, image_map, None, primitives)
+    }
+
+    /// Fill the inner vertex buffer from the given primitives, drawing images that were inserted
+    /// into the given `image::Atlas` from its pages.
+    ///
+    /// Images are packed into the atlas' pages as they are drawn. Consecutive images within the
+    /// same page are batched into a single `Draw::Image` command referring to the page's
+    /// `image::Id`. Call `Atlas::update_pages` afterwards to upload any modified pages.
+    pub fn fill_with_atlas<P, I>(
+        &mut self,
+        viewport: Rect,
+        dpi_factor: f64,
+        image_map: &image::Map<I>,
+        atlas: &mut image::Atlas,
+        primitives: P,
+    ) -> Result<Fill, rt::gpu_cache::CacheWriteErr>
+    where
+        P: render::PrimitiveWalker,
+        I: ImageDimensions,
+    {
+        atlas.begin_frame();
+        self.fill_inner(viewport, dpi_factor, image_map, Some(atlas), primitives)
+    }
+
+    fn fill_inner<P, I>(
+        &mut self,
+        viewport: Rect,
+        dpi_factor: f64,
+        image_map: &image::Map<I>,
+        mut atlas: Option<&mut image::Atlas>,
         mut primitives: P,
     ) -> Result<Fill, rt::gpu_cache::CacheWriteErr>
     where
@@ -171,13 +258,17 @@ impl Mesh {
         let Mesh {
             ref mut glyph_cache,
             ref mut glyph_cache_pixel_buffer,
+            max_glyph_cache_dims,
             ref mut commands,
             ref mut vertices,
             ref mut positioned_glyphs,
+            ref mut text_glyphs,
+            ref mut image_quads,
         } = *self;
 
         commands.clear();
         vertices.clear();
+        text_glyphs.clear();
 
         enum State {
             Image { image_id: image::Id, start: usize },
@@ -189,15 +280,14 @@ impl Mesh {
         // Keep track of whether or not the glyph cache texture needs to be updated.
         let mut glyph_cache_requires_upload = false;
 
+        // Keep track of whether or not any images did not fit within the atlas.
+        let mut atlas_overflow = false;
+
         // Viewport dimensions and the "dots per inch" factor.
         let (viewport_w, viewport_h) = viewport.w_h();
         let half_viewport_w = viewport_w / 2.0;
         let half_viewport_h = viewport_h / 2.0;
 
-        // Width of the glyph cache is useful when writing to the pixel buffer.
-        let (glyph_cache_w, _) = glyph_cache.dimensions();
-        let glyph_cache_w = glyph_cache_w as usize;
-
         // Functions for converting for conrod scalar coords to normalised vertex coords (-1.0 to 1.0).
         let vx = |x: Scalar| (x * dpi_factor / half_viewport_w) as f32;
         let vy = |y: Scalar| -1.0 * (y * dpi_factor / half_viewport_h) as f32;
@@ -242,6 +332,8 @@ impl Mesh {
             let render::Primitive {
                 kind,
                 scizzor,
+                crop_polygon,
+                transform,
                 rect,
                 ..
             } = primitive;
@@ -276,6 +368,30 @@ impl Mesh {
                 continue;
             }
 
+            // Keep track of where this primitive's vertices begin in case they must be cropped.
+            let primitive_start = vertices.len();
+
+            // Maps the vertices from `start` to the window if the primitive is rotated or scaled
+            // and crops them to the crop polygon if there is one.
+            let crop_polygon: Option<Vec<Point>> = crop_polygon.map(|polygon| {
+                polygon
+                    .iter()
+                    .map(|p| [vx(p[0]) as Scalar, vy(p[1]) as Scalar])
+                    .collect()
+            });
+            let place = |vertices: &mut Vec<Vertex>, start: usize| {
+                if !transform.is_identity() {
+                    // The *y* axis of the viewport points downwards.
+                    let half_window = [half_viewport_w / dpi_factor, -half_viewport_h / dpi_factor];
+                    for vertex in &mut vertices[start..] {
+                        vertex.position = transform.apply_ndc(vertex.position, half_window);
+                    }
+                }
+                if let Some(ref polygon) = crop_polygon {
+                    crop_vertices(vertices, start, polygon);
+                }
+            };
+
             match kind {
                 render::PrimitiveKind::Rectangle { color } => {
                     switch_to_plain_state!();
@@ -350,40 +466,14 @@ impl Mesh {
                     }
                 }
 
-                render::PrimitiveKind::Text {
-                    color,
-                    text,
-                    font_id,
-                } => {
+                render::PrimitiveKind::Text { color, text, .. } => {
                     switch_to_plain_state!();
 
                     positioned_glyphs.clear();
-                    positioned_glyphs.extend(text.positioned_glyphs(dpi_factor as f32));
-
-                    // Queue the glyphs to be cached
-                    for glyph in positioned_glyphs.iter() {
-                        glyph_cache.queue_glyph(font_id.index(), glyph.clone());
-                    }
-
-                    glyph_cache.cache_queued(|rect, data| {
-                        let width = (rect.max.x - rect.min.x) as usize;
-                        let height = (rect.max.y - rect.min.y) as usize;
-                        let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
-                        let mut src_ix = 0;
-                        for _ in 0..height {
-                            let dst_range = dst_ix..dst_ix + width;
-                            let src_range = src_ix..src_ix + width;
-                            let dst_slice = &mut glyph_cache_pixel_buffer[dst_range];
-                            let src_slice = &data[src_range];
-                            dst_slice.copy_from_slice(src_slice);
-                            dst_ix += glyph_cache_w;
-                            src_ix += width;
-                        }
-                        glyph_cache_requires_upload = true;
-                    })?;
+                    positioned_glyphs
+                        .extend(text.positioned_glyphs_with_font_ids(dpi_factor as f32));
 
                     let color = gamma_srgb_to_linear(color.to_fsa());
-                    let cache_id = font_id.index();
                     let origin = rt::point(0.0, 0.0);
 
                     // A closure to convert RustType rects to GL rects
@@ -400,57 +490,79 @@ impl Mesh {
                             )) * 2.0,
                     };
 
-                    for g in positioned_glyphs.drain(..) {
-                        if let Ok(Some((uv_rect, screen_rect))) = glyph_cache.rect_for(cache_id, &g)
-                        {
-                            let vk_rect = to_vk_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                position: p,
-                                tex_coords: t,
-                                rgba: color,
-                                mode: MODE_TEXT,
-                            };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v(
-                                [vk_rect.min.x, vk_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [vk_rect.min.x, vk_rect.min.y],
-                                [uv_rect.min.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.max.y],
-                                [uv_rect.max.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [vk_rect.min.x, vk_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                        }
+                    // The glyphs are cached once all text within the frame is known, so each quad
+                    // is placed now and mapped into the glyph cache at the end of the fill.
+                    for (font_id, glyph) in positioned_glyphs.drain(..) {
+                        let screen_rect = match glyph.pixel_bounding_box() {
+                            Some(rect) => rect,
+                            None => continue,
+                        };
+                        let vk_rect = to_vk_rect(screen_rect);
+                        let v = |p, t| Vertex {
+                            position: p,
+                            tex_coords: t,
+                            rgba: color,
+                            mode: MODE_TEXT,
+                        };
+                        let start = vertices.len();
+                        let mut push_v = |p, t| vertices.push(v(p, t));
+                        push_v([vk_rect.min.x, vk_rect.max.y], [0.0, 1.0]);
+                        push_v([vk_rect.min.x, vk_rect.min.y], [0.0, 0.0]);
+                        push_v([vk_rect.max.x, vk_rect.min.y], [1.0, 0.0]);
+                        push_v([vk_rect.max.x, vk_rect.min.y], [1.0, 0.0]);
+                        push_v([vk_rect.max.x, vk_rect.max.y], [1.0, 1.0]);
+                        push_v([vk_rect.min.x, vk_rect.max.y], [0.0, 1.0]);
+
+                        // Place each quad separately so that its vertex range remains known.
+                        place(vertices, start);
+                        let vertices = start..vertices.len();
+                        let glyph = TextGlyph {
+                            font_id,
+                            glyph,
+                            vertices,
+                        };
+                        text_glyphs.push(glyph);
                     }
+
+                    continue;
                 }
 
                 render::PrimitiveKind::Image {
                     image_id,
                     color,
                     source_rect,
+                    fit,
+                    x_align,
+                    y_align,
                 } => {
-                    let image_ref = match image_map.get(&image_id) {
-                        None => continue,
-                        Some(img) => img,
+                    // Images within the atlas are drawn from their page instead, offset by their
+                    // location within the page.
+                    let (new_image_id, image_dim, texture_dim, offset) = match atlas {
+                        Some(ref mut atlas) if atlas.contains(image_id) => {
+                            match atlas.locate(image_id) {
+                                Some(location) => {
+                                    let [page_w, page_h] = location.page_dimensions;
+                                    let page_dim = [page_w as Scalar, page_h as Scalar];
+                                    let offset = location.rect.bottom_left();
+                                    (location.page_id, location.rect.dim(), page_dim, offset)
+                                }
+                                None => {
+                                    atlas_overflow = true;
+                                    continue;
+                                }
+                            }
+                        }
+                        _ => match image_map.get(&image_id) {
+                            Some(img) => {
+                                let [w, h] = img.dimensions();
+                                let dim = [w as Scalar, h as Scalar];
+                                (image_id, dim, dim, [0.0, 0.0])
+                            }
+                            None => continue,
+                        },
                     };
 
                     // Switch to the `Image` state for this image if we're not in it already.
-                    let new_image_id = image_id;
                     match current_state {
                         // If we're already in the drawing mode for this image, we're done.
                         State::Image { image_id, .. } if image_id == new_image_id => (),
@@ -475,26 +587,12 @@ impl Mesh {
                     }
 
                     let color = color.unwrap_or(color::WHITE).to_fsa();
-                    let [image_w, image_h] = image_ref.dimensions();
-                    let (image_w, image_h) = (image_w as Scalar, image_h as Scalar);
-
-                    // Get the sides of the source rectangle as uv coordinates.
-                    //
-                    // Texture coordinates range:
-                    // - left to right: 0.0 to 1.0
-                    // - bottom to top: 1.0 to 0.0
-                    let (uv_l, uv_r, uv_b, uv_t) = match source_rect {
-                        Some(src_rect) => {
-                            let (l, r, b, t) = src_rect.l_r_b_t();
-                            (
-                                (l / image_w) as f32,
-                                (r / image_w) as f32,
-                                1.0 - (b / image_h) as f32,
-                                1.0 - (t / image_h) as f32,
-                            )
-                        }
-                        None => (0.0, 1.0, 1.0, 0.0),
-                    };
+                    let [texture_w, texture_h] = texture_dim;
+
+                    // Divide the image into the quads required by its `Fit`.
+                    let src_rect =
+                        source_rect.unwrap_or_else(|| Rect::from_corners([0.0, 0.0], image_dim));
+                    fit.quads(rect, src_rect, x_align, y_align, image_quads);
 
                     let v = |x, y, t| {
                         // Convert from conrod Scalar range to normalised range -1.0 to 1.0.
@@ -510,23 +608,69 @@ impl Mesh {
 
                     let mut push_v = |x, y, t| vertices.push(v(x, y, t));
 
-                    // Swap bottom and top to suit reversed vulkan coords.
-                    let (l, r, b, t) = rect.l_r_b_t();
-
-                    // Bottom left triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(l, b, [uv_l, uv_b]);
-
-                    // Top right triangle.
-                    push_v(l, t, [uv_l, uv_t]);
-                    push_v(r, b, [uv_r, uv_b]);
-                    push_v(r, t, [uv_r, uv_t]);
+                    for &(dst_rect, src_rect) in image_quads.iter() {
+                        // Get the sides of the source rectangle as uv coordinates.
+                        //
+                        // Texture coordinates range:
+                        // - left to right: 0.0 to 1.0
+                        // - bottom to top: 1.0 to 0.0
+                        let (l, r, b, t) = src_rect.shift(offset).l_r_b_t();
+                        let (uv_l, uv_r, uv_b, uv_t) = (
+                            (l / texture_w) as f32,
+                            (r / texture_w) as f32,
+                            1.0 - (b / texture_h) as f32,
+                            1.0 - (t / texture_h) as f32,
+                        );
+
+                        // Swap bottom and top to suit reversed vulkan coords.
+                        let (l, r, b, t) = dst_rect.l_r_b_t();
+
+                        // Bottom left triangle.
+                        push_v(l, t, [uv_l, uv_t]);
+                        push_v(r, b, [uv_r, uv_b]);
+                        push_v(l, b, [uv_l, uv_b]);
+
+                        // Top right triangle.
+                        push_v(l, t, [uv_l, uv_t]);
+                        push_v(r, b, [uv_r, uv_b]);
+                        push_v(r, t, [uv_r, uv_t]);
+                    }
                 }
 
                 // We have no special case widgets to handle.
                 render::PrimitiveKind::Other(_) => (),
             }
+
+            place(vertices, primitive_start);
+        }
+
+        // Cache the glyphs of all text within the frame at once so that none of them may be
+        // evicted by another, growing the cache until they fit.
+        let mut glyph_cache_resized = false;
+        for glyph in text_glyphs.iter() {
+            glyph_cache.queue_glyph(glyph.font_id.index(), glyph.glyph.clone());
+        }
+        loop {
+            let cached = cache_queued(
+                glyph_cache,
+                glyph_cache_pixel_buffer,
+                &mut glyph_cache_requires_upload,
+            );
+            match cached {
+                Ok(_) => break,
+                // The queue is left intact on failure, so it may be retried once grown.
+                Err(err) => match grow(glyph_cache, max_glyph_cache_dims) {
+                    Some([w, h]) => {
+                        glyph_cache_pixel_buffer.clear();
+                        glyph_cache_pixel_buffer.resize(w as usize * h as usize, 0);
+                        glyph_cache_resized = true;
+                    }
+                    None => {
+                        glyph_cache.clear_queue();
+                        return Err(err);
+                    }
+                },
+            }
         }
 
         // Enter the final command.
@@ -537,8 +681,30 @@ impl Mesh {
             }
         }
 
+        // Now that the glyphs are cached, map each quad's texture coordinates into the cache.
+        //
+        // Any glyph that cannot be found within the cache is skipped rather than drawn from
+        // whatever happens to lie at the quad's placeholder coordinates.
+        let mut skipped = vec![];
+        for glyph in text_glyphs.drain(..) {
+            let cache_id = glyph.font_id.index();
+            match glyph_cache.rect_for(cache_id, &glyph.glyph) {
+                Ok(Some((uv_rect, _))) => {
+                    let [w, h] = [uv_rect.width(), uv_rect.height()];
+                    for vertex in &mut vertices[glyph.vertices] {
+                        let [x, y] = vertex.tex_coords;
+                        vertex.tex_coords = [uv_rect.min.x + x * w, uv_rect.min.y + y * h];
+                    }
+                }
+                _ => skipped.push(glyph.vertices),
+            }
+        }
+        remove_vertices(vertices, commands, &skipped);
+
         let fill = Fill {
             glyph_cache_requires_upload,
+            atlas_overflow,
+            glyph_cache_resized,
         };
 
         Ok(fill)
@@ -573,6 +739,49 @@ impl Mesh {
     }
 }
 
+// Remove the given ranges of `vertices`, shifting the ranges of the `commands` that follow them.
+//
+// The ranges must be in order and must not overlap, nor may any span the boundary of a command.
+fn remove_vertices(
+    vertices: &mut Vec<Vertex>,
+    commands: &mut [PreparedCommand],
+    removed: &[ops::Range<usize>],
+) {
+    if removed.is_empty() {
+        return;
+    }
+    let shift = |index: usize| {
+        let removed_before: usize = removed
+            .iter()
+            .take_while(|range| range.end <= index)
+            .map(|range| range.len())
+            .sum();
+        index - removed_before
+    };
+    for command in commands.iter_mut() {
+        match *command {
+            PreparedCommand::Image(_, ref mut range) | PreparedCommand::Plain(ref mut range) => {
+                *range = shift(range.start)..shift(range.end);
+            }
+            PreparedCommand::Scizzor(_) => (),
+        }
+    }
+    let mut index = 0;
+    let mut removed = removed.iter().peekable();
+    vertices.retain(|_| {
+        let keep = loop {
+            match removed.peek() {
+                Some(range) if range.end <= index => (),
+                Some(range) => break index < range.start,
+                None => break true,
+            }
+            removed.next();
+        };
+        index += 1;
+        keep
+    });
+}
+
 impl<'a> Iterator for Commands<'a> {
     type Item = Command;
     fn next(&mut self) -> Option<Self::Item> {
@@ -610,6 +819,94 @@ impl From<text::GlyphCache<'static>> for GlyphCache {
     }
 }
 
+// Cache the queued glyphs, writing any newly rasterized glyphs to the pixel buffer.
+fn cache_queued(
+    glyph_cache: &mut GlyphCache,
+    pixel_buffer: &mut [u8],
+    requires_upload: &mut bool,
+) -> Result<rt::gpu_cache::CachedBy, rt::gpu_cache::CacheWriteErr> {
+    let (glyph_cache_w, _) = glyph_cache.dimensions();
+    let glyph_cache_w = glyph_cache_w as usize;
+    glyph_cache.cache_queued(|rect, data| {
+        let width = (rect.max.x - rect.min.x) as usize;
+        let height = (rect.max.y - rect.min.y) as usize;
+        let mut dst_ix = rect.min.y as usize * glyph_cache_w + rect.min.x as usize;
+        let mut src_ix = 0;
+        for _ in 0..height {
+            let dst_range = dst_ix..dst_ix + width;
+            let src_range = src_ix..src_ix + width;
+            let dst_slice = &mut pixel_buffer[dst_range];
+            let src_slice = &data[src_range];
+            dst_slice.copy_from_slice(src_slice);
+            dst_ix += glyph_cache_w;
+            src_ix += width;
+        }
+        *requires_upload = true;
+    })
+}
+
+// Double the shorter side of the glyph cache without exceeding `max_dims`, clearing the cache.
+//
+// Returns the new dimensions, or `None` if the cache may not grow any further.
+fn grow(glyph_cache: &mut GlyphCache, max_dims: [u32; 2]) -> Option<[u32; 2]> {
+    let (w, h) = glyph_cache.dimensions();
+    let [max_w, max_h] = max_dims;
+    let wider = [w.saturating_mul(2).min(max_w).max(w), h];
+    let taller = [w, h.saturating_mul(2).min(max_h).max(h)];
+    let [new_w, new_h] = match (wider[0] > w, taller[1] > h) {
+        (true, true) if w <= h => wider,
+        (true, true) => taller,
+        (true, false) => wider,
+        (false, true) => taller,
+        (false, false) => return None,
+    };
+    glyph_cache
+        .to_builder()
+        .dimensions(new_w, new_h)
+        .rebuild(glyph_cache);
+    Some([new_w, new_h])
+}
+
+/// Crop the triangles described by `vertices[start..]` to the given convex `polygon`.
+///
+/// The parts of each triangle that lie outside of the polygon are removed, while the position,
+/// texture coordinates and color of the new vertices are interpolated from the original vertices.
+pub fn crop_vertices(vertices: &mut Vec<Vertex>, start: usize, polygon: &[Point]) {
+    let position = |v: &Vertex| [v.position[0] as Scalar, v.position[1] as Scalar];
+    let lerp = |a: &Vertex, b: &Vertex, t: Scalar| {
+        let t = t as f32;
+        let f = |a: f32, b: f32| a + (b - a) * t;
+        Vertex {
+            position: [
+                f(a.position[0], b.position[0]),
+                f(a.position[1], b.position[1]),
+            ],
+            tex_coords: [
+                f(a.tex_coords[0], b.tex_coords[0]),
+                f(a.tex_coords[1], b.tex_coords[1]),
+            ],
+            rgba: [
+                f(a.rgba[0], b.rgba[0]),
+                f(a.rgba[1], b.rgba[1]),
+                f(a.rgba[2], b.rgba[2]),
+                f(a.rgba[3], b.rgba[3]),
+            ],
+            mode: a.mode,
+        }
+    };
+    let triangles: Vec<Vertex> = vertices.drain(start..).collect();
+    let mut cropped = Vec::new();
+    for triangle in triangles.chunks(3) {
+        crop::clip_convex(triangle, polygon, position, lerp, &mut cropped);
+        // The cropped triangle is a convex polygon, so we can triangulate it as a fan.
+        for i in 1..cropped.len().saturating_sub(1) {
+            vertices.push(cropped[0]);
+            vertices.push(cropped[i]);
+            vertices.push(cropped[i + 1]);
+        }
+    }
+}
+
 fn gamma_srgb_to_linear(c: [f32; 4]) -> [f32; 4] {
     fn component(f: f32) -> f32 {
         // Taken from https://github.com/PistonDevelopers/graphics/src/color.rs#L42
@@ -0,0 +1,131 @@
+//! A 2D affine transform, used for rotating and scaling widgets along with their descendants.
+
+use super::{Point, Rect, Scalar};
+
+/// A 2D affine transform.
+///
+/// Maps a point `[x, y]` to `[a * x + b * y + tx, c * x + d * y + ty]` where the matrix is
+/// laid out as `[[a, b, tx], [c, d, ty]]`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// The rows of the transform's matrix.
+    pub matrix: [[Scalar; 3]; 2],
+}
+
+impl Transform {
+    /// The transform that leaves all points unchanged.
+    pub const IDENTITY: Transform = Transform {
+        matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+    };
+
+    /// A transform that translates points by the given offset.
+    pub fn translation(offset: Point) -> Self {
+        Transform {
+            matrix: [[1.0, 0.0, offset[0]], [0.0, 1.0, offset[1]]],
+        }
+    }
+
+    /// A transform that rotates points counter-clockwise about the origin by the given radians.
+    pub fn rotation(radians: Scalar) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Transform {
+            matrix: [[cos, -sin, 0.0], [sin, cos, 0.0]],
+        }
+    }
+
+    /// A transform that scales points about the origin by the given factor along each axis.
+    pub fn scale(scale: [Scalar; 2]) -> Self {
+        Transform {
+            matrix: [[scale[0], 0.0, 0.0], [0.0, scale[1], 0.0]],
+        }
+    }
+
+    /// A transform that first scales and then rotates points about the given `pivot`.
+    pub fn about(pivot: Point, radians: Scalar, scale: [Scalar; 2]) -> Self {
+        Transform::translation([-pivot[0], -pivot[1]])
+            .then(Transform::scale(scale))
+            .then(Transform::rotation(radians))
+            .then(Transform::translation(pivot))
+    }
+
+    /// Produces the transform that applies `self` followed by `other`.
+    pub fn then(self, other: Transform) -> Self {
+        let [[a, b, tx], [c, d, ty]] = self.matrix;
+        let [[oa, ob, otx], [oc, od, oty]] = other.matrix;
+        Transform {
+            matrix: [
+                [oa * a + ob * c, oa * b + ob * d, oa * tx + ob * ty + otx],
+                [oc * a + od * c, oc * b + od * d, oc * tx + od * ty + oty],
+            ],
+        }
+    }
+
+    /// Whether or not the transform leaves all points unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Transform::IDENTITY
+    }
+
+    /// Apply the transform to the given point.
+    pub fn apply(&self, p: Point) -> Point {
+        let [[a, b, tx], [c, d, ty]] = self.matrix;
+        [a * p[0] + b * p[1] + tx, c * p[0] + d * p[1] + ty]
+    }
+
+    /// Apply the transform to the given vector, ignoring translation.
+    ///
+    /// This is useful for transforming offsets, such as the deltas of a drag.
+    pub fn apply_vector(&self, v: [Scalar; 2]) -> [Scalar; 2] {
+        let [[a, b, _], [c, d, _]] = self.matrix;
+        [a * v[0] + b * v[1], c * v[0] + d * v[1]]
+    }
+
+    /// Apply the transform to a vertex position given in normalised device coordinates, i.e.
+    /// where the window spans `-1.0` to `1.0` along each axis.
+    ///
+    /// `half_window` is half of the window's dimensions in the coordinates of the transform. A
+    /// negative dimension may be given for an axis that points in the opposite direction.
+    pub fn apply_ndc(&self, position: [f32; 2], half_window: [Scalar; 2]) -> [f32; 2] {
+        let [w, h] = half_window;
+        let [x, y] = self.apply([position[0] as Scalar * w, position[1] as Scalar * h]);
+        [(x / w) as f32, (y / h) as f32]
+    }
+
+    /// The transform that undoes `self`.
+    ///
+    /// Returns `None` if the transform is not invertible, e.g. if it scales an axis by `0.0`.
+    pub fn inverse(&self) -> Option<Self> {
+        let [[a, b, tx], [c, d, ty]] = self.matrix;
+        let det = a * d - b * c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let (ia, ib, ic, id) = (d / det, -b / det, -c / det, a / det);
+        Some(Transform {
+            matrix: [
+                [ia, ib, -(ia * tx + ib * ty)],
+                [ic, id, -(ic * tx + id * ty)],
+            ],
+        })
+    }
+
+    /// The axis-aligned `Rect` bounding the given `rect` once transformed.
+    pub fn bounding_rect(&self, rect: Rect) -> Rect {
+        if self.is_identity() {
+            return rect;
+        }
+        let (l, r, b, t) = rect.l_r_b_t();
+        let corners = [[l, b], [r, b], [r, t], [l, t]];
+        let first = self.apply(corners[0]);
+        let init = Rect::from_corners(first, first);
+        corners[1..].iter().fold(init, |bounds, &corner| {
+            let p = self.apply(corner);
+            bounds.stretch_to_point(p)
+        })
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
@@ -11,10 +11,11 @@
 use color;
 use graph::{self, Graph};
 use image;
-use position::{Align, Dimensions};
+use position::{Align, Dimensions, Transform};
 use std;
 use text;
 use theme::Theme;
+use widget::primitive::image::Fit;
 use widget::triangles::{ColoredPoint, Triangle};
 use widget::{self, Widget};
 use {Color, FontSize, Point, Rect, Scalar};
@@ -28,7 +29,9 @@ use {Color, FontSize, Point, Rect, Scalar};
 /// require ownership over the sequence of primitives, consider using the `OwnedPrimitives` type.
 /// The `OwnedPrimitives` type can be produced by calling the `Primitives::owned` method.
 pub struct Primitives<'a> {
-    crop_stack: Vec<(widget::Id, Rect)>,
+    crop_stack: Vec<(widget::Id, Rect, std::ops::Range<usize>)>,
+    /// The points of the crop polygons referenced by the `crop_stack`.
+    crop_points: Vec<Point>,
     depth_order: std::slice::Iter<'a, widget::Id>,
     graph: &'a Graph,
     theme: &'a Theme,
@@ -36,6 +39,40 @@ pub struct Primitives<'a> {
     window_rect: Rect,
     /// A buffer to use for triangulating polygons and lines for the `Triangles`.
     triangles: Vec<Triangle<Point>>,
+    /// A buffer to use for generating shadows and applying opacity to the `TrianglesMultiColor`.
+    triangles_multi_color: Vec<Triangle<ColoredPoint>>,
+    /// A `Text` widget whose remaining decoration primitives are yet to be yielded.
+    pending_text: Option<(VisibleWidget<'a>, TextStage)>,
+    /// The cache from which the layouts of `Text` widgets are reused between frames.
+    text_layouts: Option<&'a TextLayouts>,
+}
+
+type TextLayouts = std::cell::RefCell<text::layout::Cache>;
+
+/// A widget yielded by `next_widget` along with its scizzor, crop polygon range and transform.
+type VisibleWidget<'a> = (
+    widget::Id,
+    Rect,
+    std::ops::Range<usize>,
+    Transform,
+    &'a graph::Container,
+);
+
+/// The primitives produced for a `Text` widget, in the order in which they are drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum TextStage {
+    /// The highlighted background behind each line.
+    Background,
+    /// The highlight behind the selected text.
+    Selection,
+    /// The glyphs drawn in the drop shadow's color at its offset.
+    DropShadow,
+    /// The given pass of the glyphs drawn in the outline's color around the text.
+    Outline(usize),
+    /// The glyphs.
+    Glyphs,
+    /// The underline and strikethrough of each line.
+    Lines,
 }
 
 /// An owned alternative to the `Primitives` type.
@@ -46,6 +83,7 @@ pub struct Primitives<'a> {
 #[derive(Clone)]
 pub struct OwnedPrimitives {
     primitives: Vec<OwnedPrimitive>,
+    crop_points: Vec<Point>,
     triangles_single_color: Vec<Triangle<Point>>,
     triangles_multi_color: Vec<Triangle<ColoredPoint>>,
     line_infos: Vec<text::line::Info>,
@@ -82,6 +120,19 @@ pub struct Primitive<'a> {
     ///
     /// Only parts of the widget within this `Rect` should be drawn.
     pub scizzor: Rect,
+    /// The convex polygon to which the primitive widget should be cropped, if any.
+    ///
+    /// This is `Some` when the primitive is a descendant of a widget that crops its children to a
+    /// non-rectangular `widget::crop::Shape`. The points are in counter-clockwise order and lie
+    /// within the `scizzor`.
+    pub crop_polygon: Option<&'a [Point]>,
+    /// The transform mapping the primitive's geometry to the window.
+    ///
+    /// This is the identity unless the primitive or one of its depth parents is rotated or scaled.
+    /// The `rect`, along with all of the geometry described by the `kind`, is given in the
+    /// primitive's untransformed coordinate space. The `scizzor` and `crop_polygon` are given in
+    /// window coordinates.
+    pub transform: Transform,
     /// The bounding rectangle for the `Primitive`.
     pub rect: Rect,
 }
@@ -122,6 +173,14 @@ pub enum PrimitiveKind<'a> {
         color: Option<Color>,
         /// The area of the texture that will be drawn to the `Image`'s `Rect`.
         source_rect: Option<Rect>,
+        /// How the `source_rect` is fitted to the `Image`'s `Rect`.
+        ///
+        /// Backends may use `Fit::quads` to produce the geometry for the image.
+        fit: Fit,
+        /// The alignment of the image along the *x* axis when it does not fill the `Rect`.
+        x_align: Align,
+        /// The alignment of the image along the *y* axis when it does not fill the `Rect`.
+        y_align: Align,
     },
 
     /// A single block of `Text`, produced by the primitive `Text` widget.
@@ -133,6 +192,9 @@ pub enum PrimitiveKind<'a> {
         text: Text<'a>,
         /// The unique identifier for the font, useful for the `glyph_cache.rect_for(id, glyph)`
         /// method when using the `conrod::text::GlyphCache` (rusttype's GPU `Cache`).
+        ///
+        /// Glyphs produced by the font's fallbacks have their own `Id`s. These are yielded by
+        /// `Text::positioned_glyphs_with_font_ids`.
         font_id: text::font::Id,
     },
 
@@ -157,12 +219,16 @@ pub struct Text<'a> {
     window_dim: Dimensions,
     text: &'a str,
     line_infos: &'a [text::line::Info],
-    font: &'a text::Font,
+    font_id: text::font::Id,
+    font: text::font::Chain<'a>,
     font_size: FontSize,
     rect: Rect,
+    // The offset of the drop shadow or outline pass drawn, applied to the laid out glyphs.
+    offset: Point,
     justify: text::Justify,
     y_align: Align,
     line_spacing: Scalar,
+    layout: Option<(&'a TextLayouts, text::layout::Key)>,
 }
 
 #[derive(Clone)]
@@ -170,6 +236,8 @@ struct OwnedPrimitive {
     id: widget::Id,
     kind: OwnedPrimitiveKind,
     scizzor: Rect,
+    crop_polygon: Option<std::ops::Range<usize>>,
+    transform: Transform,
     rect: Rect,
 }
 
@@ -189,6 +257,9 @@ enum OwnedPrimitiveKind {
         image_id: image::Id,
         color: Option<Color>,
         source_rect: Option<Rect>,
+        fit: Fit,
+        x_align: Align,
+        y_align: Align,
     },
     Text {
         color: Color,
@@ -203,8 +274,13 @@ struct OwnedText {
     line_infos_range: std::ops::Range<usize>,
     window_dim: Dimensions,
     font: text::Font,
+    fallbacks: Vec<(text::font::Id, text::Font)>,
+    tab_stops: text::TabStops,
+    #[cfg(feature = "shaping")]
+    face: Option<text::shape::Face>,
     font_size: FontSize,
     rect: Rect,
+    offset: Point,
     justify: text::Justify,
     y_align: Align,
     line_spacing: Scalar,
@@ -213,6 +289,7 @@ struct OwnedText {
 /// An iterator-like type for yielding `Primitive`s from an `OwnedPrimitives`.
 pub struct WalkOwnedPrimitives<'a> {
     primitives: std::slice::Iter<'a, OwnedPrimitive>,
+    crop_points: &'a [Point],
     triangles_single_color: &'a [Triangle<Point>],
     triangles_multi_color: &'a [Triangle<ColoredPoint>],
     line_infos: &'a [text::line::Info],
@@ -231,14 +308,85 @@ impl<'a> Text<'a> {
     /// out text. This is because conrod positioning uses a "pixel-agnostic" `Scalar` value
     /// representing *perceived* distances for its positioning and layout, rather than pixel
     /// values. During rendering however, the pixel density must be known
+    ///
+    /// Glyphs for characters not covered by the font may be produced by its fallback fonts. Use
+    /// `positioned_glyphs_with_font_ids` when caching glyphs so that each is cached for its font.
     pub fn positioned_glyphs(
         self,
         dpi_factor: f32,
     ) -> impl 'a + Iterator<Item = rusttype::PositionedGlyph<'static>> {
+        self.positioned_glyphs_with_font_ids(dpi_factor)
+            .map(|(_, g)| g)
+    }
+
+    /// Produces a list of `PositionedGlyph`s along with the `Id` of the font that produced each.
+    ///
+    /// This is the same as `positioned_glyphs`, however each glyph is paired with the `Id` of the
+    /// font or fallback font with which it should be cached and looked up within the
+    /// `GlyphCache`.
+    pub fn positioned_glyphs_with_font_ids(
+        self,
+        dpi_factor: f32,
+    ) -> impl 'a + Iterator<Item = (text::font::Id, rusttype::PositionedGlyph<'static>)> {
+        let [x, y] = self.offset;
+        let (dx, dy) = (
+            (x * dpi_factor as Scalar) as f32,
+            (-y * dpi_factor as Scalar) as f32,
+        );
+        let glyphs = self.shared_glyphs(dpi_factor);
+        (0..glyphs.len()).map(move |i| {
+            let (id, ref g) = glyphs[i];
+            if dx == 0.0 && dy == 0.0 {
+                return (id, g.clone());
+            }
+            let p = g.position();
+            let p = text::rt::point(p.x + dx, p.y + dy);
+            (id, g.unpositioned().clone().positioned(p))
+        })
+    }
+
+    // The glyphs laid out within the `rect`, before the `offset` is applied.
+    fn shared_glyphs(
+        self,
+        dpi_factor: f32,
+    ) -> std::sync::Arc<Vec<(text::font::Id, rusttype::PositionedGlyph<'static>)>> {
+        // Reuse the glyphs from the layout cache if it is available and not already borrowed.
+        let (text_layouts, key) = match self.layout {
+            Some(layout) => layout,
+            None => return std::sync::Arc::new(self.layout_glyphs(dpi_factor)),
+        };
+        let mut text_layouts = match text_layouts.try_borrow_mut() {
+            Ok(text_layouts) => text_layouts,
+            Err(_) => return std::sync::Arc::new(self.layout_glyphs(dpi_factor)),
+        };
+        let [win_w, win_h] = self.window_dim;
+        let origin = [
+            ((self.rect.left() + win_w / 2.0) * dpi_factor as Scalar) as f32,
+            ((-self.rect.top() + win_h / 2.0) * dpi_factor as Scalar) as f32,
+        ];
+        let placement = text::layout::Placement {
+            origin: origin,
+            dim: self.rect.dim(),
+            justify: self.justify,
+            y_align: self.y_align,
+            line_spacing: self.line_spacing,
+            dpi_factor: dpi_factor,
+        };
+        let (text, line_infos) = (self.text, self.line_infos);
+        let layout = || self.layout_glyphs(dpi_factor);
+        text_layouts.positioned_glyphs(key, text, line_infos, placement, layout)
+    }
+
+    // Lay out the glyphs of each line in visual order.
+    fn layout_glyphs(
+        self,
+        dpi_factor: f32,
+    ) -> Vec<(text::font::Id, rusttype::PositionedGlyph<'static>)> {
         let Text {
             window_dim,
             text,
             line_infos,
+            font_id,
             font,
             font_size,
             rect,
@@ -248,26 +396,74 @@ impl<'a> Text<'a> {
             ..
         } = self;
 
+        let font = font.with_dpi_factor(dpi_factor);
+
         // Convert conrod coordinates to pixel coordinates.
         let trans_x = move |x: Scalar| (x + window_dim[0] / 2.0) * dpi_factor as Scalar;
         let trans_y = move |y: Scalar| ((-y) + window_dim[1] / 2.0) * dpi_factor as Scalar;
 
         // Produce the text layout iterators.
         let line_infos = line_infos.iter().cloned();
-        let lines = line_infos.clone().map(move |info| &text[info.byte_range()]);
-        let line_rects =
-            text::line::rects(line_infos, font_size, rect, justify, y_align, line_spacing);
+        let line_rects = text::line::rects(
+            line_infos.clone(),
+            font_size,
+            rect,
+            justify,
+            y_align,
+            line_spacing,
+        );
 
         // Clear the existing glyphs and fill the buffer with glyphs for this Text.
         let scale = text::f32_pt_to_scale(font_size as f32 * dpi_factor);
-        lines.zip(line_rects).flat_map(move |(line, line_rect)| {
-            let (x, y) = (
-                trans_x(line_rect.left()) as f32,
-                trans_y(line_rect.bottom()) as f32,
-            );
-            let point = text::rt::Point { x: x, y: y };
-            font.layout(line, scale, point)
-        })
+        line_infos
+            .zip(line_rects)
+            .flat_map(move |(info, line_rect)| {
+                let line = &text[info.byte_range()];
+                let (x, y) = (
+                    trans_x(line_rect.left()) as f32,
+                    trans_y(line_rect.bottom()) as f32,
+                );
+                let point = text::rt::Point { x: x, y: y };
+
+                // Shift each glyph by the extra space inserted between the words visually before
+                // the character that produced it.
+                let spacing = text::line::WordSpacing::new(line, info.width, line_rect.w());
+                let runs = info.visual_runs(text);
+                let chars: Vec<char> = line.chars().collect();
+                let mut offsets = vec![0.0; chars.len()];
+                let mut offset = 0.0;
+                for run in &runs {
+                    let mut visit = |i: usize| {
+                        offsets[i] = (offset * dpi_factor as Scalar) as f32;
+                        offset += spacing.after(i, chars[i]);
+                    };
+                    match run.direction {
+                        text::Direction::LeftToRight => run.char_range.clone().for_each(&mut visit),
+                        text::Direction::RightToLeft => {
+                            run.char_range.clone().rev().for_each(&mut visit)
+                        }
+                    }
+                }
+
+                // Lay out each run in visual order, following on from the runs to its left.
+                let mut glyphs = Vec::with_capacity(chars.len());
+                let mut run_x = 0.0;
+                for run in runs {
+                    let run_text = &line[run.byte_range.clone()];
+                    let start = text::rt::point(point.x + run_x, point.y);
+                    let layout = font.layout_run(run_text, scale, start, Some(run.direction));
+                    for (char_i, fallback_id, g) in layout {
+                        let char_i = run.char_range.start + char_i;
+                        let position = g.position();
+                        let position = text::rt::point(position.x + offsets[char_i], position.y);
+                        let g = g.into_unpositioned().positioned(position);
+                        glyphs.push((fallback_id.unwrap_or(font_id), g));
+                    }
+                    run_x += font.advances(run_text, scale).sum::<Scalar>() as f32;
+                }
+                glyphs
+            })
+            .collect()
     }
 }
 
@@ -282,28 +478,60 @@ impl<'a> Primitives<'a> {
     ) -> Self {
         Primitives {
             crop_stack: Vec::new(),
+            crop_points: Vec::new(),
             depth_order: depth_order.iter(),
             graph: graph,
             theme: theme,
             fonts: fonts,
             window_rect: Rect::from_xy_dim([0.0, 0.0], window_dim),
             triangles: Vec::new(),
+            triangles_multi_color: Vec::new(),
+            pending_text: None,
+            text_layouts: None,
         }
     }
 
+    /// Reuse the glyphs laid out for `Text` widgets on previous frames via the given cache.
+    pub fn with_text_layouts(mut self, text_layouts: &'a TextLayouts) -> Self {
+        self.text_layouts = Some(text_layouts);
+        self
+    }
+
     /// Yield the next `Primitive` for rendering.
     pub fn next(&mut self) -> Option<Primitive> {
         let Primitives {
             ref mut crop_stack,
+            ref mut crop_points,
             ref mut depth_order,
             ref mut triangles,
+            ref mut triangles_multi_color,
+            ref mut pending_text,
             graph,
             theme,
             fonts,
             window_rect,
+            text_layouts,
         } = *self;
 
-        while let Some(widget) = next_widget(depth_order, graph, crop_stack, window_rect) {
+        // Finish yielding the primitives of a decorated `Text` widget.
+        if let Some((widget, stage)) = pending_text.take() {
+            return text_primitive(
+                widget,
+                stage,
+                pending_text,
+                graph,
+                theme,
+                fonts,
+                text_layouts,
+                window_rect,
+                crop_points,
+                triangles,
+            );
+        }
+
+        while let Some(widget) =
+            next_widget(depth_order, graph, crop_stack, crop_points, window_rect)
+        {
             use widget::primitive::point_path::{State as PointPathState, Style as PointPathStyle};
             use widget::primitive::shape::polygon::State as PolygonState;
             use widget::primitive::shape::Style as ShapeStyle;
@@ -313,8 +541,9 @@ impl<'a> Primitives<'a> {
             type TrianglesMultiColorState =
                 widget::triangles::State<Vec<widget::triangles::Triangle<(Point, color::Rgba)>>>;
 
-            let (id, scizzor, container) = widget;
+            let (id, scizzor, crop_range, transform, container) = widget;
             let rect = container.rect;
+            let opacity = graph::algo::opacity_of_widget(graph, id);
 
             fn state_type_id<W>() -> std::any::TypeId
             where
@@ -331,16 +560,25 @@ impl<'a> Primitives<'a> {
                     match *style {
                         ShapeStyle::Fill(_) => {
                             let kind = PrimitiveKind::Rectangle { color: color };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
                         ShapeStyle::Outline(ref line_style) => {
+                            triangles.clear();
                             let (l, r, b, t) = rect.l_r_b_t();
                             let array = [[l, b], [l, t], [r, t], [r, b], [l, b]];
-                            let cap = line_style.get_cap(theme);
+                            let join = line_style.get_join(theme);
                             let thickness = line_style.get_thickness(theme);
                             let points = array.iter().cloned();
                             let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
+                                match widget::point_path::triangles(points, join, thickness) {
                                     None => &[],
                                     Some(iter) => {
                                         triangles.extend(iter);
@@ -351,7 +589,15 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
                     }
                 }
@@ -368,16 +614,45 @@ impl<'a> Primitives<'a> {
                         color: color,
                         triangles: &state.triangles,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
                 }
             } else if container.type_id == std::any::TypeId::of::<TrianglesMultiColorState>() {
                 type Style = widget::triangles::MultiColor;
                 if let Some(tris) = container.state_and_style::<TrianglesMultiColorState, Style>() {
                     let graph::UniqueWidgetState { ref state, .. } = *tris;
+                    let triangles = if opacity < 1.0 {
+                        triangles_multi_color.clear();
+                        triangles_multi_color.extend(state.triangles.iter().map(|triangle| {
+                            let with_opacity = |(p, color::Rgba(r, g, b, a)): ColoredPoint| {
+                                (p, color::Rgba(r, g, b, a * opacity))
+                            };
+                            let [a, b, c] = triangle.0;
+                            Triangle([with_opacity(a), with_opacity(b), with_opacity(c)])
+                        }));
+                        &triangles_multi_color[..]
+                    } else {
+                        &state.triangles[..]
+                    };
                     let kind = PrimitiveKind::TrianglesMultiColor {
-                        triangles: &state.triangles,
+                        triangles: triangles,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
                 }
             } else if container.type_id == state_type_id::<widget::Oval<widget::oval::Full>>() {
                 if let Some(oval) =
@@ -400,14 +675,22 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
 
                         ShapeStyle::Outline(ref line_style) => {
-                            let cap = line_style.get_cap(theme);
+                            let join = line_style.get_join(theme);
                             let thickness = line_style.get_thickness(theme);
                             let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
+                                match widget::point_path::triangles(points, join, thickness) {
                                     None => &[],
                                     Some(iter) => {
                                         triangles.extend(iter);
@@ -418,7 +701,15 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
                     }
                 }
@@ -447,17 +738,25 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
 
                         ShapeStyle::Outline(ref line_style) => {
                             use std::iter::once;
-                            let cap = line_style.get_cap(theme);
+                            let join = line_style.get_join(theme);
                             let thickness = line_style.get_thickness(theme);
                             let middle = rect.xy();
                             let points = once(middle).chain(points).chain(once(middle));
                             let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
+                                match widget::point_path::triangles(points, join, thickness) {
                                     None => &[],
                                     Some(iter) => {
                                         triangles.extend(iter);
@@ -468,7 +767,15 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
                     }
                 }
@@ -496,14 +803,24 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
 
                         ShapeStyle::Outline(ref line_style) => {
-                            let cap = line_style.get_cap(theme);
+                            let join = line_style.get_join(theme);
                             let thickness = line_style.get_thickness(theme);
+                            // Return to the first point so that the outline is closed.
+                            let points = points.chain(state.points.first().cloned());
                             let triangles =
-                                match widget::point_path::triangles(points, cap, thickness) {
+                                match widget::point_path::triangles(points, join, thickness) {
                                     None => &[],
                                     Some(iter) => {
                                         triangles.extend(iter);
@@ -514,7 +831,15 @@ impl<'a> Primitives<'a> {
                                 color: color.to_rgb(),
                                 triangles: &triangles,
                             };
-                            return Some(new_primitive(id, kind, scizzor, rect));
+                            return Some(new_primitive(
+                                id,
+                                kind,
+                                scizzor,
+                                crop_polygon(crop_points, crop_range),
+                                opacity,
+                                transform,
+                                rect,
+                            ));
                         }
                     }
                 }
@@ -526,10 +851,10 @@ impl<'a> Primitives<'a> {
                     } = *line;
                     triangles.clear();
                     let color = style.get_color(theme);
-                    let cap = style.get_cap(theme);
+                    let join = style.get_join(theme);
                     let thickness = style.get_thickness(theme);
                     let points = std::iter::once(state.start).chain(std::iter::once(state.end));
-                    let triangles = match widget::point_path::triangles(points, cap, thickness) {
+                    let triangles = match widget::point_path::triangles(points, join, thickness) {
                         None => &[],
                         Some(iter) => {
                             triangles.extend(iter);
@@ -540,7 +865,15 @@ impl<'a> Primitives<'a> {
                         color: color.to_rgb(),
                         triangles: triangles,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
                 }
             } else if container.type_id == std::any::TypeId::of::<PointPathState>() {
                 if let Some(point_path) =
@@ -552,10 +885,10 @@ impl<'a> Primitives<'a> {
                     } = *point_path;
                     triangles.clear();
                     let color = style.get_color(theme);
-                    let cap = style.get_cap(theme);
+                    let join = style.get_join(theme);
                     let thickness = style.get_thickness(theme);
                     let points = state.points.iter().map(|&t| t);
-                    let triangles = match widget::point_path::triangles(points, cap, thickness) {
+                    let triangles = match widget::point_path::triangles(points, join, thickness) {
                         None => &[],
                         Some(iter) => {
                             triangles.extend(iter);
@@ -566,48 +899,92 @@ impl<'a> Primitives<'a> {
                         color: color.to_rgb(),
                         triangles: triangles,
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
                 }
             } else if container.type_id == state_type_id::<widget::Text>() {
-                if let Some(text) = container.unique_widget_state::<widget::Text>() {
+                let has_font = container
+                    .unique_widget_state::<widget::Text>()
+                    .and_then(|text| text.style.resolve_font_id(theme, fonts))
+                    .and_then(|font_id| fonts.chain(font_id))
+                    .is_some();
+                if !has_font {
+                    continue;
+                }
+                let widget = (id, scizzor, crop_range, transform, container);
+                return text_primitive(
+                    widget,
+                    TextStage::Background,
+                    pending_text,
+                    graph,
+                    theme,
+                    fonts,
+                    text_layouts,
+                    window_rect,
+                    crop_points,
+                    triangles,
+                );
+            } else if container.type_id == state_type_id::<widget::Shadow>() {
+                use widget::primitive::shadow::{State, Style};
+                if let Some(shadow) = container.state_and_style::<State, Style>() {
+                    let graph::UniqueWidgetState { ref style, .. } = *shadow;
+                    triangles_multi_color.clear();
+                    widget::shadow::triangles(rect, style, theme, triangles_multi_color);
+                    if opacity < 1.0 {
+                        for triangle in triangles_multi_color.iter_mut() {
+                            for &mut (_, ref mut color) in triangle.0.iter_mut() {
+                                color.3 *= opacity;
+                            }
+                        }
+                    }
+                    let kind = PrimitiveKind::TrianglesMultiColor {
+                        triangles: &triangles_multi_color[..],
+                    };
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
+                }
+            } else if container.type_id == state_type_id::<widget::Svg>() {
+                use widget::primitive::svg::{State, Style};
+                if let Some(svg) = container.state_and_style::<State, Style>() {
                     let graph::UniqueWidgetState {
                         ref state,
                         ref style,
-                    } = *text;
-                    let font_id = match style.font_id(theme).or_else(|| fonts.ids().next()) {
-                        Some(id) => id,
-                        None => continue,
-                    };
-                    let font = match fonts.get(font_id) {
-                        Some(font) => font,
-                        None => continue,
-                    };
-
-                    // Retrieve styling.
-                    let color = style.color(theme);
-                    let font_size = style.font_size(theme);
-                    let line_spacing = style.line_spacing(theme);
-                    let justify = style.justify(theme);
-                    let y_align = Align::End;
-
-                    let text = Text {
-                        window_dim: window_rect.dim(),
-                        text: &state.string,
-                        line_infos: &state.line_infos,
-                        font: font,
-                        font_size: font_size,
-                        rect: rect,
-                        justify: justify,
-                        y_align: y_align,
-                        line_spacing: line_spacing,
-                    };
-
-                    let kind = PrimitiveKind::Text {
-                        color: color,
-                        text: text,
-                        font_id: font_id,
+                    } = *svg;
+                    triangles_multi_color.clear();
+                    widget::svg::triangles(rect, state, style, theme, triangles_multi_color);
+                    if opacity < 1.0 {
+                        for triangle in triangles_multi_color.iter_mut() {
+                            for &mut (_, ref mut color) in triangle.0.iter_mut() {
+                                color.3 *= opacity;
+                            }
+                        }
+                    }
+                    let kind = PrimitiveKind::TrianglesMultiColor {
+                        triangles: &triangles_multi_color[..],
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
                 }
             } else if container.type_id == state_type_id::<widget::Image>() {
                 use widget::primitive::image::{State, Style};
@@ -621,14 +998,33 @@ impl<'a> Primitives<'a> {
                         color: color,
                         image_id: state.image_id,
                         source_rect: state.src_rect,
+                        fit: style.fit(theme),
+                        x_align: style.x_align(theme),
+                        y_align: style.y_align(theme),
                     };
-                    return Some(new_primitive(id, kind, scizzor, rect));
+                    return Some(new_primitive(
+                        id,
+                        kind,
+                        scizzor,
+                        crop_polygon(crop_points, crop_range),
+                        opacity,
+                        transform,
+                        rect,
+                    ));
                 }
 
             // Return an `Other` variant for all non-primitive widgets.
             } else {
                 let kind = PrimitiveKind::Other(container);
-                return Some(new_primitive(id, kind, scizzor, rect));
+                return Some(new_primitive(
+                    id,
+                    kind,
+                    scizzor,
+                    crop_polygon(crop_points, crop_range),
+                    opacity,
+                    transform,
+                    rect,
+                ));
             }
         }
 
@@ -640,6 +1036,8 @@ impl<'a> Primitives<'a> {
     /// This is useful for sending `Ui` rendering data across threads in an efficient manner.
     pub fn owned(mut self) -> OwnedPrimitives {
         let mut primitives = Vec::with_capacity(self.depth_order.len());
+        let mut primitive_crop_points: Vec<Point> = Vec::new();
+        let mut prev_crop_polygon: Option<std::ops::Range<usize>> = None;
         let mut primitive_triangles_multi_color = Vec::new();
         let mut primitive_triangles_single_color = Vec::new();
         let mut primitive_line_infos = Vec::new();
@@ -649,13 +1047,30 @@ impl<'a> Primitives<'a> {
             id,
             rect,
             scizzor,
+            crop_polygon,
+            transform,
             kind,
         }) = self.next()
         {
+            // Consecutive primitives usually share the same crop polygon, so only store it once.
+            let crop_polygon = crop_polygon.map(|polygon| match prev_crop_polygon {
+                Some(ref range) if &primitive_crop_points[range.clone()] == polygon => {
+                    range.clone()
+                }
+                _ => {
+                    let start = primitive_crop_points.len();
+                    primitive_crop_points.extend_from_slice(polygon);
+                    start..primitive_crop_points.len()
+                }
+            });
+            prev_crop_polygon = crop_polygon.clone();
+
             let new = |kind| OwnedPrimitive {
                 id: id,
                 rect: rect,
                 scizzor: scizzor,
+                crop_polygon: crop_polygon.clone(),
+                transform: transform,
                 kind: kind,
             };
 
@@ -690,11 +1105,17 @@ impl<'a> Primitives<'a> {
                     image_id,
                     color,
                     source_rect,
+                    fit,
+                    x_align,
+                    y_align,
                 } => {
                     let kind = OwnedPrimitiveKind::Image {
                         image_id: image_id,
                         color: color,
                         source_rect: source_rect,
+                        fit: fit,
+                        x_align: x_align,
+                        y_align: y_align,
                     };
                     primitives.push(new(kind));
                 }
@@ -711,6 +1132,7 @@ impl<'a> Primitives<'a> {
                         font,
                         font_size,
                         rect,
+                        offset,
                         justify,
                         y_align,
                         line_spacing,
@@ -731,9 +1153,14 @@ impl<'a> Primitives<'a> {
                         str_byte_range: start_str_byte..end_str_byte,
                         line_infos_range: start_line_info_idx..end_line_info_idx,
                         window_dim: window_dim,
-                        font: font.clone(),
+                        font: font.font().clone(),
+                        fallbacks: font.fallbacks().to_vec(),
+                        tab_stops: font.tab_stops().clone(),
+                        #[cfg(feature = "shaping")]
+                        face: font.face().cloned(),
                         font_size: font_size,
                         rect: rect,
+                        offset: offset,
                         justify: justify,
                         y_align: y_align,
                         line_spacing: line_spacing,
@@ -754,6 +1181,7 @@ impl<'a> Primitives<'a> {
 
         OwnedPrimitives {
             primitives: primitives,
+            crop_points: primitive_crop_points,
             triangles_single_color: primitive_triangles_single_color,
             triangles_multi_color: primitive_triangles_multi_color,
             line_infos: primitive_line_infos,
@@ -767,6 +1195,7 @@ impl OwnedPrimitives {
     pub fn walk(&self) -> WalkOwnedPrimitives {
         let OwnedPrimitives {
             ref primitives,
+            ref crop_points,
             ref triangles_single_color,
             ref triangles_multi_color,
             ref line_infos,
@@ -774,6 +1203,7 @@ impl OwnedPrimitives {
         } = *self;
         WalkOwnedPrimitives {
             primitives: primitives.iter(),
+            crop_points: crop_points,
             triangles_single_color: triangles_single_color,
             triangles_multi_color: triangles_multi_color,
             line_infos: line_infos,
@@ -787,6 +1217,7 @@ impl<'a> WalkOwnedPrimitives<'a> {
     pub fn next(&mut self) -> Option<Primitive> {
         let WalkOwnedPrimitives {
             ref mut primitives,
+            crop_points,
             triangles_single_color,
             triangles_multi_color,
             line_infos,
@@ -798,12 +1229,17 @@ impl<'a> WalkOwnedPrimitives<'a> {
                       id,
                       rect,
                       scizzor,
+                      ref crop_polygon,
+                      transform,
                       ref kind,
                   }| {
+                let crop_polygon = crop_polygon.clone().map(|range| &crop_points[range]);
                 let new = |kind| Primitive {
                     id: id,
                     rect: rect,
                     scizzor: scizzor,
+                    crop_polygon: crop_polygon,
+                    transform: transform,
                     kind: kind,
                 };
 
@@ -840,14 +1276,21 @@ impl<'a> WalkOwnedPrimitives<'a> {
                             ref str_byte_range,
                             ref line_infos_range,
                             ref font,
+                            ref fallbacks,
                             window_dim,
                             font_size,
                             rect,
+                            offset,
                             justify,
                             y_align,
                             line_spacing,
+                            ..
                         } = *text;
 
+                        let font =
+                            text::font::Chain::new(font, fallbacks).with_tab_stops(&text.tab_stops);
+                        #[cfg(feature = "shaping")]
+                        let font = font.with_face(text.face.as_ref());
                         let text_str = &texts_str[str_byte_range.clone()];
                         let line_infos = &line_infos[line_infos_range.clone()];
 
@@ -855,12 +1298,15 @@ impl<'a> WalkOwnedPrimitives<'a> {
                             window_dim: window_dim,
                             text: text_str,
                             line_infos: line_infos,
+                            font_id: font_id,
                             font: font,
                             font_size: font_size,
                             rect: rect,
+                            offset: offset,
                             justify: justify,
                             y_align: y_align,
                             line_spacing: line_spacing,
+                            layout: None,
                         };
 
                         let kind = PrimitiveKind::Text {
@@ -875,11 +1321,17 @@ impl<'a> WalkOwnedPrimitives<'a> {
                         image_id,
                         color,
                         source_rect,
+                        fit,
+                        x_align,
+                        y_align,
                     } => {
                         let kind = PrimitiveKind::Image {
                             image_id: image_id,
                             color: color,
                             source_rect: source_rect,
+                            fit: fit,
+                            x_align: x_align,
+                            y_align: y_align,
                         };
                         new(kind)
                     }
@@ -889,24 +1341,302 @@ impl<'a> WalkOwnedPrimitives<'a> {
     }
 }
 
+/// Produce the primitive for the given `stage` of a `Text` widget, or for the first stage after
+/// it that has one.
+///
+/// If any stages remain after the yielded primitive, they are stored in `pending` so that they
+/// may be produced on the following call. Returns `None` only if the widget's font cannot be
+/// found.
+#[allow(clippy::too_many_arguments)]
+fn text_primitive<'a, 'b>(
+    widget: VisibleWidget<'a>,
+    stage: TextStage,
+    pending: &mut Option<(VisibleWidget<'a>, TextStage)>,
+    graph: &'a Graph,
+    theme: &'b Theme,
+    fonts: &'b text::font::Map,
+    text_layouts: Option<&'b TextLayouts>,
+    window_rect: Rect,
+    crop_points: &'b [Point],
+    triangles: &'b mut Vec<Triangle<Point>>,
+) -> Option<Primitive<'b>>
+where
+    'a: 'b,
+{
+    let (id, scizzor, ref crop_range, transform, container) = widget;
+    let text = container.unique_widget_state::<widget::Text>()?;
+    let graph::UniqueWidgetState {
+        ref state,
+        ref style,
+    } = *text;
+    let font_id = style.resolve_font_id(theme, fonts)?;
+    let font = fonts.chain(font_id)?;
+    let metrics = widget::text::DecorationMetrics::from_tables(
+        fonts.get(font_id)?,
+        fonts.decorations(font_id),
+        style.font_size(theme),
+    );
+    let rect = container.rect;
+    let opacity = graph::algo::opacity_of_widget(graph, id);
+
+    // Retrieve styling.
+    let color = style.color(theme);
+    let font_size = style.font_size(theme);
+    let line_spacing = style.line_spacing(theme);
+    let justify = style.justify(theme);
+    let y_align = Align::End;
+    let background = style.background(theme);
+    let underline = style.underline(theme);
+    let strikethrough = style.strikethrough(theme);
+    let drop_shadow = style.drop_shadow(theme);
+    let outline = style.outline(theme);
+
+    // The glyphs are shared with the layout cached while the widget was updated.
+    let layout = text_layouts.map(|text_layouts| {
+        let wrap = match style.maybe_truncate(theme) {
+            Some(_) => None,
+            None => style.maybe_wrap(theme).map(|wrap| (wrap, rect.w())),
+        };
+        let key = text::layout::Key::new(state.displayed(), font_id, font_size, wrap);
+        (text_layouts, key)
+    });
+
+    let line_rects = || {
+        text::line::rects(
+            state.line_infos.iter().cloned(),
+            font_size,
+            rect,
+            justify,
+            y_align,
+            line_spacing,
+        )
+    };
+    let primitive = |kind, rect| {
+        new_primitive(
+            id,
+            kind,
+            scizzor,
+            crop_polygon(crop_points, crop_range.clone()),
+            opacity,
+            transform,
+            rect,
+        )
+    };
+    // The glyphs of the text in the given color, offset from the widget's `rect`.
+    //
+    // Every pass shares the glyphs laid out within the `rect` itself, to which the offset is only
+    // applied as they are yielded.
+    let glyphs = |offset: Point, color| {
+        let text = Text {
+            window_dim: window_rect.dim(),
+            text: state.displayed(),
+            line_infos: &state.line_infos,
+            font_id: font_id,
+            font: font,
+            font_size: font_size,
+            rect: rect,
+            offset: offset,
+            justify: justify,
+            y_align: y_align,
+            line_spacing: line_spacing,
+            layout: layout,
+        };
+        let kind = PrimitiveKind::Text {
+            color: color,
+            text: text,
+            font_id: font_id,
+        };
+        primitive(kind, rect.shift(offset))
+    };
+
+    let mut stage = stage;
+    loop {
+        let next = match stage {
+            TextStage::Background => Some(TextStage::Selection),
+            TextStage::Selection => Some(TextStage::DropShadow),
+            TextStage::DropShadow => Some(TextStage::Outline(0)),
+            TextStage::Outline(pass) if pass + 1 < outline.map_or(0, |o| o.passes()) => {
+                Some(TextStage::Outline(pass + 1))
+            }
+            TextStage::Outline(_) => Some(TextStage::Glyphs),
+            TextStage::Glyphs if underline.is_some() || strikethrough => Some(TextStage::Lines),
+            TextStage::Glyphs | TextStage::Lines => None,
+        };
+        match stage {
+            TextStage::Background => {
+                if let Some(background) = background {
+                    triangles.clear();
+                    widget::text::background_triangles(line_rects(), metrics, triangles);
+                    *pending = next.map(|next| (widget.clone(), next));
+                    let kind = PrimitiveKind::TrianglesSingleColor {
+                        color: background.to_rgb(),
+                        triangles: &triangles[..],
+                    };
+                    return Some(primitive(kind, rect));
+                }
+            }
+            TextStage::Selection => {
+                let selection = state
+                    .selection
+                    .filter(|&(start, end)| start != end && style.selectable(theme));
+                if let Some((start, end)) = selection {
+                    let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
+                    let text = state.displayed();
+                    let lines = state.line_infos.iter().map(|info| &text[info.byte_range()]);
+                    let lines_with_rects = lines.zip(line_rects());
+                    let selected_rects =
+                        text::line::selected_rects(lines_with_rects, font, font_size, start, end);
+                    triangles.clear();
+                    for rect in selected_rects {
+                        let (l, r, b, t) = rect.l_r_b_t();
+                        let (a, b) = widget::triangles::from_quad([[l, b], [l, t], [r, t], [r, b]]);
+                        triangles.push(a);
+                        triangles.push(b);
+                    }
+                    let selection_color = style
+                        .selection_color(theme)
+                        .unwrap_or_else(|| color.highlighted().alpha(0.25));
+                    *pending = next.map(|next| (widget.clone(), next));
+                    let kind = PrimitiveKind::TrianglesSingleColor {
+                        color: selection_color.to_rgb(),
+                        triangles: &triangles[..],
+                    };
+                    return Some(primitive(kind, rect));
+                }
+            }
+            TextStage::DropShadow => {
+                if let Some(shadow) = drop_shadow {
+                    *pending = next.map(|next| (widget.clone(), next));
+                    return Some(glyphs(shadow.offset, shadow.color));
+                }
+            }
+            TextStage::Outline(pass) => {
+                if let Some((outline, offset)) =
+                    outline.and_then(|o| o.offset(pass).map(|offset| (o, offset)))
+                {
+                    *pending = next.map(|next| (widget.clone(), next));
+                    return Some(glyphs(offset, outline.color));
+                }
+            }
+            TextStage::Glyphs => {
+                *pending = next.map(|next| (widget.clone(), next));
+                return Some(glyphs([0.0, 0.0], color));
+            }
+            TextStage::Lines => {
+                let decoration_color = style.decoration_color(theme).unwrap_or(color);
+                triangles.clear();
+                widget::text::line_triangles(
+                    line_rects(),
+                    metrics,
+                    underline,
+                    strikethrough,
+                    triangles,
+                );
+                let kind = PrimitiveKind::TrianglesSingleColor {
+                    color: decoration_color.to_rgb(),
+                    triangles: &triangles[..],
+                };
+                return Some(primitive(kind, rect));
+            }
+        }
+        stage = next?;
+    }
+}
+
 /// Simplify the constructor for a `Primitive`.
-fn new_primitive(id: widget::Id, kind: PrimitiveKind, scizzor: Rect, rect: Rect) -> Primitive {
+fn new_primitive<'a>(
+    id: widget::Id,
+    kind: PrimitiveKind<'a>,
+    scizzor: Rect,
+    crop_polygon: Option<&'a [Point]>,
+    opacity: f32,
+    transform: Transform,
+    rect: Rect,
+) -> Primitive<'a> {
     Primitive {
         id: id,
-        kind: kind,
+        kind: apply_opacity(kind, opacity),
         scizzor: scizzor,
+        crop_polygon: crop_polygon,
+        transform: transform,
         rect: rect,
     }
 }
 
+/// Multiply the alpha of the given primitive's colors by the given `opacity`.
+///
+/// The colors of `TrianglesMultiColor` are not modified, as they are borrowed from the widget's
+/// state and must be copied in order to be modified.
+fn apply_opacity(kind: PrimitiveKind, opacity: f32) -> PrimitiveKind {
+    if opacity >= 1.0 {
+        return kind;
+    }
+    match kind {
+        PrimitiveKind::Rectangle { color } => PrimitiveKind::Rectangle {
+            color: color.alpha(opacity),
+        },
+        PrimitiveKind::TrianglesSingleColor { color, triangles } => {
+            let color::Rgba(r, g, b, a) = color;
+            PrimitiveKind::TrianglesSingleColor {
+                color: color::Rgba(r, g, b, a * opacity),
+                triangles: triangles,
+            }
+        }
+        PrimitiveKind::Image {
+            image_id,
+            color,
+            source_rect,
+            fit,
+            x_align,
+            y_align,
+        } => PrimitiveKind::Image {
+            image_id: image_id,
+            color: Some(color.unwrap_or(color::WHITE).alpha(opacity)),
+            source_rect: source_rect,
+            fit: fit,
+            x_align: x_align,
+            y_align: y_align,
+        },
+        PrimitiveKind::Text {
+            color,
+            text,
+            font_id,
+        } => PrimitiveKind::Text {
+            color: color.alpha(opacity),
+            text: text,
+            font_id: font_id,
+        },
+        kind => kind,
+    }
+}
+
+/// The crop polygon described by the given range of the `crop_points`, if there is one.
+fn crop_polygon(crop_points: &[Point], range: std::ops::Range<usize>) -> Option<&[Point]> {
+    if range.start == range.end {
+        None
+    } else {
+        Some(&crop_points[range])
+    }
+}
+
 /// Retrieves the next visible widget from the `depth_order`, updating the `crop_stack` as
 /// necessary.
+///
+/// Along with the widget, yields the scizzor and the range of the `crop_points` describing the
+/// crop polygon for the widget. The range is empty if the widget is only cropped by the scizzor.
 fn next_widget<'a>(
     depth_order: &mut std::slice::Iter<widget::Id>,
     graph: &'a Graph,
-    crop_stack: &mut Vec<(widget::Id, Rect)>,
+    crop_stack: &mut Vec<(widget::Id, Rect, std::ops::Range<usize>)>,
+    crop_points: &mut Vec<Point>,
     window_rect: Rect,
-) -> Option<(widget::Id, Rect, &'a graph::Container)> {
+) -> Option<(
+    widget::Id,
+    Rect,
+    std::ops::Range<usize>,
+    Transform,
+    &'a graph::Container,
+)> {
     while let Some(&id) = depth_order.next() {
         let container = match graph.widget(id) {
             Some(container) => container,
@@ -916,39 +1646,82 @@ fn next_widget<'a>(
         // If we're currently using a cropped context and the current `crop_parent_idx` is
         // *not* a depth-wise parent of the widget at the current `idx`, we should pop that
         // cropped context from the stack as we are done with it.
-        while let Some(&(crop_parent_idx, _)) = crop_stack.last() {
+        while let Some(&(crop_parent_idx, _, ref range)) = crop_stack.last() {
             if graph.does_recursive_depth_edge_exist(crop_parent_idx, id) {
                 break;
             } else {
+                crop_points.truncate(range.start);
                 crop_stack.pop();
             }
         }
 
         // Check the stack for the current Context.
-        let scizzor = crop_stack
+        let (scizzor, crop_range) = crop_stack
             .last()
-            .map(|&(_, scizzor)| scizzor)
-            .unwrap_or(window_rect);
+            .map(|&(_, scizzor, ref range)| (scizzor, range.clone()))
+            .unwrap_or((window_rect, 0..0));
+
+        let transform = graph::algo::transform_of_widget(graph, id);
 
         // If the current widget should crop its children, we need to add a rect for it to
         // the top of the crop stack.
         if container.crop_kids {
-            let scizzor_rect = container
-                .kid_area
-                .rect
-                .overlap(scizzor)
-                .unwrap_or_else(|| Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]));
-            crop_stack.push((id, scizzor_rect));
+            let shape = container.crop_shape;
+            let is_rectangle = matches!(shape, widget::crop::Shape::Rectangle);
+
+            // Rectangles within a rectangular context only require updating the scizzor.
+            if is_rectangle && crop_range.start == crop_range.end && transform.is_identity() {
+                let scizzor_rect = container
+                    .kid_area
+                    .rect
+                    .overlap(scizzor)
+                    .unwrap_or_else(|| Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0]));
+                let end = crop_points.len();
+                crop_stack.push((id, scizzor_rect, end..end));
+
+            // Otherwise intersect the shape with the current crop polygon.
+            } else {
+                let mut shape_points = Vec::new();
+                shape.points(container.kid_area.rect, &mut shape_points);
+                for point in &mut shape_points {
+                    *point = transform.apply(*point);
+                }
+                // Transforms that mirror an axis reverse the winding of the shape.
+                if widget::crop::signed_area(&shape_points) < 0.0 {
+                    shape_points.reverse();
+                }
+                let mut parent_points = Vec::new();
+                if crop_range.start == crop_range.end {
+                    widget::crop::Shape::Rectangle.points(scizzor, &mut parent_points);
+                } else {
+                    parent_points.extend_from_slice(&crop_points[crop_range.clone()]);
+                }
+                let mut polygon = Vec::new();
+                widget::crop::intersect(&shape_points, &parent_points, &mut polygon);
+                let scizzor_rect = if polygon.is_empty() {
+                    Rect::from_xy_dim([0.0, 0.0], [0.0, 0.0])
+                } else {
+                    let bounds =
+                        widget::primitive::bounding_box_for_points(polygon.iter().cloned());
+                    bounds.overlap(scizzor).unwrap_or(bounds)
+                };
+                let start = crop_points.len();
+                crop_points.extend(polygon);
+                crop_stack.push((id, scizzor_rect, start..crop_points.len()));
+            }
         }
 
         // We only want to return primitives that are actually visible.
-        let is_visible = container.rect.overlap(scizzor).is_some()
+        let is_visible = transform
+            .bounding_rect(container.rect)
+            .overlap(scizzor)
+            .is_some()
             && graph::algo::cropped_area_of_widget(graph, id).is_some();
         if !is_visible {
             continue;
         }
 
-        return Some((id, scizzor, container));
+        return Some((id, scizzor, crop_range, transform, container));
     }
 
     None
@@ -0,0 +1,128 @@
+use image;
+use render::PrimitiveKind;
+use std::time::Duration;
+use widget;
+use widget::animated_image::{Frame, Mode};
+use {Positionable, Sizeable, Ui, UiBuilder, Widget};
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+// Three frames displayed for 10ms, 20ms and 30ms respectively.
+fn frames() -> Vec<Frame> {
+    let mut image_map = image::Map::new();
+    [10, 20, 30]
+        .iter()
+        .map(|&delay| Frame::new(image_map.insert(()), ms(delay)))
+        .collect()
+}
+
+#[test]
+fn loop_restarts_from_first_frame() {
+    let frames = frames();
+    assert_eq!(Mode::Loop.frame_at(&frames, ms(0)), Some((0, Some(ms(10)))));
+    assert_eq!(
+        Mode::Loop.frame_at(&frames, ms(15)),
+        Some((1, Some(ms(15))))
+    );
+    assert_eq!(Mode::Loop.frame_at(&frames, ms(59)), Some((2, Some(ms(1)))));
+    assert_eq!(
+        Mode::Loop.frame_at(&frames, ms(60)),
+        Some((0, Some(ms(10))))
+    );
+}
+
+#[test]
+fn ping_pong_reverses_without_repeating_ends() {
+    let frames = frames();
+    // 0 (10ms), 1 (20ms), 2 (30ms), 1 (20ms), then 0 again.
+    assert_eq!(
+        Mode::PingPong.frame_at(&frames, ms(65)),
+        Some((1, Some(ms(15))))
+    );
+    assert_eq!(
+        Mode::PingPong.frame_at(&frames, ms(80)),
+        Some((0, Some(ms(10))))
+    );
+    assert_eq!(
+        Mode::PingPong.frame_at(&frames[..1], ms(25)),
+        Some((0, Some(ms(5))))
+    );
+}
+
+#[test]
+fn once_stops_at_last_frame() {
+    let frames = frames();
+    assert_eq!(
+        Mode::Once.frame_at(&frames, ms(35)),
+        Some((2, Some(ms(25))))
+    );
+    assert_eq!(Mode::Once.frame_at(&frames, ms(60)), Some((2, None)));
+    assert_eq!(Mode::Once.frame_at(&[], ms(0)), None);
+}
+
+#[test]
+fn widget_draws_current_frame() {
+    let frames = frames();
+    let mut ui = windowless_ui();
+    let playing_id = ui.widget_id_generator().next();
+    let paused_id = ui.widget_id_generator().next();
+    let (playing, paused) = {
+        let ui = &mut ui.set_widgets();
+        let playing = widget::AnimatedImage::new(&frames)
+            .w_h(100.0, 100.0)
+            .middle()
+            .set(playing_id, ui);
+        let paused = widget::AnimatedImage::new(&frames)
+            .w_h(100.0, 100.0)
+            .middle()
+            .playing(false)
+            .set(paused_id, ui);
+        (playing, paused)
+    };
+
+    // Playing returns the time until the next frame, while pausing does not require updates.
+    assert!(playing.is_some_and(|remaining| remaining <= ms(10)));
+    assert_eq!(paused, None);
+
+    let mut primitives = ui.draw();
+    let mut image_ids = vec![];
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Image { image_id, .. } = primitive.kind {
+            image_ids.push(image_id);
+        }
+    }
+    assert_eq!(image_ids, vec![frames[0].image_id; 2]);
+}
+
+#[cfg(feature = "gif")]
+#[test]
+fn gif_frames_are_composited() {
+    use gif;
+    use widget::animated_image::decode;
+
+    // A 2x1 animation whose second frame only covers its right pixel.
+    let mut data = vec![];
+    {
+        let mut encoder = gif::Encoder::new(&mut data, 2, 1, &[]).unwrap();
+        let mut first = gif::Frame::from_rgba(2, 1, &mut [255, 0, 0, 255, 255, 0, 0, 255]);
+        first.delay = 5;
+        encoder.write_frame(&first).unwrap();
+        let mut second = gif::Frame::from_rgba(1, 1, &mut [0, 0, 255, 255]);
+        second.left = 1;
+        second.delay = 0;
+        encoder.write_frame(&second).unwrap();
+    }
+
+    let frames = decode::gif(&data[..]).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].delay, ms(50));
+    assert_eq!(frames[1].delay, decode::DEFAULT_GIF_DELAY);
+    assert_eq!(frames[1].dimensions, [2, 1]);
+    assert_eq!(frames[1].rgba, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+}
@@ -0,0 +1,188 @@
+use image::atlas::{Atlas, Eviction, InsertError, PADDING};
+use image::{self, Map};
+use mesh::{self, Mesh};
+use widget;
+use {Positionable, Rect, Sizeable, Ui, UiBuilder, Widget};
+
+// Page textures are never looked up by the mesh, so the map only needs to reserve ids.
+struct Page;
+
+impl mesh::ImageDimensions for Page {
+    fn dimensions(&self) -> [u32; 2] {
+        [64, 64]
+    }
+}
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+fn rgba([w, h]: [u32; 2]) -> Vec<u8> {
+    vec![255; w as usize * h as usize * 4]
+}
+
+// Draw an `Image` widget for each of the given ids and fill the mesh with the atlas, returning
+// whether or not any of the images overflowed the atlas.
+fn fill(
+    ui: &mut Ui,
+    mesh: &mut Mesh,
+    map: &Map<Page>,
+    atlas: &mut Atlas,
+    ids: &[image::Id],
+) -> bool {
+    {
+        let ui = &mut ui.set_widgets();
+        for (i, &image_id) in ids.iter().enumerate() {
+            let id = ui.widget_id_generator().next();
+            widget::Image::new(image_id)
+                .w_h(10.0, 10.0)
+                .x_y(i as f64 * 20.0, 0.0)
+                .set(id, ui);
+        }
+    }
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    let fill = mesh
+        .fill_with_atlas(viewport, 1.0, map, atlas, ui.draw())
+        .unwrap();
+    fill.atlas_overflow
+}
+
+#[test]
+fn insert_rejects_invalid_images() {
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [64, 64], 1);
+    assert_eq!(
+        atlas.insert(&mut map, [4, 4], vec![0; 3]),
+        Err(InsertError::InvalidLength)
+    );
+    assert_eq!(
+        atlas.insert(&mut map, [0, 4], vec![]),
+        Err(InsertError::InvalidLength)
+    );
+    assert_eq!(
+        atlas.insert(&mut map, [64, 4], rgba([64, 4])),
+        Err(InsertError::TooLarge)
+    );
+    assert!(atlas.insert(&mut map, [62, 4], rgba([62, 4])).is_ok());
+}
+
+#[test]
+fn images_are_packed_into_one_page() {
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [64, 64], 2);
+    let a = atlas.insert(&mut map, [10, 10], rgba([10, 10])).unwrap();
+    let b = atlas.insert(&mut map, [20, 8], rgba([20, 8])).unwrap();
+    assert!(!atlas.page_ids().contains(&a));
+    let a_loc = atlas.locate(a).unwrap();
+    let b_loc = atlas.locate(b).unwrap();
+    assert_eq!(atlas.page_count(), 1);
+    assert_eq!(a_loc.page_id, b_loc.page_id);
+    assert_eq!(a_loc.rect.dim(), [10.0, 10.0]);
+    assert_eq!(b_loc.rect.dim(), [20.0, 8.0]);
+
+    // The images are padded and do not overlap.
+    let pad = PADDING as f64;
+    assert!(a_loc.rect.left() >= pad && a_loc.rect.top() <= 64.0 - pad);
+    assert!(a_loc.rect.overlap(b_loc.rect).is_none());
+}
+
+#[test]
+fn atlas_images_are_batched_into_one_draw() {
+    let mut ui = windowless_ui();
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [64, 64], 1);
+    let a = atlas.insert(&mut map, [10, 10], rgba([10, 10])).unwrap();
+    let b = atlas.insert(&mut map, [10, 10], rgba([10, 10])).unwrap();
+    let mut mesh = Mesh::new();
+    assert!(!fill(&mut ui, &mut mesh, &map, &mut atlas, &[a, b]));
+
+    let page_id = atlas.page_ids()[0];
+    let draws: Vec<_> = mesh
+        .commands()
+        .filter_map(|cmd| match cmd {
+            mesh::Command::Draw(mesh::Draw::Image(id, range)) => Some((id, range)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(draws, vec![(page_id, 6..18)]);
+
+    // Texture coordinates refer to the image's area within the page.
+    let location = atlas.locate(a).unwrap();
+    let (l, r) = (location.rect.left() / 64.0, location.rect.right() / 64.0);
+    let us: Vec<f32> = mesh.vertices()[6..12]
+        .iter()
+        .map(|v| v.tex_coords[0])
+        .collect();
+    assert!(us.iter().all(|&u| u == l as f32 || u == r as f32));
+}
+
+#[test]
+fn pages_are_added_up_to_max_pages_but_never_resized() {
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [32, 32], 2).eviction(Eviction::None);
+    let ids: Vec<_> = (0..3)
+        .map(|_| atlas.insert(&mut map, [28, 28], rgba([28, 28])).unwrap())
+        .collect();
+    let a_loc = atlas.locate(ids[0]).unwrap();
+    let b_loc = atlas.locate(ids[1]).unwrap();
+    assert_ne!(a_loc.page_id, b_loc.page_id);
+    assert!(atlas.locate(ids[2]).is_none());
+    assert_eq!(atlas.page_count(), 2);
+    assert_eq!(atlas.page_dimensions(), [32, 32]);
+    assert_eq!(b_loc.page_dimensions, [32, 32]);
+}
+
+#[test]
+fn least_recently_used_page_is_evicted() {
+    let mut ui = windowless_ui();
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [32, 32], 1);
+    let a = atlas.insert(&mut map, [28, 28], rgba([28, 28])).unwrap();
+    let b = atlas.insert(&mut map, [28, 28], rgba([28, 28])).unwrap();
+    let mut mesh = Mesh::new();
+
+    // Both images cannot be resident during the same frame.
+    assert!(fill(&mut ui, &mut mesh, &map, &mut atlas, &[a, b]));
+
+    // In a later frame the page is evicted to make room for the other image.
+    assert!(!fill(&mut ui, &mut mesh, &map, &mut atlas, &[b]));
+    assert_eq!(atlas.page_count(), 1);
+    assert!(atlas.locate(b).is_some());
+}
+
+#[test]
+fn no_eviction_reports_overflow() {
+    let mut ui = windowless_ui();
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [32, 32], 1).eviction(Eviction::None);
+    let a = atlas.insert(&mut map, [28, 28], rgba([28, 28])).unwrap();
+    let b = atlas.insert(&mut map, [28, 28], rgba([28, 28])).unwrap();
+    let mut mesh = Mesh::new();
+    assert!(!fill(&mut ui, &mut mesh, &map, &mut atlas, &[a]));
+    assert!(fill(&mut ui, &mut mesh, &map, &mut atlas, &[b]));
+}
+
+#[test]
+fn pages_are_uploaded_once() {
+    let mut map = Map::<Page>::new();
+    let mut atlas = Atlas::with_pages(&mut map, [16, 16], 1);
+    let a = atlas.insert(&mut map, [2, 2], rgba([2, 2])).unwrap();
+    atlas.locate(a).unwrap();
+
+    let mut updates = vec![];
+    atlas.update_pages(|update| {
+        assert_eq!(update.rgba.len(), 16 * 16 * 4);
+        updates.push((update.page_id, update.is_new));
+    });
+    assert_eq!(updates, vec![(atlas.page_ids()[0], true)]);
+
+    // Nothing has changed since the last update.
+    let mut count = 0;
+    atlas.update_pages(|_| count += 1);
+    assert_eq!(count, 0);
+
+    // Packing another image requires an update, but the page already exists.
+    let b = atlas.insert(&mut map, [2, 2], rgba([2, 2])).unwrap();
+    atlas.locate(b).unwrap();
+    atlas.update_pages(|update| assert!(!update.is_new));
+}
@@ -0,0 +1,87 @@
+use image;
+use mesh::{self, Mesh};
+use widget::{self, crop};
+use {Point, Positionable, Rect, Scalar, Ui, UiBuilder, Widget};
+
+struct Image;
+
+impl mesh::ImageDimensions for Image {
+    fn dimensions(&self) -> [u32; 2] {
+        [1, 1]
+    }
+}
+
+fn polygon_area(polygon: &[Point]) -> Scalar {
+    crop::signed_area(polygon).abs() / 2.0
+}
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+#[test]
+fn intersect_overlapping_squares() {
+    let a = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+    let b = [[5.0, 5.0], [15.0, 5.0], [15.0, 15.0], [5.0, 15.0]];
+    let mut output = vec![];
+    crop::intersect(&a, &b, &mut output);
+    assert!((polygon_area(&output) - 25.0).abs() < 1e-9);
+}
+
+#[test]
+fn intersect_with_clockwise_clip() {
+    let a = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+    let b = [[5.0, 5.0], [5.0, 15.0], [15.0, 15.0], [15.0, 5.0]];
+    let mut output = vec![];
+    crop::intersect(&a, &b, &mut output);
+    assert!((polygon_area(&output) - 25.0).abs() < 1e-9);
+}
+
+#[test]
+fn intersect_disjoint_is_empty() {
+    let a = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+    let b = [[5.0, 5.0], [6.0, 5.0], [5.0, 6.0]];
+    let mut output = vec![];
+    crop::intersect(&a, &b, &mut output);
+    assert!(output.is_empty());
+}
+
+#[test]
+fn mesh_crops_kids_to_oval() {
+    let mut ui = windowless_ui();
+    let parent = ui.widget_id_generator().next();
+    let child = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 200.0])
+            .middle()
+            .crop_kids_to(crop::Shape::oval())
+            .set(parent, ui);
+        widget::Rectangle::fill([200.0, 200.0])
+            .middle_of(parent)
+            .set(child, ui);
+    }
+
+    let mut mesh = Mesh::new();
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    let image_map = image::Map::<Image>::new();
+    mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+
+    // The first twelve vertices describe the window and the uncropped parent rectangle.
+    let vertices = &mesh.vertices()[12..];
+    assert!(vertices.len() > 6);
+    let to_point = |v: &mesh::Vertex| {
+        let [x, y] = v.position;
+        [x as Scalar * 400.0, -y as Scalar * 300.0]
+    };
+    let mut area = 0.0;
+    for triangle in vertices.chunks(3) {
+        let points: Vec<Point> = triangle.iter().map(&to_point).collect();
+        for p in &points {
+            assert!((p[0] * p[0] + p[1] * p[1]).sqrt() <= 100.0 + 1e-3);
+        }
+        area += polygon_area(&points);
+    }
+    let circle_area = std::f64::consts::PI * 100.0 * 100.0;
+    assert!((area - circle_area).abs() / circle_area < 0.01);
+}
@@ -0,0 +1,93 @@
+use image;
+use mesh::{self, Mesh};
+use position::{Align, Range};
+use widget;
+use widget::primitive::image::Fit;
+use {Positionable, Rect, Sizeable, Ui, UiBuilder, Widget};
+
+struct Image;
+
+impl mesh::ImageDimensions for Image {
+    fn dimensions(&self) -> [u32; 2] {
+        [20, 10]
+    }
+}
+
+fn src_rect() -> Rect {
+    Rect::from_corners([0.0, 0.0], [20.0, 10.0])
+}
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+#[test]
+fn contain_letterboxes_image() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let mut quads = vec![];
+    Fit::Contain.quads(rect, src_rect(), Align::Middle, Align::Start, &mut quads);
+    assert_eq!(quads.len(), 1);
+    let (dst, src) = quads[0];
+    assert_eq!(dst, Rect::from_corners([0.0, 0.0], [100.0, 50.0]));
+    assert_eq!(src, src_rect());
+}
+
+#[test]
+fn cover_crops_image() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 100.0]);
+    let mut quads = vec![];
+    Fit::Cover.quads(rect, src_rect(), Align::Middle, Align::Middle, &mut quads);
+    assert_eq!(quads.len(), 1);
+    let (dst, src) = quads[0];
+    assert_eq!(dst, rect);
+    assert_eq!(src, Rect::from_corners([5.0, 0.0], [15.0, 10.0]));
+}
+
+#[test]
+fn center_crops_without_scaling() {
+    let rect = Rect::from_corners([0.0, 0.0], [10.0, 40.0]);
+    let mut quads = vec![];
+    Fit::Center.quads(rect, src_rect(), Align::End, Align::Middle, &mut quads);
+    assert_eq!(quads.len(), 1);
+    let (dst, src) = quads[0];
+    assert_eq!(dst, Rect::from_corners([0.0, 15.0], [10.0, 25.0]));
+    assert_eq!(src, Rect::from_corners([10.0, 0.0], [20.0, 10.0]));
+}
+
+#[test]
+fn tile_repeats_from_alignment() {
+    let rect = Rect::from_corners([0.0, 0.0], [50.0, 10.0]);
+    let mut quads = vec![];
+    Fit::Tile.quads(rect, src_rect(), Align::Middle, Align::Start, &mut quads);
+    // A centred tile is surrounded by two partial tiles.
+    assert_eq!(quads.len(), 3);
+    assert_eq!(quads[0].0.x, Range::new(0.0, 15.0));
+    assert_eq!(quads[0].1.x, Range::new(5.0, 20.0));
+    assert_eq!(quads[1].0.x, Range::new(15.0, 35.0));
+    assert_eq!(quads[1].1.x, Range::new(0.0, 20.0));
+    assert_eq!(quads[2].0.x, Range::new(35.0, 50.0));
+    assert_eq!(quads[2].1.x, Range::new(0.0, 15.0));
+}
+
+#[test]
+fn mesh_draws_one_quad_per_tile() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let mut image_map = image::Map::new();
+    let image_id = image_map.insert(Image);
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Image::new(image_id)
+            .w_h(40.0, 20.0)
+            .middle()
+            .fit(Fit::Tile)
+            .align(Align::Start, Align::Start)
+            .set(id, ui);
+    }
+    let mut mesh = Mesh::new();
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+
+    // The first six vertices describe the window, followed by two tiles along each axis.
+    assert_eq!(mesh.vertices().len(), 6 + 4 * 6);
+}
@@ -1,4 +1,16 @@
+mod animated_image;
+mod atlas;
 mod color;
+mod crop;
 mod global_input;
+mod image;
+mod nine_slice;
+mod point_path;
+mod render;
+mod rich_text;
+mod svg;
+mod text;
+mod text_edit;
+mod transform;
 mod ui;
 mod widget_input;
@@ -0,0 +1,100 @@
+use image;
+use position::{Padding, Range};
+use render::PrimitiveKind;
+use widget;
+use widget::nine_slice::{self, Skin};
+use {Rect, Sizeable, Ui, UiBuilder, Widget};
+
+fn skin() -> Skin {
+    let src_rect = Rect::from_corners([0.0, 0.0], [30.0, 30.0]);
+    let insets = Padding {
+        x: Range::new(10.0, 10.0),
+        y: Range::new(10.0, 10.0),
+    };
+    let image_id = image::Map::<()>::new().insert(());
+    Skin::new(image_id, src_rect, insets)
+}
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+#[test]
+fn stretch_keeps_corners_unscaled() {
+    let rect = Rect::from_corners([0.0, 0.0], [100.0, 50.0]);
+    let mut slices = vec![];
+    nine_slice::skin_slices(rect, &skin(), &mut slices);
+    assert_eq!(slices.len(), 9);
+    let (bottom_left, src) = slices[0];
+    assert_eq!(bottom_left, Rect::from_corners([0.0, 0.0], [10.0, 10.0]));
+    assert_eq!(src, Rect::from_corners([0.0, 0.0], [10.0, 10.0]));
+    let (centre, src) = slices[4];
+    assert_eq!(centre, Rect::from_corners([10.0, 10.0], [90.0, 40.0]));
+    assert_eq!(src, Rect::from_corners([10.0, 10.0], [20.0, 20.0]));
+}
+
+#[test]
+fn tile_cuts_last_tile_short() {
+    let rect = Rect::from_corners([0.0, 0.0], [45.0, 30.0]);
+    let mut slices = vec![];
+    nine_slice::skin_slices(rect, &skin().tile(), &mut slices);
+    // Along x: corner, two full tiles, a 5 wide tile and a corner. Along y: corner, tile, corner.
+    assert_eq!(slices.len(), 5 * 3);
+    let (last_tile, src) = slices[3];
+    assert_eq!(last_tile.x, Range::new(30.0, 35.0));
+    assert_eq!(src.x, Range::new(10.0, 15.0));
+}
+
+#[test]
+fn tile_count_is_capped() {
+    let rect = Rect::from_corners([0.0, 0.0], [2020.0, 30.0]);
+    let mut slices = vec![];
+    nine_slice::skin_slices(rect, &skin().tile(), &mut slices);
+    // The 2000 wide centre would need 200 tiles, so the capped tiles are stretched instead.
+    assert_eq!(slices.len(), (nine_slice::MAX_TILES + 2) * 3);
+    let (first_tile, src) = slices[1];
+    assert_eq!(first_tile.x, Range::new(10.0, 260.0));
+    assert_eq!(src.x, Range::new(10.0, 20.0));
+    let (last_tile, _) = slices[nine_slice::MAX_TILES];
+    assert_eq!(last_tile.x, Range::new(1760.0, 2010.0));
+}
+
+#[test]
+fn corners_shrink_to_fit() {
+    let rect = Rect::from_corners([0.0, 0.0], [10.0, 40.0]);
+    let mut slices = vec![];
+    nine_slice::skin_slices(rect, &skin(), &mut slices);
+    // No room remains for the centre column.
+    assert_eq!(slices.len(), 6);
+    assert_eq!(slices[0].0.x, Range::new(0.0, 5.0));
+    assert_eq!(slices[0].1.x, Range::new(0.0, 10.0));
+}
+
+#[test]
+fn skinned_canvas_draws_slices() {
+    let mut ui = windowless_ui();
+    let canvas = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(200.0, 100.0)
+            .skin(skin())
+            .set(canvas, ui);
+    }
+    let graph = ui.widget_graph();
+    let mut primitives = ui.draw();
+    let mut images = 0;
+    while let Some(primitive) = primitives.next() {
+        if !graph.does_recursive_depth_edge_exist(canvas, primitive.id) {
+            continue;
+        }
+        match primitive.kind {
+            PrimitiveKind::Image { .. } => images += 1,
+            PrimitiveKind::Rectangle { .. } => {
+                panic!("expected the skin to replace the canvas rectangle")
+            }
+            _ => (),
+        }
+    }
+    assert_eq!(images, 9);
+}
@@ -0,0 +1,53 @@
+use widget::line::Join;
+use widget::point_path::{is_over, triangles};
+use Point;
+
+// A closed square outline with corners at `[0, 0]` and `[10, 10]`.
+fn square() -> Vec<Point> {
+    vec![
+        [0.0, 0.0],
+        [0.0, 10.0],
+        [10.0, 10.0],
+        [10.0, 0.0],
+        [0.0, 0.0],
+    ]
+}
+
+fn triangles_area(points: Vec<Point>, join: Join, thickness: f64) -> f64 {
+    triangles(points, join, thickness)
+        .unwrap()
+        .map(|t| {
+            let [a, b, c] = t.0;
+            ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.0
+        })
+        .sum()
+}
+
+#[test]
+fn miter_join_fills_outer_corner() {
+    // The outer corner of the square lies a full half thickness away in both axes.
+    let corner = [-0.9, 10.9];
+    assert!(is_over(square(), Join::Miter(4.0), 2.0, corner));
+    assert!(!is_over(square(), Join::Bevel, 2.0, corner));
+    assert!(!is_over(square(), Join::Round, 2.0, corner));
+}
+
+#[test]
+fn miter_join_falls_back_to_bevel_beyond_limit() {
+    let corner = [-0.9, 10.9];
+    assert!(!is_over(square(), Join::Miter(1.0), 2.0, corner));
+}
+
+#[test]
+fn closed_outline_does_not_overlap() {
+    // A thickness 2 outline of a 10x10 square covers the area between a 12x12 and 8x8 square.
+    let expected = 12.0 * 12.0 - 8.0 * 8.0;
+    let area = triangles_area(square(), Join::Miter(4.0), 2.0);
+    assert!((area - expected).abs() < 1e-6, "{} != {}", area, expected);
+}
+
+#[test]
+fn straight_path_has_no_join() {
+    let points = vec![[0.0, 0.0], [5.0, 0.0], [10.0, 0.0]];
+    assert_eq!(triangles(points, Join::Round, 2.0).unwrap().count(), 4);
+}
@@ -0,0 +1,80 @@
+use color;
+use render::PrimitiveKind;
+use widget;
+use {Colorable, Positionable, Sizeable, Ui, UiBuilder, Widget};
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+#[test]
+fn nested_opacity_composes() {
+    let mut ui = windowless_ui();
+    let outer = ui.widget_id_generator().next();
+    let inner = ui.widget_id_generator().next();
+    let rectangle = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().opacity(0.5).set(outer, ui);
+        widget::Canvas::new()
+            .parent(outer)
+            .opacity(0.5)
+            .set(inner, ui);
+        widget::Rectangle::fill([10.0, 10.0])
+            .middle_of(inner)
+            .color(color::RED)
+            .set(rectangle, ui);
+    }
+
+    let mut primitives = ui.draw();
+    let mut alpha = None;
+    while let Some(primitive) = primitives.next() {
+        if primitive.id == rectangle {
+            if let PrimitiveKind::Rectangle { color } = primitive.kind {
+                let color::Rgba(_, _, _, a) = color.to_rgb();
+                alpha = Some(a);
+            }
+        }
+    }
+    assert_eq!(alpha, Some(0.25));
+}
+
+#[test]
+fn shadow_fades_to_transparent_beyond_rect() {
+    let mut ui = windowless_ui();
+    let shadow = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Shadow::new()
+            .w_h(100.0, 100.0)
+            .middle()
+            .offset([5.0, -5.0])
+            .blur(10.0)
+            .color(color::BLACK)
+            .set(shadow, ui);
+    }
+
+    let mut primitives = ui.draw();
+    let mut found = false;
+    while let Some(primitive) = primitives.next() {
+        if primitive.id != shadow {
+            continue;
+        }
+        found = true;
+        match primitive.kind {
+            PrimitiveKind::TrianglesMultiColor { triangles } => {
+                assert!(!triangles.is_empty());
+                for &(p, color::Rgba(_, _, _, a)) in triangles.iter().flat_map(|t| t.0.iter()) {
+                    // Opaque points lie within the core while transparent points lie on the
+                    // outer edge, half of the blur beyond the offset rect.
+                    let extent = if a == 0.0 { 55.0 } else { 45.0 };
+                    assert!(a == 0.0 || a == 1.0);
+                    assert!((p[0] - 5.0).abs() <= extent + 1e-9);
+                    assert!((p[1] + 5.0).abs() <= extent + 1e-9);
+                }
+            }
+            _ => panic!("expected the shadow to be drawn as `TrianglesMultiColor`"),
+        }
+    }
+    assert!(found);
+}
@@ -0,0 +1,175 @@
+use color;
+use event::Input;
+use input::{Button, Motion, MouseButton};
+use render::PrimitiveKind;
+use text;
+use widget;
+use widget::rich_text::{Event, Span};
+use {Colorable, Positionable, Sizeable, Ui, UiBuilder, Widget};
+
+fn font() -> text::Font {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    text::font::from_file(path).unwrap()
+}
+
+fn windowless_ui() -> Ui {
+    let mut ui = UiBuilder::new([800.0, 600.0]).build();
+    ui.fonts.insert(font());
+    ui
+}
+
+fn spans() -> Vec<Span<'static>> {
+    vec![
+        Span::new("The quick brown "),
+        Span::new("fox jumps").font_size(24).color(color::RED),
+        Span::new(" over the lazy dog.").underline(),
+    ]
+}
+
+fn set_rich_text(ui: &mut Ui, id: widget::Id, spans: &[Span]) -> Event {
+    let ui = &mut ui.set_widgets();
+    widget::RichText::new(spans).w(120.0).middle().set(id, ui)
+}
+
+#[test]
+fn spans_wrap_together_across_lines() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let spans = spans();
+    set_rich_text(&mut ui, id, &spans);
+
+    let rect = ui.rect_of(id).unwrap();
+    let state = ui
+        .widget_graph()
+        .widget(id)
+        .and_then(|container| container.unique_widget_state::<widget::RichText>())
+        .unwrap();
+    let runs = state.state.runs();
+
+    // Every run lies within the widget and no span's text is lost.
+    for run in runs {
+        assert!(run.rect.left() >= rect.left() - 1e-6);
+        assert!(run.rect.right() <= rect.right() + 1e-6);
+    }
+    let mut bottoms: Vec<_> = runs.iter().map(|run| run.rect.bottom()).collect();
+    bottoms.dedup();
+    assert!(bottoms.len() > 2);
+    let words = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+    let all_text: String = spans.iter().map(|span| span.text).collect();
+    let run_text: String = runs
+        .iter()
+        .map(|run| format!("{} ", &spans[run.span].text[run.bytes.clone()]))
+        .collect();
+    assert_eq!(words(&run_text), words(&all_text));
+
+    // The larger span raises the height of its line.
+    let big = runs.iter().find(|run| run.span == 1).unwrap();
+    assert_eq!(big.rect.h(), 24.0);
+}
+
+#[test]
+fn spans_wrap_like_text() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let text = "The quick brown fox jumps over the lazy dog.";
+    let spans = [
+        Span::new(&text[..16]),
+        Span::new(&text[16..]).color(color::RED),
+    ];
+    set_rich_text(&mut ui, id, &spans);
+
+    let state = ui
+        .widget_graph()
+        .widget(id)
+        .and_then(|container| container.unique_widget_state::<widget::RichText>())
+        .unwrap();
+    let mut lines: Vec<String> = vec![];
+    let mut last_bottom = None;
+    for run in state.state.runs() {
+        let run_text = &spans[run.span].text[run.bytes.clone()];
+        match last_bottom {
+            Some(bottom) if bottom == run.rect.bottom() => {
+                lines.last_mut().unwrap().push_str(run_text)
+            }
+            _ => lines.push(run_text.to_owned()),
+        }
+        last_bottom = Some(run.rect.bottom());
+    }
+
+    // Spans sharing a font and size break where a single `Text` would.
+    let font = ui
+        .fonts
+        .ids()
+        .next()
+        .and_then(|id| ui.fonts.chain(id))
+        .unwrap();
+    let font_size = ui.theme.font_size_medium;
+    let expected: Vec<_> = text::line::infos(text, font, font_size)
+        .wrap_by_whitespace(120.0)
+        .map(|info| text[info.byte_range()].to_owned())
+        .collect();
+    assert!(expected.len() > 2);
+    assert_eq!(lines, expected);
+}
+
+#[test]
+fn each_run_is_drawn_as_text() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let spans = spans();
+    set_rich_text(&mut ui, id, &spans);
+
+    let mut texts = vec![];
+    let mut decorations = 0;
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        match primitive.kind {
+            PrimitiveKind::Text { color, .. } => texts.push(color),
+            PrimitiveKind::TrianglesSingleColor { .. } => decorations += 1,
+            _ => (),
+        }
+    }
+    assert!(texts.len() >= spans.len());
+    assert!(texts.contains(&color::RED));
+    // The underlined span is underlined on each of its runs.
+    assert!(decorations > 0);
+}
+
+#[test]
+fn clicking_a_span_reports_its_index() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let spans = spans();
+    set_rich_text(&mut ui, id, &spans);
+
+    let target = {
+        let state = ui
+            .widget_graph()
+            .widget(id)
+            .and_then(|container| container.unique_widget_state::<widget::RichText>())
+            .unwrap();
+        state
+            .state
+            .runs()
+            .iter()
+            .find(|run| run.span == 1)
+            .unwrap()
+            .rect
+            .xy()
+    };
+    ui.handle_event(Input::Motion(Motion::MouseCursor {
+        x: target[0],
+        y: target[1],
+    }));
+    let event = set_rich_text(&mut ui, id, &spans);
+    assert_eq!(event.hovered, Some(1));
+    assert_eq!(event.clicked, None);
+
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    let event = set_rich_text(&mut ui, id, &spans);
+    assert_eq!(event.clicked, Some(1));
+}
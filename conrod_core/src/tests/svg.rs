@@ -0,0 +1,160 @@
+use color::{self, Rgba};
+use render::PrimitiveKind;
+use widget;
+use widget::svg::{Document, Error};
+use widget::triangles::{ColoredPoint, Triangle};
+use {Colorable, Positionable, Rect, Sizeable, Ui, UiBuilder, Widget};
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+fn area(triangles: &[Triangle<ColoredPoint>]) -> f64 {
+    triangles
+        .iter()
+        .map(|t| {
+            let [(a, _), (b, _), (c, _)] = t.0;
+            ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.0
+        })
+        .sum()
+}
+
+fn bounds(triangles: &[Triangle<ColoredPoint>]) -> Rect {
+    let mut points = triangles.iter().flat_map(|t| t.0.iter().map(|&(p, _)| p));
+    let first = points.next().unwrap();
+    points.fold(Rect::from_corners(first, first), |r, p| {
+        r.stretch_to_point(p)
+    })
+}
+
+#[test]
+fn rect_fills_view_box() {
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">
+        <rect width="10" height="10" fill="#ff0000"/>
+    </svg>"##;
+    let document = Document::parse(svg).unwrap();
+    assert_eq!(
+        document.view_box,
+        Rect::from_corners([0.0, 0.0], [10.0, 10.0])
+    );
+    assert!((area(&document.triangles) - 100.0).abs() < 1e-9);
+    for &(_, color) in document.triangles.iter().flat_map(|t| t.0.iter()) {
+        assert_eq!(color, Rgba(1.0, 0.0, 0.0, 1.0));
+    }
+}
+
+#[test]
+fn fill_rules() {
+    let svg = |rule: &str| {
+        format!(
+            r#"<svg viewBox="0 0 10 10"><path fill-rule="{}" d="M0 0H10V10H0Z M2 2H8V8H2Z"/></svg>"#,
+            rule
+        )
+    };
+    let even_odd = Document::parse(&svg("evenodd")).unwrap();
+    assert!((area(&even_odd.triangles) - 64.0).abs() < 1e-9);
+    let non_zero = Document::parse(&svg("nonzero")).unwrap();
+    assert!((area(&non_zero.triangles) - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn circle_is_flattened_smoothly() {
+    let svg = r#"<svg width="20" height="20"><circle cx="10" cy="10" r="10"/></svg>"#;
+    let document = Document::parse(svg).unwrap();
+    let expected = std::f64::consts::PI * 100.0;
+    assert!((area(&document.triangles) - expected).abs() / expected < 5e-3);
+}
+
+#[test]
+fn transforms_compose() {
+    let svg = r#"<svg viewBox="0 0 10 10">
+        <g transform="translate(5 0)"><rect transform="scale(2)" width="1" height="1"/></g>
+    </svg>"#;
+    let document = Document::parse(svg).unwrap();
+    assert_eq!(
+        bounds(&document.triangles),
+        Rect::from_corners([5.0, 0.0], [7.0, 2.0])
+    );
+}
+
+#[test]
+fn stroke_surrounds_path() {
+    let svg = r#"<svg viewBox="0 0 10 10">
+        <line x1="1" y1="5" x2="9" y2="5" stroke="blue" stroke-width="2"/>
+    </svg>"#;
+    let document = Document::parse(svg).unwrap();
+    assert_eq!(
+        bounds(&document.triangles),
+        Rect::from_corners([1.0, 4.0], [9.0, 6.0])
+    );
+}
+
+#[test]
+fn linear_gradient_interpolates_stops() {
+    let svg = r##"<svg viewBox="0 0 10 10">
+        <defs>
+            <linearGradient id="a"><stop offset="0" stop-color="#f00"/><stop offset="1" stop-color="#00f"/></linearGradient>
+        </defs>
+        <rect width="10" height="10" fill="url(#a)"/>
+    </svg>"##;
+    let document = Document::parse(svg).unwrap();
+    for &(p, Rgba(r, _, b, _)) in document.triangles.iter().flat_map(|t| t.0.iter()) {
+        let t = (p[0] / 10.0) as f32;
+        assert!((r - (1.0 - t)).abs() < 1e-6);
+        assert!((b - t).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    match Document::parse("<html></html>") {
+        Err(Error::NotSvg) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match Document::parse("<svg><g></svg>") {
+        Err(Error::Xml(_)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+    match Document::parse("<svg></svg>") {
+        Err(Error::NoViewBox) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn tinted_svg_fits_rect() {
+    let mut ui = windowless_ui();
+    let id = ui.widget_id_generator().next();
+    let svg = r#"<svg viewBox="0 0 20 10"><rect width="20" height="10" fill="red"/></svg>"#;
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Svg::new(svg)
+            .w_h(100.0, 100.0)
+            .middle()
+            .color(color::GREEN)
+            .set(id, ui);
+    }
+
+    let mut primitives = ui.draw();
+    let mut found = false;
+    while let Some(primitive) = primitives.next() {
+        if primitive.id != id {
+            continue;
+        }
+        found = true;
+        match primitive.kind {
+            PrimitiveKind::TrianglesMultiColor { triangles } => {
+                // The document is scaled uniformly and centred within the rect.
+                let bounds = bounds(triangles);
+                assert!((bounds.w() - 100.0).abs() < 1e-9);
+                assert!((bounds.h() - 50.0).abs() < 1e-9);
+                assert!(bounds.x().abs() < 1e-9 && bounds.y().abs() < 1e-9);
+                for &(_, color) in triangles.iter().flat_map(|t| t.0.iter()) {
+                    assert_eq!(color, color::GREEN.to_rgb());
+                }
+            }
+            _ => panic!("expected the svg to be drawn as `TrianglesMultiColor`"),
+        }
+    }
+    assert!(found);
+}
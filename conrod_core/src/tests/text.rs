@@ -0,0 +1,1481 @@
+use position::{Align, Rect};
+use render::PrimitiveKind;
+use text::{self, Direction, Justify};
+use widget;
+use {Positionable, Sizeable, UiBuilder, Widget};
+
+const TEXT: &str = "The quick brown fox jumps over the lazy dog.\nThe end.";
+const FONT_SIZE: u32 = 14;
+
+fn font() -> text::Font {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    text::font::from_file(path).unwrap()
+}
+
+fn infos(font: &text::Font, max_width: f64) -> Vec<text::line::Info> {
+    text::line::infos(TEXT, font, FONT_SIZE)
+        .wrap_by_whitespace(max_width)
+        .collect()
+}
+
+#[test]
+fn full_justify_stretches_wrapped_lines() {
+    let font = font();
+    let rect = Rect::from_corners([0.0, 0.0], [150.0, 200.0]);
+    let infos = infos(&font, rect.w());
+    assert!(infos.len() > 2);
+    let rects: Vec<_> = text::line::rects(
+        infos.iter().cloned(),
+        FONT_SIZE,
+        rect,
+        Justify::Full,
+        Align::End,
+        0.0,
+    )
+    .collect();
+
+    for (info, line_rect) in infos.iter().zip(&rects) {
+        match info.end_break {
+            text::line::Break::Wrap { .. } => assert_eq!(line_rect.x, rect.x),
+            // The last line of each paragraph is aligned to the start.
+            _ => {
+                assert_eq!(line_rect.left(), rect.left());
+                assert!((line_rect.w() - info.width).abs() < 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn full_justify_cursor_reaches_both_edges() {
+    let font = font();
+    let rect = Rect::from_corners([0.0, 0.0], [150.0, 200.0]);
+    let infos = infos(&font, rect.w());
+    let xys = text::cursor::xys_per_line_from_text(
+        TEXT,
+        &infos,
+        &font,
+        FONT_SIZE,
+        Justify::Full,
+        Align::End,
+        0.0,
+        rect,
+    );
+    for ((xs, _), info) in xys.zip(&infos) {
+        let xs: Vec<_> = xs.collect();
+        assert_eq!(xs[0], rect.left());
+        if let text::line::Break::Wrap { .. } = info.end_break {
+            // The last glyph's pixel bounds may fall short of its advance.
+            let last = *xs.last().unwrap();
+            assert!(
+                rect.right() - last < 2.0,
+                "{} does not reach the edge",
+                last
+            );
+        }
+    }
+}
+
+#[test]
+fn full_justify_glyphs_match_cursor() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(TEXT)
+            .font_size(FONT_SIZE)
+            .w_h(150.0, 200.0)
+            .middle()
+            .full_justify()
+            .set(id, ui);
+    }
+
+    let font = font();
+    let mut primitives = ui.draw();
+    let primitive = loop {
+        let primitive = primitives.next().expect("no text primitive");
+        if primitive.id == id {
+            break primitive;
+        }
+    };
+    let text = match primitive.kind {
+        PrimitiveKind::Text { text, .. } => text,
+        _ => panic!("expected text"),
+    };
+    let glyphs: Vec<_> = text.positioned_glyphs(1.0).collect();
+
+    // Compare the right edge of each glyph on the first line with the cursor that follows it.
+    let infos = infos(&font, primitive.rect.w());
+    let mut xys = text::cursor::xys_per_line_from_text(
+        TEXT,
+        &infos,
+        &font,
+        FONT_SIZE,
+        Justify::Full,
+        Align::End,
+        0.0,
+        primitive.rect,
+    );
+    let (xs, _) = xys.next().unwrap();
+    let first_line = infos[0].char_range().len();
+    let cursor_xs: Vec<_> = xs.skip(1).collect();
+    assert_eq!(cursor_xs.len(), first_line);
+    for (glyph, cursor_x) in glyphs[..first_line].iter().zip(cursor_xs) {
+        if let Some(bb) = glyph.pixel_bounding_box() {
+            let glyph_right = bb.max.x as f64 - 200.0;
+            assert!((glyph_right - cursor_x).abs() <= 1.0);
+        }
+    }
+}
+
+#[test]
+fn font_map_fallbacks() {
+    let mut fonts = text::font::Map::new();
+    let regular = fonts.insert(font());
+    let fallback = fonts.insert(font());
+    let missing = {
+        let mut other = text::font::Map::new();
+        other.insert(font());
+        other.insert(font());
+        other.insert(font())
+    };
+
+    // Unknown ids and the font itself are ignored.
+    fonts.set_fallbacks(regular, vec![missing, regular, fallback]);
+    assert_eq!(fonts.fallbacks(regular), vec![fallback]);
+    assert!(fonts.fallbacks(fallback).is_empty());
+    let chain = fonts.chain(regular).unwrap();
+    assert_eq!(chain.fallbacks().len(), 1);
+    assert!(fonts.chain(missing).is_none());
+
+    fonts.set_fallbacks(regular, None);
+    assert!(fonts.fallbacks(regular).is_empty());
+}
+
+#[test]
+fn uncovered_chars_use_the_first_font() {
+    let mut fonts = text::font::Map::new();
+    let regular = fonts.insert(font());
+    let fallback = fonts.insert(font());
+    fonts.set_fallbacks(regular, vec![fallback]);
+    let chain = fonts.chain(regular).unwrap();
+
+    // The first font that covers a character is used, otherwise the first font's notdef glyph.
+    assert_eq!(chain.font_for('a').0, None);
+    assert_eq!(chain.font_for('\u{4e2d}').0, None);
+    assert_eq!(
+        text::line::natural_width(TEXT, chain, FONT_SIZE),
+        text::line::natural_width(TEXT, fonts.get(regular).unwrap(), FONT_SIZE)
+    );
+}
+
+#[test]
+fn text_glyphs_are_paired_with_their_font() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let regular = ui.fonts.insert(font());
+    let fallback = ui.fonts.insert(font());
+    ui.fonts.set_fallbacks(regular, vec![fallback]);
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Fallback")
+            .font_id(regular)
+            .font_size(FONT_SIZE)
+            .middle()
+            .set(id, ui);
+    }
+
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { text, font_id, .. } = primitive.kind {
+            assert_eq!(font_id, regular);
+            let glyphs: Vec<_> = text.positioned_glyphs_with_font_ids(1.0).collect();
+            assert_eq!(glyphs.len(), "Fallback".len());
+            assert!(glyphs.iter().all(|&(id, _)| id == regular));
+            return;
+        }
+    }
+    panic!("no text primitive");
+}
+
+#[test]
+fn cursor_moves_by_grapheme() {
+    // An "e" followed by a combining acute accent forms a single grapheme cluster.
+    let text = "ae\u{301}b";
+    let font = font();
+    let infos: Vec<_> = text::line::infos(text, &font, FONT_SIZE).collect();
+    let index = |char| text::cursor::Index {
+        line: 0,
+        char: char,
+    };
+    let lines = || infos.iter().cloned();
+
+    assert_eq!(index(1).next_grapheme(text, lines()), Some(index(3)));
+    assert_eq!(index(3).previous_grapheme(text, lines()), Some(index(1)));
+    assert_eq!(index(4).previous_grapheme(text, lines()), Some(index(3)));
+    assert_eq!(index(4).next_grapheme(text, lines()), None);
+    assert_eq!(index(2).snap_to_grapheme(text, lines()), index(1));
+    assert_eq!(index(4).snap_to_grapheme(text, lines()), index(4));
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn shaped_advances_match_glyph_layout() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let mut fonts = text::font::Map::new();
+    let id = fonts.insert_from_file(path).unwrap();
+    let chain = fonts.chain(id).unwrap();
+    assert!(chain.face().is_some());
+
+    let line = "office e\u{301}";
+    let scale = text::pt_to_scale(FONT_SIZE);
+    let shaped = chain.shape(line, scale).unwrap();
+    assert_eq!(shaped.advances.len(), line.chars().count());
+    // The combining accent belongs to the grapheme of its base, so it has no advance of its own.
+    assert_eq!(shaped.advances[8], 0.0);
+
+    let point = text::rt::point(0.0, 0.0);
+    let glyphs: Vec<_> = chain.layout(line, scale, point).collect();
+    assert_eq!(glyphs.len(), shaped.glyphs.len());
+    assert!(glyphs
+        .iter()
+        .all(|&(char_i, id, _)| char_i < 9 && id.is_none()));
+    let width: f64 = shaped.advances.iter().sum();
+    assert!((text::line::natural_width(line, chain, FONT_SIZE) - width).abs() < 1e-6);
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn wrapped_lines_share_the_advances_of_their_shaped_paragraph() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let mut fonts = text::font::Map::new();
+    let id = fonts.insert_from_file(path).unwrap();
+    let chain = fonts.chain(id).unwrap();
+    let scale = text::pt_to_scale(FONT_SIZE);
+
+    // Given advances are yielded for text beginning with their line, followed by those of glyphs.
+    let text = "ab\ncd";
+    let given = [1.0, 2.0];
+    let advances: Vec<_> = chain
+        .with_shaped_advances(&text[..2], scale, &given)
+        .advances(text, scale)
+        .collect();
+    assert_eq!(&advances[..2], &given[..]);
+    assert_eq!(advances.len(), 5);
+    let advances: Vec<_> = chain
+        .with_shaped_advances(&text[..2], scale, &given)
+        .advances(&text[3..], scale)
+        .collect();
+    assert!(advances.iter().all(|&advance| advance > 2.0));
+
+    // Each line wrapped from a paragraph is given the remaining advances of the whole paragraph.
+    let paragraph = "office officer offices";
+    let shaped = chain.shape(paragraph, scale).unwrap().advances;
+    let infos: Vec<_> = text::line::infos_wrapped_by(
+        paragraph,
+        chain,
+        FONT_SIZE,
+        60.0,
+        |text: &str, chain: text::font::Chain, font_size, max_width| {
+            let start = paragraph.len() - text.len();
+            let start_char = paragraph[..start].chars().count();
+            let advances: Vec<_> = chain.advances(text, scale).collect();
+            assert_eq!(advances, &shaped[start_char..]);
+            text::line::break_by_whitespace(
+                text,
+                chain.advances(text, text::pt_to_scale(font_size)),
+                max_width,
+            )
+        },
+    )
+    .collect();
+    assert!(infos.len() > 1);
+}
+
+#[test]
+fn paragraphs_take_the_direction_of_their_first_strong_char() {
+    let text = "abc \u{5d0}\u{5d1}\u{5d2} def\n\u{5d0}\u{5d1}\u{5d2} abc";
+    let font = font();
+    let infos: Vec<_> = text::line::infos(text, &font, FONT_SIZE).collect();
+    assert_eq!(infos[0].direction, Direction::LeftToRight);
+    assert_eq!(infos[1].direction, Direction::RightToLeft);
+
+    let runs: Vec<_> = infos[0]
+        .visual_runs(text)
+        .into_iter()
+        .map(|run| (run.char_range, run.direction))
+        .collect();
+    assert_eq!(
+        runs,
+        vec![
+            (0..4, Direction::LeftToRight),
+            (4..7, Direction::RightToLeft),
+            (7..11, Direction::LeftToRight),
+        ]
+    );
+
+    // The runs of a right-to-left paragraph are ordered from its end.
+    let runs = infos[1].visual_runs(text);
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].char_range, 4..7);
+    assert_eq!(runs[1].char_range, 0..4);
+    assert!(runs[1].is_right_to_left());
+}
+
+#[test]
+fn cursor_moves_visually_through_rtl_text() {
+    let text = "ab \u{5d0}\u{5d1}";
+    let font = font();
+    let infos: Vec<_> = text::line::infos(text, &font, FONT_SIZE).collect();
+    let index = |char| text::cursor::Index {
+        line: 0,
+        char: char,
+    };
+    let lines = || infos.iter().cloned();
+
+    // Visually "ab \u{5d1}\u{5d0}", so moving right past the space reaches the end of the text
+    // and then moves backwards through the Hebrew word towards its start.
+    assert_eq!(index(2).right(text, lines()), Some(index(5)));
+    assert_eq!(index(5).right(text, lines()), Some(index(4)));
+    assert_eq!(index(4).right(text, lines()), Some(index(3)));
+    assert_eq!(index(3).right(text, lines()), None);
+    assert_eq!(index(3).left(text, lines()), Some(index(4)));
+    assert_eq!(index(1).left(text, lines()), Some(index(0)));
+
+    // The cursor before each Hebrew char lies on its right edge.
+    let rect = Rect::from_corners([0.0, 0.0], [400.0, 100.0]);
+    let line_xs = || {
+        let mut xys = text::cursor::xys_per_line_from_text(
+            text,
+            &infos,
+            &font,
+            FONT_SIZE,
+            Justify::Left,
+            Align::End,
+            0.0,
+            rect,
+        );
+        xys.next().unwrap().0
+    };
+    let xs: Vec<_> = line_xs().collect();
+    assert_eq!(xs.len(), 6);
+    assert!(xs[0] < xs[1] && xs[1] < xs[2] && xs[2] < xs[5]);
+    assert!(xs[5] < xs[4] && xs[4] < xs[3]);
+    let (closest, _) = text::cursor::closest_cursor_index_on_line(xs[4] + 0.1, line_xs());
+    assert_eq!(closest, 4);
+
+    // Selecting the space and the first Hebrew char highlights two separate ranges.
+    let lines = infos.iter().map(|info| &text[info.byte_range()]);
+    let line_rects = text::line::rects(
+        infos.iter().cloned(),
+        FONT_SIZE,
+        rect,
+        Justify::Left,
+        Align::End,
+        0.0,
+    );
+    let selected: Vec<_> =
+        text::line::selected_rects(lines.zip(line_rects), &font, FONT_SIZE, index(2), index(4))
+            .collect();
+    assert_eq!(selected.len(), 2);
+    assert_eq!(selected[0].x.start, xs[2]);
+    assert_eq!(selected[1].x.end, xs[3]);
+}
+
+#[test]
+fn rtl_glyphs_are_drawn_in_visual_order() {
+    // The right-to-left override reverses the Latin letters that follow it.
+    let text = "xy \u{202e}abc";
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(text)
+            .font_size(FONT_SIZE)
+            .middle()
+            .set(id, ui);
+    }
+
+    let font = font();
+    let override_id = font.glyph('\u{202e}').id();
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { text, .. } = primitive.kind {
+            let glyphs: Vec<_> = text.positioned_glyphs(1.0).collect();
+            let xs: Vec<_> = glyphs.iter().map(|g| g.position().x).collect();
+            assert!(xs.windows(2).all(|w| w[0] <= w[1]));
+            let ids: Vec<_> = glyphs
+                .iter()
+                .map(|g| g.id())
+                .filter(|&id| id != override_id)
+                .collect();
+            let expected: Vec<_> = "xy cba".chars().map(|ch| font.glyph(ch).id()).collect();
+            assert_eq!(ids, expected);
+            return;
+        }
+    }
+    panic!("no text primitive");
+}
+
+#[test]
+fn truncate_elides_lines_wider_than_the_max_width() {
+    use widget::text::{truncate, Truncate, ELLIPSIS};
+
+    let font = font();
+    let path = "/home/user/documents/projects/conrod/readme.md";
+    let max_width = text::line::width(path, &font, FONT_SIZE) / 2.0;
+
+    let end = truncate(path, Truncate::End, &font, FONT_SIZE, max_width);
+    assert!(end.starts_with("/home/") && end.ends_with(ELLIPSIS));
+    let start = truncate(path, Truncate::Start, &font, FONT_SIZE, max_width);
+    assert!(start.starts_with(ELLIPSIS) && start.ends_with("readme.md"));
+    let middle = truncate(path, Truncate::Middle, &font, FONT_SIZE, max_width);
+    assert!(middle.starts_with("/home/") && middle.ends_with(".md"));
+    assert!(middle.contains(ELLIPSIS));
+
+    for truncated in &[&end, &start, &middle] {
+        assert!(text::line::width(truncated, &font, FONT_SIZE) <= max_width);
+    }
+
+    // Text that already fits is left untouched.
+    let fits = truncate(path, Truncate::End, &font, FONT_SIZE, max_width * 2.0);
+    assert_eq!(fits, ::std::borrow::Cow::Borrowed(path));
+}
+
+#[test]
+fn truncated_text_keeps_the_full_string_in_state() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(TEXT)
+            .font_size(FONT_SIZE)
+            .w(100.0)
+            .middle()
+            .truncate(widget::text::Truncate::End)
+            .set(id, ui);
+    }
+
+    let state = ui
+        .widget_graph()
+        .widget(id)
+        .and_then(|container| container.unique_widget_state::<widget::Text>())
+        .unwrap();
+    assert_eq!(state.state.string, TEXT);
+    let displayed = state.state.displayed();
+    assert_ne!(displayed, TEXT);
+    assert_eq!(displayed.lines().count(), 2);
+    assert_eq!(state.state.line_infos.len(), 2);
+    assert!(displayed.ends_with("The end."));
+}
+
+#[test]
+fn font_weight_and_style_are_guessed_from_names() {
+    use text::font::{Style, Weight};
+    let guess = Weight::and_style_from_name;
+    assert_eq!(guess("NotoSans-Regular"), (Weight::NORMAL, Style::Normal));
+    assert_eq!(guess("NotoSans-BoldItalic"), (Weight::BOLD, Style::Italic));
+    assert_eq!(guess("Inter Semi-Bold"), (Weight::SEMI_BOLD, Style::Normal));
+    assert_eq!(
+        guess("Roboto_ExtraLightOblique"),
+        (Weight::EXTRA_LIGHT, Style::Italic)
+    );
+}
+
+#[test]
+fn font_families_select_the_closest_member() {
+    use text::font::{Style, Weight};
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans");
+    let mut fonts = text::font::Map::new();
+    let family = fonts.insert_family_from_dir("Noto Sans", dir).unwrap();
+    assert_eq!(fonts.family_id("Noto Sans"), Some(family));
+    assert_eq!(fonts.family_name(family), Some("Noto Sans"));
+    assert_eq!(fonts.ids().count(), 4);
+
+    let select = |weight, style| fonts.select(family, weight, style).unwrap();
+    let regular = select(Weight::NORMAL, Style::Normal);
+    let bold = select(Weight::BOLD, Style::Normal);
+    let italic = select(Weight::NORMAL, Style::Italic);
+    let bold_italic = select(Weight::BOLD, Style::Italic);
+    let mut ids = vec![regular, bold, italic, bold_italic];
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 4);
+
+    // Missing weights resolve to the nearest member, favouring heavier fonts for heavy weights.
+    assert_eq!(select(Weight::BLACK, Style::Normal), bold);
+    assert_eq!(select(Weight::SEMI_BOLD, Style::Normal), bold);
+    assert_eq!(select(Weight::MEDIUM, Style::Normal), regular);
+    assert_eq!(select(Weight::LIGHT, Style::Italic), italic);
+}
+
+#[test]
+fn text_selects_its_font_from_the_theme_family() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans");
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let family = ui.fonts.insert_family_from_dir("Noto Sans", dir).unwrap();
+    ui.theme.font_family = Some(family);
+    let bold = ui
+        .fonts
+        .select(family, text::font::Weight::BOLD, text::font::Style::Normal);
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Bold").bold().middle().set(id, ui);
+    }
+
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { font_id, .. } = primitive.kind {
+            assert_eq!(Some(font_id), bold);
+            return;
+        }
+    }
+    panic!("no text primitive");
+}
+
+#[test]
+fn widget_fonts_take_precedence_over_the_theme_font() {
+    use text::font::{Style, Weight};
+    use Labelable;
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans");
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let family = ui.fonts.insert_family_from_dir("Noto Sans", dir).unwrap();
+    let regular = ui.fonts.select(family, Weight::NORMAL, Style::Normal);
+    let bold = ui.fonts.select(family, Weight::BOLD, Style::Normal);
+    ui.theme.font_id = regular;
+    ui.theme.font_family = Some(family);
+    let text = ui.widget_id_generator().next();
+    let button = ui.widget_id_generator().next();
+    let plain = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Bold").bold().top_left().set(text, ui);
+        widget::Button::new()
+            .label("Bold")
+            .label_font_weight(Weight::BOLD)
+            .w_h(100.0, 40.0)
+            .middle()
+            .set(button, ui);
+        widget::Text::new("Plain").bottom_left().set(plain, ui);
+    }
+
+    let mut font_ids = vec![];
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { font_id, .. } = primitive.kind {
+            font_ids.push(Some(font_id));
+        }
+    }
+    assert_eq!(font_ids, vec![bold, bold, regular]);
+}
+
+#[test]
+fn text_input_and_rich_text_select_fonts_by_weight() {
+    use text::font::{Style, Weight};
+    use widget::rich_text::Span;
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans");
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let family = ui.fonts.insert_family_from_dir("Noto Sans", dir).unwrap();
+    let regular = ui.fonts.select(family, Weight::NORMAL, Style::Normal);
+    let bold = ui.fonts.select(family, Weight::BOLD, Style::Normal);
+    ui.theme.font_id = regular;
+    let (text_edit, text_box, rich_text) = {
+        let mut id_gen = ui.widget_id_generator();
+        (id_gen.next(), id_gen.next(), id_gen.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::TextEdit::new("Edit")
+            .font_family(family)
+            .font_weight(Weight::BOLD)
+            .w_h(100.0, 40.0)
+            .top_left()
+            .set(text_edit, ui);
+        widget::TextBox::new("Box")
+            .font_family(family)
+            .font_weight(Weight::BOLD)
+            .w_h(100.0, 40.0)
+            .middle()
+            .set(text_box, ui);
+        widget::RichText::new(&[Span::new("Rich")])
+            .font_family(family)
+            .font_weight(Weight::BOLD)
+            .w(100.0)
+            .bottom_left()
+            .set(rich_text, ui);
+    }
+
+    let mut font_ids = vec![];
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { font_id, .. } = primitive.kind {
+            font_ids.push(Some(font_id));
+        }
+    }
+    assert_eq!(font_ids, vec![bold, bold, bold]);
+}
+
+#[test]
+fn decorations_are_drawn_around_the_text() {
+    use color;
+
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let (plain, decorated) = {
+        let mut id_gen = ui.widget_id_generator();
+        (id_gen.next(), id_gen.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Plain")
+            .font_size(FONT_SIZE)
+            .mid_top()
+            .set(plain, ui);
+        widget::Text::new("Decorated")
+            .font_size(FONT_SIZE)
+            .middle()
+            .background(color::YELLOW)
+            .wavy_underline()
+            .strikethrough()
+            .decoration_color(color::RED)
+            .set(decorated, ui);
+    }
+
+    let kinds = |id| {
+        let mut kinds = vec![];
+        let mut primitives = ui.draw();
+        while let Some(primitive) = primitives.next() {
+            if primitive.id != id {
+                continue;
+            }
+            kinds.push(match primitive.kind {
+                PrimitiveKind::TrianglesSingleColor { color, triangles } => {
+                    assert!(!triangles.is_empty());
+                    Some(color)
+                }
+                PrimitiveKind::Text { .. } => None,
+                _ => panic!("unexpected primitive"),
+            });
+        }
+        kinds
+    };
+    assert_eq!(kinds(plain), vec![None]);
+    assert_eq!(
+        kinds(decorated),
+        vec![
+            Some(color::YELLOW.to_rgb()),
+            None,
+            Some(color::RED.to_rgb())
+        ]
+    );
+
+    // The owned primitives retain all of the decorations.
+    let owned = ui.draw().owned();
+    let mut walk = owned.walk();
+    let mut count = 0;
+    while let Some(primitive) = walk.next() {
+        if primitive.id == decorated {
+            count += 1;
+        }
+    }
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn decoration_geometry_follows_the_font_metrics() {
+    use widget::text::{background_triangles, line_triangles, DecorationMetrics, Underline};
+
+    let font = font();
+    let metrics = DecorationMetrics::new(&font, FONT_SIZE);
+    assert!(metrics.descent < metrics.underline && metrics.underline < 0.0);
+    assert!(0.0 < metrics.strikethrough && metrics.strikethrough < metrics.ascent);
+
+    let line = Rect::from_corners([10.0, 100.0], [110.0, 114.0]);
+    let points = |tris: &[widget::triangles::Triangle<[f64; 2]>]| -> Vec<[f64; 2]> {
+        tris.iter().flat_map(|t| t.points().to_vec()).collect()
+    };
+
+    let mut tris = vec![];
+    background_triangles(Some(line), metrics, &mut tris);
+    let ys: Vec<f64> = points(&tris).iter().map(|p| p[1]).collect();
+    let min = ys.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = ys.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    assert_eq!(
+        (min, max),
+        (100.0 + metrics.descent, 100.0 + metrics.ascent)
+    );
+
+    let count = |underline, strikethrough| {
+        let mut tris = vec![];
+        line_triangles(Some(line), metrics, underline, strikethrough, &mut tris);
+        let points = points(&tris);
+        let t = metrics.thickness;
+        assert!(points.iter().all(|p| p[0] >= 10.0 - t && p[0] <= 110.0 + t));
+        tris.len()
+    };
+    let single = count(Some(Underline::Single), false);
+    assert!(single > 0);
+    assert_eq!(count(Some(Underline::Double), false), single * 2);
+    assert_eq!(count(Some(Underline::Single), true), single * 2);
+    assert!(count(Some(Underline::Wavy), false) > single * 2);
+    assert_eq!(count(None, false), 0);
+}
+
+#[test]
+fn decoration_metrics_are_read_from_the_font_tables() {
+    use widget::text::DecorationMetrics;
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    let mut fonts = text::font::Map::new();
+    let id = fonts.insert_from_file(path).unwrap();
+    let decorations = fonts.decorations(id);
+    assert_eq!(decorations.underline, Some((-154, 102)));
+    assert_eq!(decorations.strikeout, Some((512, 102)));
+
+    // Fonts inserted without their data fall back to the ratios of the vertical metrics.
+    let font = fonts.get(id).unwrap().clone();
+    let other = fonts.insert(font.clone());
+    assert_eq!(fonts.decorations(other), text::font::Decorations::default());
+    let font = &font;
+
+    let fallback = DecorationMetrics::new(font, FONT_SIZE);
+    let metrics = DecorationMetrics::from_tables(font, decorations, FONT_SIZE);
+    assert_eq!(
+        (metrics.ascent, metrics.descent),
+        (fallback.ascent, fallback.descent)
+    );
+    assert!(metrics != fallback);
+    // NotoSans has 2789 units between its ascent and descent.
+    let px_per_unit = (metrics.ascent - metrics.descent) / 2789.0;
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+    assert!(close(metrics.thickness, (102.0 * px_per_unit).max(1.0)));
+    assert!(close(
+        metrics.underline,
+        -154.0 * px_per_unit - metrics.thickness / 2.0
+    ));
+    assert!(close(
+        metrics.strikethrough,
+        512.0 * px_per_unit - metrics.strikethrough_thickness / 2.0
+    ));
+}
+
+#[test]
+fn outline_passes_ring_the_glyphs() {
+    use color;
+    use widget::text::Outline;
+
+    let outline = Outline {
+        width: 2.5,
+        color: color::BLACK,
+    };
+    assert_eq!(outline.passes(), 24);
+    assert!(outline.offset(24).is_none());
+    let mut offsets = vec![];
+    for pass in 0..outline.passes() {
+        let [x, y] = outline.offset(pass).unwrap();
+        assert!(x == x.round() && y == y.round());
+        assert!((x != 0.0 || y != 0.0) && x.abs() <= 3.0 && y.abs() <= 3.0);
+        offsets.push([x as i32, y as i32]);
+    }
+    assert_eq!(outline.offset(0), Some([1.0, 0.0]));
+    assert_eq!(outline.offset(16), Some([3.0, 0.0]));
+    offsets.sort();
+    offsets.dedup();
+    assert_eq!(offsets.len(), 24);
+
+    // Thin outlines draw a single ring while wide outlines draw no more than three.
+    let thin = Outline {
+        width: 0.3,
+        ..outline
+    };
+    assert_eq!(thin.passes(), 8);
+    assert_eq!(thin.offset(1), Some([1.0, 1.0]));
+    let wide = Outline {
+        width: 40.0,
+        ..outline
+    };
+    assert_eq!(wide.passes(), 24);
+    assert_eq!(wide.offset(23).map(|[x, _]| x), Some(40.0));
+}
+
+#[test]
+fn outline_and_shadow_are_drawn_as_extra_glyph_passes() {
+    use color;
+    use image;
+    use mesh::{self, Mesh};
+
+    struct NoImage;
+    impl mesh::ImageDimensions for NoImage {
+        fn dimensions(&self) -> [u32; 2] {
+            [0, 0]
+        }
+    }
+
+    let text_vertices = |effects: bool| {
+        let mut ui = UiBuilder::new([400.0, 400.0]).build();
+        ui.fonts.insert(font());
+        let id = ui.widget_id_generator().next();
+        {
+            let ui = &mut ui.set_widgets();
+            let text = widget::Text::new("HUD").font_size(FONT_SIZE).middle();
+            let text = if effects {
+                text.outline(1.0, color::BLACK)
+                    .drop_shadow([2.0, -2.0], color::DARK_GREY)
+            } else {
+                text
+            };
+            text.set(id, ui);
+        }
+
+        // The shadow is drawn first, then the outline, then the text itself.
+        let mut passes = vec![];
+        let mut primitives = ui.draw();
+        while let Some(primitive) = primitives.next() {
+            if let PrimitiveKind::Text { color, .. } = primitive.kind {
+                passes.push((color, primitive.rect.xy()));
+            }
+        }
+        let (_, xy) = *passes.last().unwrap();
+        if effects {
+            assert_eq!(passes.len(), 10);
+            assert_eq!(passes[0], (color::DARK_GREY, [xy[0] + 2.0, xy[1] - 2.0]));
+            assert!(passes[1..9].iter().all(|&(c, _)| c == color::BLACK));
+        } else {
+            assert_eq!(passes.len(), 1);
+        }
+
+        let mut mesh = Mesh::new();
+        let viewport = Rect::from_xy_dim([0.0, 0.0], [400.0, 400.0]);
+        let image_map = image::Map::<NoImage>::new();
+        mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+        mesh.vertices()
+            .iter()
+            .filter(|v| v.mode == mesh::MODE_TEXT)
+            .count()
+    };
+    assert_eq!(text_vertices(true), text_vertices(false) * 10);
+}
+
+#[test]
+fn glyph_cache_grows_to_fit_the_text_of_the_frame() {
+    use image;
+    use mesh::{self, Mesh};
+
+    struct NoImage;
+    impl mesh::ImageDimensions for NoImage {
+        fn dimensions(&self) -> [u32; 2] {
+            [0, 0]
+        }
+    }
+
+    let mut ui = UiBuilder::new([800.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let ids: Vec<_> = (0..2).map(|_| ui.widget_id_generator().next()).collect();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(TEXT)
+            .font_size(48)
+            .mid_top()
+            .set(ids[0], ui);
+        widget::Text::new(&TEXT.to_uppercase())
+            .font_size(48)
+            .mid_bottom()
+            .set(ids[1], ui);
+    }
+
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [800.0, 400.0]);
+    let image_map = image::Map::<NoImage>::new();
+    let text_vertices = |mesh: &Mesh| {
+        mesh.vertices()
+            .iter()
+            .filter(|v| v.mode == mesh::MODE_TEXT)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let mut roomy = Mesh::new();
+    let fill = roomy.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    assert!(!fill.glyph_cache_resized);
+
+    // Every glyph is still drawn when the cache starts out far too small.
+    let mut tiny = Mesh::with_glyph_cache_dimensions([64, 64]);
+    let fill = tiny.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    assert!(fill.glyph_cache_resized);
+    assert!(fill.glyph_cache_requires_upload);
+    let (w, h) = tiny.glyph_cache().dimensions();
+    assert!(w > 64 && h > 64);
+    assert_eq!(tiny.glyph_cache_pixel_buffer().len(), (w * h) as usize);
+    let (roomy, tiny) = (text_vertices(&roomy), text_vertices(&tiny));
+    assert!(!tiny.is_empty());
+    assert_eq!(roomy.len(), tiny.len());
+    for (a, b) in roomy.iter().zip(&tiny) {
+        assert_eq!(a.position, b.position);
+        assert!(b.tex_coords.iter().all(|t| (0.0..=1.0).contains(t)));
+    }
+
+    // Now that the cache is large enough it no longer grows.
+    let mut grown = Mesh::with_glyph_cache_dimensions([64, 64]);
+    grown.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    let fill = grown.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+    assert!(!fill.glyph_cache_resized);
+
+    // Growth may be disabled, in which case the error is returned as before.
+    let mut fixed = Mesh::with_glyph_cache_dimensions([64, 64]);
+    fixed.set_max_glyph_cache_dimensions([64, 64]);
+    assert!(fixed.fill(viewport, 1.0, &image_map, ui.draw()).is_err());
+    assert_eq!(fixed.glyph_cache().dimensions(), (64, 64));
+}
+
+#[test]
+fn layout_cache_evicts_least_recently_used_layouts() {
+    use text::layout::{Cache, Key};
+
+    let font = font();
+    let mut fonts = text::font::Map::new();
+    let font_id = fonts.insert(font);
+    let chain = fonts.chain(font_id).unwrap();
+    let key = |s: &str| Key::new(s, font_id, FONT_SIZE, None);
+
+    let mut cache = Cache::with_capacity(2);
+    assert_eq!(cache.line_infos(key("a"), "a", chain).len(), 1);
+    assert_eq!(cache.line_infos(key("b\nb"), "b\nb", chain).len(), 2);
+    cache.line_infos(key("a"), "a", chain);
+    cache.line_infos(key("c"), "c", chain);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.contains(&key("a")));
+    assert!(!cache.contains(&key("b\nb")));
+    assert!(cache.contains(&key("c")));
+
+    // Wrapping to a different width produces a different layout.
+    let wrapped = Key::new(
+        TEXT,
+        font_id,
+        FONT_SIZE,
+        Some((widget::text::Wrap::Whitespace, 100.0)),
+    );
+    let infos = cache.line_infos(wrapped, TEXT, chain).to_vec();
+    assert_eq!(infos, self::infos(fonts.get(font_id).unwrap(), 100.0));
+    assert!(!cache.contains(&key("a")));
+
+    cache.set_capacity(0);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn layout_cache_lays_out_text_again_when_fallbacks_change() {
+    use position::Align;
+    use text::layout::{Cache, Key, Placement};
+
+    let mut fonts = text::font::Map::new();
+    let font_id = fonts.insert(font());
+    let fallback = fonts.insert(font());
+    let key = Key::new(TEXT, font_id, FONT_SIZE, None);
+    let placement = Placement {
+        origin: [0.0, 0.0],
+        dim: [400.0, 400.0],
+        justify: text::Justify::Left,
+        y_align: Align::End,
+        line_spacing: 0.0,
+        dpi_factor: 1.0,
+    };
+    let mut cache = Cache::new();
+    // Whether or not the glyphs had to be laid out rather than reused.
+    let lay_out = |cache: &mut Cache, fonts: &text::font::Map| {
+        let infos = cache
+            .line_infos(key, TEXT, fonts.chain(font_id).unwrap())
+            .to_vec();
+        let mut laid_out = false;
+        cache.positioned_glyphs(key, TEXT, &infos, placement, || {
+            laid_out = true;
+            vec![]
+        });
+        laid_out
+    };
+
+    // The same string laid out with the same chain is reused.
+    assert!(lay_out(&mut cache, &fonts));
+    assert!(!lay_out(&mut cache, &fonts));
+
+    // Changing the fallbacks of the font invalidates the layout, as does removing them again.
+    fonts.set_fallbacks(font_id, vec![fallback]);
+    assert!(lay_out(&mut cache, &fonts));
+    assert!(!lay_out(&mut cache, &fonts));
+    fonts.set_fallbacks(font_id, None);
+    assert!(lay_out(&mut cache, &fonts));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn layout_cache_reuses_lines_and_glyphs_between_frames() {
+    use text::layout::Key;
+
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    let font_id = ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let set = |ui: &mut ::Ui, x: f64| {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(TEXT)
+            .font_size(FONT_SIZE)
+            .w(150.0)
+            .x_y(x, 0.0)
+            .set(id, ui);
+    };
+    let glyphs = |ui: &::Ui| {
+        let mut glyphs = vec![];
+        let mut primitives = ui.draw();
+        while let Some(primitive) = primitives.next() {
+            if let PrimitiveKind::Text { text, .. } = primitive.kind {
+                for (_, g) in text.positioned_glyphs_with_font_ids(1.0) {
+                    glyphs.push((g.id(), g.position()));
+                }
+            }
+        }
+        glyphs
+    };
+
+    // The line infos laid out while setting the widget are cached.
+    set(&mut ui, 0.0);
+    let wrap = Some((widget::text::Wrap::Whitespace, 150.0));
+    let key = Key::new(TEXT, font_id, FONT_SIZE, wrap);
+    assert!(ui.text_layouts().contains(&key));
+    let state = ui
+        .widget_graph()
+        .widget(id)
+        .and_then(|c| c.unique_widget_state::<widget::Text>())
+        .unwrap();
+    assert!(state.state.line_infos.len() > 2);
+
+    // Glyphs drawn from the cache match those laid out without it, including once moved.
+    let first = glyphs(&ui);
+    assert!(!first.is_empty());
+    assert_eq!(glyphs(&ui), first);
+    set(&mut ui, 37.25);
+    let moved = glyphs(&ui);
+    let uncached = {
+        let _text_layouts = ui.text_layouts();
+        glyphs(&ui)
+    };
+    assert_eq!(moved.len(), uncached.len());
+    for (a, b) in moved.iter().zip(&uncached) {
+        assert_eq!(a.0, b.0);
+        assert!((a.1.x - b.1.x).abs() < 1e-3 && (a.1.y - b.1.y).abs() < 1e-3);
+    }
+    assert_eq!(ui.text_layouts().len(), 1);
+}
+
+#[test]
+fn shadow_and_outline_passes_offset_the_cached_glyphs() {
+    use color;
+
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(TEXT)
+            .font_size(FONT_SIZE)
+            .w(150.0)
+            .x_y(0.5, 0.0)
+            .drop_shadow([2.0, -3.0], color::BLACK)
+            .outline(1.0, color::WHITE)
+            .set(id, ui);
+    }
+
+    // The rect and glyphs of each pass, from the drop shadow to the text itself.
+    let mut passes = vec![];
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { text, .. } = primitive.kind {
+            let glyphs: Vec<_> = text
+                .positioned_glyphs(1.0)
+                .map(|g| (g.id(), g.position()))
+                .collect();
+            passes.push((primitive.rect, glyphs));
+        }
+    }
+    let outline = widget::text::Outline {
+        width: 1.0,
+        color: color::WHITE,
+    };
+    let offsets: Vec<_> = Some([2.0, -3.0])
+        .into_iter()
+        .chain((0..outline.passes()).map(|pass| outline.offset(pass).unwrap()))
+        .collect();
+    assert_eq!(passes.len(), offsets.len() + 1);
+    let (rect, ref glyphs) = passes[offsets.len()];
+    assert!(!glyphs.is_empty());
+    for (&[x, y], &(pass_rect, ref pass_glyphs)) in offsets.iter().zip(&passes) {
+        assert_eq!(pass_rect, rect.shift([x, y]));
+        assert_eq!(pass_glyphs.len(), glyphs.len());
+        for (a, b) in pass_glyphs.iter().zip(glyphs) {
+            assert_eq!(a.0, b.0);
+            assert_eq!(a.1, text::rt::point(b.1.x + x as f32, b.1.y - y as f32));
+        }
+    }
+}
+
+#[test]
+fn tabs_advance_to_the_next_tab_stop() {
+    let font = font();
+    let scale = text::pt_to_scale(FONT_SIZE);
+    let space = font.glyph(' ').scaled(scale).h_metrics().advance_width as f64;
+    let spaces = text::TabStops::Spaces(4);
+    let positions = text::TabStops::Positions(vec![30.0, 100.0]);
+    let width = |text: &str, tab_stops| {
+        let chain = text::font::Chain::from(&font).with_tab_stops(tab_stops);
+        text::line::width(text, chain, FONT_SIZE)
+    };
+    let approx = |a: f64, b: f64| (a - b).abs() < 1e-3;
+
+    assert!(approx(width("\t", &spaces), space * 4.0));
+    assert!(approx(width("a\t", &spaces), space * 4.0));
+    assert!(approx(width("\t\t", &spaces), space * 8.0));
+    assert!(approx(width("\t", &positions), 30.0));
+    assert!(approx(width("a\t\t", &positions), 100.0));
+    assert!(approx(width("\t\t\t", &positions), 100.0 + space));
+
+    // Line infos measure tabs the same way.
+    let chain = text::font::Chain::from(&font).with_tab_stops(&positions);
+    let infos: Vec<_> = text::line::infos("a\t\t", chain, FONT_SIZE).collect();
+    assert!(approx(infos[0].width, 100.0));
+}
+
+#[test]
+fn cursor_hit_testing_spans_tabs() {
+    let text = "a\tb";
+    let font = font();
+    let tab_stops = text::TabStops::Positions(vec![60.0]);
+    let chain = text::font::Chain::from(&font).with_tab_stops(&tab_stops);
+    let infos: Vec<_> = text::line::infos(text, chain, FONT_SIZE).collect();
+    let rect = Rect::from_corners([0.0, -100.0], [200.0, 100.0]);
+    let xys_per_line = || {
+        text::cursor::xys_per_line_from_text(
+            text,
+            &infos,
+            chain,
+            FONT_SIZE,
+            Justify::Left,
+            Align::End,
+            0.0,
+            rect,
+        )
+    };
+
+    // The cursor following the tab sits at the tab stop.
+    let (xs, y) = xys_per_line().next().unwrap();
+    let xs: Vec<_> = xs.collect();
+    assert_eq!(xs.len(), 4);
+    assert!((xs[2] - (rect.left() + 60.0)).abs() < 1e-3);
+
+    // Positions within the tab snap to its nearest edge.
+    let hit = |x: f64| {
+        let xy = [x, y.middle()];
+        text::cursor::closest_cursor_index_and_xy(xy, xys_per_line())
+            .unwrap()
+            .0
+            .char
+    };
+    assert_eq!(hit(xs[1] + 1.0), 1);
+    assert_eq!(hit(xs[2] - 1.0), 2);
+}
+
+#[test]
+fn text_glyphs_follow_the_tab_stops_of_the_font_map() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    ui.fonts
+        .set_tab_stops(text::TabStops::Positions(vec![80.0]));
+    let id = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("a\tb")
+            .font_size(FONT_SIZE)
+            .left_justify()
+            .top_left()
+            .set(id, ui);
+    }
+
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if let PrimitiveKind::Text { text, .. } = primitive.kind {
+            // Explicit positions are scaled along with the glyphs.
+            let glyphs: Vec<_> = text.positioned_glyphs_with_font_ids(2.0).collect();
+            assert_eq!(glyphs.len(), 3);
+            let a = glyphs[0].1.position().x;
+            let b = glyphs[2].1.position().x;
+            assert!((b - a - 160.0).abs() < 1e-3);
+            // The tab itself has nothing to draw.
+            assert!(glyphs[1].1.pixel_bounding_box().is_none());
+            return;
+        }
+    }
+    panic!("no text primitive");
+}
+
+#[test]
+fn word_navigation_follows_unicode_word_boundaries() {
+    let text = "foo, bar.baz 漢字";
+    let font = font();
+    let infos: Vec<_> = text::line::infos(text, &font, FONT_SIZE).collect();
+    let index = |char| text::cursor::Index {
+        line: 0,
+        char: char,
+    };
+    let lines = || infos.iter().cloned();
+    let next = |char| index(char).next_word_end(text, lines()).unwrap().char;
+    let previous = |char| index(char).previous_word_start(text, lines()).unwrap().char;
+
+    // Punctuation ends a word, unless it joins letters on both sides.
+    assert_eq!(next(0), 3);
+    assert_eq!(next(3), 12);
+    assert_eq!(next(6), 12);
+    assert_eq!(previous(12), 5);
+    assert_eq!(previous(5), 0);
+    // Each ideograph is a word of its own.
+    assert_eq!(next(12), 14);
+    assert_eq!(next(14), 15);
+    assert_eq!(previous(15), 14);
+
+    // Double-click selection prefers the word touching the cursor.
+    let bounds = |char| {
+        let (start, end) = index(char).word_bounds(text, lines()).unwrap();
+        (start.char, end.char)
+    };
+    assert_eq!(bounds(1), (0, 3));
+    assert_eq!(bounds(3), (0, 3));
+    assert_eq!(bounds(4), (4, 5));
+    assert_eq!(bounds(14), (14, 15));
+    assert_eq!(bounds(15), (14, 15));
+}
+
+#[test]
+fn wrap_by_whitespace_follows_unicode_line_breaks() {
+    let font = font();
+    let lines = |text: &'static str, max_width: f64| -> Vec<&'static str> {
+        text::line::infos(text, &font, FONT_SIZE)
+            .wrap_by_whitespace(max_width)
+            .map(|info| &text[info.byte_range()])
+            .collect()
+    };
+    let width = |text: &str| text::line::width(text, &font, FONT_SIZE);
+
+    // Hyphens offer a break opportunity after them.
+    let max_width = width("well-kn");
+    assert_eq!(lines("well-known", max_width), vec!["well-", "known"]);
+
+    // No-break spaces keep their words together, while the following space is consumed.
+    let max_width = width("foo\u{a0}bar ba");
+    assert_eq!(
+        lines("foo\u{a0}bar baz", max_width),
+        vec!["foo\u{a0}bar", "baz"]
+    );
+}
+
+#[test]
+fn selectable_text_is_selected_by_dragging_and_keyboard() {
+    use event::Input;
+    use input::{Button, Key, Motion, MouseButton};
+
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let ids = {
+        let mut gen = ui.widget_id_generator();
+        (gen.next(), gen.next())
+    };
+    let set = |ui: &mut ::Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Hello world")
+            .font_size(FONT_SIZE)
+            .selectable(true)
+            .middle()
+            .set(ids.0, ui);
+        widget::Text::new("Hello world")
+            .font_size(FONT_SIZE)
+            .mid_bottom()
+            .set(ids.1, ui);
+    };
+    let state = |ui: &::Ui, id| {
+        ui.widget_graph()
+            .widget(id)
+            .and_then(|c| c.unique_widget_state::<widget::Text>())
+            .map(|text| text.state.clone())
+            .unwrap()
+    };
+    let drag_across = |ui: &mut ::Ui, id| {
+        let rect = ui.rect_of(id).unwrap();
+        let (x, y) = (rect.left() + 0.5, rect.y());
+        ui.handle_event(Input::Motion(Motion::MouseCursor { x: x, y: y }));
+        set(ui);
+        ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        set(ui);
+        let x = rect.right() - 0.5;
+        ui.handle_event(Input::Motion(Motion::MouseCursor { x: x, y: y }));
+        set(ui);
+        ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+        set(ui);
+    };
+
+    set(&mut ui);
+    drag_across(&mut ui, ids.0);
+    let selected = state(&ui, ids.0);
+    assert_eq!(selected.selected(), Some("Hello world"));
+    assert!(!selected.selecting);
+
+    // The selection is highlighted behind the glyphs.
+    let mut kinds = vec![];
+    let mut primitives = ui.draw();
+    while let Some(primitive) = primitives.next() {
+        if primitive.id == ids.0 {
+            kinds.push(match primitive.kind {
+                PrimitiveKind::TrianglesSingleColor { triangles, .. } => !triangles.is_empty(),
+                PrimitiveKind::Text { .. } => false,
+                _ => continue,
+            });
+        }
+    }
+    assert_eq!(kinds, vec![true, false]);
+
+    // CTRL+SHIFT+Left shrinks the selection by a word.
+    for &key in &[Key::LShift, Key::LCtrl, Key::Left] {
+        ui.handle_event(Input::Press(Button::Keyboard(key)));
+    }
+    set(&mut ui);
+    assert_eq!(state(&ui, ids.0).selected(), Some("Hello "));
+    for &key in &[Key::Left, Key::LCtrl, Key::LShift] {
+        ui.handle_event(Input::Release(Button::Keyboard(key)));
+    }
+
+    // Text that is not selectable ignores the mouse, while the selection is cleared once the
+    // other text takes the keyboard.
+    drag_across(&mut ui, ids.1);
+    assert_eq!(state(&ui, ids.1).selection, None);
+    assert_eq!(state(&ui, ids.0).selection, None);
+}
+
+#[test]
+fn selecting_truncated_text_copies_the_elided_text() {
+    use event::Input;
+    use input::{Button, Key, Motion, MouseButton};
+
+    const LONG: &str = "The quick brown fox jumps over the lazy dog";
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let set = |ui: &mut ::Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new(LONG)
+            .font_size(FONT_SIZE)
+            .w(100.0)
+            .truncate(widget::text::Truncate::Middle)
+            .selectable(true)
+            .middle()
+            .set(id, ui);
+    };
+    let state = |ui: &::Ui| {
+        ui.widget_graph()
+            .widget(id)
+            .and_then(|c| c.unique_widget_state::<widget::Text>())
+            .map(|text| text.state.clone())
+            .unwrap()
+    };
+    let press_keys = |ui: &mut ::Ui, keys: &[Key]| {
+        for &key in keys {
+            ui.handle_event(Input::Press(Button::Keyboard(key)));
+        }
+        for &key in keys.iter().rev() {
+            ui.handle_event(Input::Release(Button::Keyboard(key)));
+        }
+    };
+
+    set(&mut ui);
+    assert!(state(&ui).displayed().contains(widget::text::ELLIPSIS));
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: 0.0, y: 0.0 }));
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    set(&mut ui);
+
+    // Selecting across the ellipsis selects the text that it elides.
+    press_keys(&mut ui, &[Key::LCtrl, Key::A]);
+    set(&mut ui);
+    assert_eq!(state(&ui).selected(), Some(LONG));
+    press_keys(&mut ui, &[Key::LShift, Key::Left]);
+    set(&mut ui);
+    assert_eq!(state(&ui).selected(), Some(&LONG[..LONG.len() - 1]));
+
+    // A selection within the kept head of the line maps directly onto the string.
+    press_keys(&mut ui, &[Key::LShift, Key::Home]);
+    set(&mut ui);
+    assert_eq!(state(&ui).selected(), None);
+    press_keys(&mut ui, &[Key::LShift, Key::Right]);
+    set(&mut ui);
+    assert_eq!(state(&ui).selected(), Some("T"));
+}
+
+#[test]
+fn selectable_labels_take_their_own_input() {
+    use event::Input;
+    use input::{Button, Motion, MouseButton};
+    use Labelable;
+
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let set = |ui: &mut ::Ui, selectable: bool| {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new()
+            .label("Label")
+            .label_selectable(selectable)
+            .w_h(200.0, 50.0)
+            .middle()
+            .set(id, ui)
+            .was_clicked()
+    };
+    let label = |ui: &::Ui| {
+        ui.widget_graph()
+            .widget(id)
+            .and_then(|c| c.unique_widget_state::<widget::Button<widget::button::Flat>>())
+            .map(|button| button.state.label)
+            .unwrap()
+    };
+    let click_label = |ui: &mut ::Ui, selectable: bool| {
+        set(ui, selectable);
+        let rect = ui.rect_of(label(ui)).unwrap();
+        let (l, r, y) = (rect.left() + 0.5, rect.right() - 0.5, rect.y());
+        ui.handle_event(Input::Motion(Motion::MouseCursor { x: l, y: y }));
+        set(ui, selectable);
+        ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+        set(ui, selectable);
+        ui.handle_event(Input::Motion(Motion::MouseCursor { x: r, y: y }));
+        set(ui, selectable);
+        ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+        set(ui, selectable)
+    };
+
+    // By default the label's input is passed to the button.
+    assert!(click_label(&mut ui, false));
+
+    // A selectable label is dragged over to select it rather than clicking the button.
+    assert!(!click_label(&mut ui, true));
+    let selected = ui
+        .widget_graph()
+        .widget(label(&ui))
+        .and_then(|c| c.unique_widget_state::<widget::Text>())
+        .and_then(|text| text.state.selected().map(str::to_owned));
+    assert_eq!(selected, Some("Label".to_string()));
+}
@@ -0,0 +1,215 @@
+use event::Input;
+use input::{Button, Key, Motion, MouseButton};
+use text;
+use widget;
+use widget::text_edit::{Cursor, History};
+use {Positionable, Sizeable, Ui, UiBuilder, Widget};
+
+fn font() -> text::Font {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../assets/fonts/NotoSans/NotoSans-Regular.ttf"
+    );
+    text::font::from_file(path).unwrap()
+}
+
+// Set a `TextEdit` over the given `text`, applying any edit that it produces.
+fn set_text_edit(ui: &mut Ui, id: widget::Id, text: &mut String, history: Option<History>) {
+    let ui = &mut ui.set_widgets();
+    let mut text_edit = widget::TextEdit::new(text)
+        .undo_history(true)
+        .w_h(200.0, 100.0)
+        .middle();
+    if let Some(history) = history {
+        text_edit = text_edit.history(history);
+    }
+    if let Some(new_text) = text_edit.set(id, ui) {
+        *text = new_text;
+    }
+}
+
+fn state(ui: &Ui, id: widget::Id) -> &widget::text_edit::State {
+    &ui.widget_graph()
+        .widget(id)
+        .and_then(|c| c.unique_widget_state::<widget::TextEdit>())
+        .unwrap()
+        .state
+}
+
+fn press_keys(ui: &mut Ui, keys: &[Key]) {
+    for &key in keys {
+        ui.handle_event(Input::Press(Button::Keyboard(key)));
+    }
+    for &key in keys.iter().rev() {
+        ui.handle_event(Input::Release(Button::Keyboard(key)));
+    }
+}
+
+#[test]
+fn text_edit_undoes_and_redoes_edits() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let mut text = String::new();
+    set_text_edit(&mut ui, id, &mut text, None);
+
+    // Click the `TextEdit` so that it captures the keyboard.
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: 0.0, y: 0.0 }));
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    set_text_edit(&mut ui, id, &mut text, None);
+
+    // Typed characters are coalesced into a single edit.
+    for ch in &["h", "i", "!"] {
+        ui.handle_event(Input::Text(ch.to_string()));
+        set_text_edit(&mut ui, id, &mut text, None);
+    }
+    assert_eq!(text, "hi!");
+    press_keys(&mut ui, &[Key::Backspace]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "hi");
+    assert_eq!(state(&ui, id).history().undo_snapshots().len(), 2);
+
+    // Undoing restores both the text and the cursor.
+    press_keys(&mut ui, &[Key::LCtrl, Key::Z]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "hi!");
+    let snapshot = &state(&ui, id).history().redo_snapshots()[0];
+    assert_eq!(snapshot.text, "hi");
+    let index = |char| {
+        Cursor::Idx(text::cursor::Index {
+            line: 0,
+            char: char,
+        })
+    };
+    assert_eq!(snapshot.cursor, index(2));
+    press_keys(&mut ui, &[Key::LCtrl, Key::Z]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "");
+    assert!(!state(&ui, id).history().can_undo());
+
+    // Redo via both CTRL+Y and CTRL+SHIFT+Z.
+    press_keys(&mut ui, &[Key::LCtrl, Key::Y]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "hi!");
+    press_keys(&mut ui, &[Key::LCtrl, Key::LShift, Key::Z]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "hi");
+    assert!(!state(&ui, id).history().can_redo());
+
+    // A new edit forgets the undone edits.
+    press_keys(&mut ui, &[Key::LCtrl, Key::Z]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    ui.handle_event(Input::Text("?".to_string()));
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "hi!?");
+    assert!(!state(&ui, id).history().can_redo());
+
+    // The history may be snapshot, cleared and restored.
+    let snapshot = state(&ui, id).history().clone();
+    set_text_edit(&mut ui, id, &mut text, Some(History::new()));
+    assert!(!state(&ui, id).history().can_undo());
+    set_text_edit(&mut ui, id, &mut text, Some(snapshot.clone()));
+    assert_eq!(state(&ui, id).history(), &snapshot);
+}
+
+#[test]
+fn history_given_on_every_update_is_only_applied_once() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let mut text = String::new();
+    let given = History::with_capacity(10);
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: 0.0, y: 0.0 }));
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+
+    // Edits made while the same history is given on each update are retained.
+    ui.handle_event(Input::Text("ab".to_string()));
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    press_keys(&mut ui, &[Key::Backspace]);
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    assert_eq!(text, "a");
+    assert_eq!(state(&ui, id).history().undo_snapshots().len(), 2);
+    press_keys(&mut ui, &[Key::LCtrl, Key::Z]);
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    assert_eq!(text, "ab");
+    assert!(state(&ui, id).history().can_undo());
+
+    // Clearing on consecutive updates only clears once, while a new history is applied.
+    set_text_edit(&mut ui, id, &mut text, None);
+    set_text_edit(&mut ui, id, &mut text, Some(History::new()));
+    assert!(!state(&ui, id).history().can_undo());
+    press_keys(&mut ui, &[Key::Backspace]);
+    set_text_edit(&mut ui, id, &mut text, Some(History::new()));
+    assert_eq!(text, "a");
+    assert!(state(&ui, id).history().can_undo());
+    set_text_edit(&mut ui, id, &mut text, Some(given.clone()));
+    assert_eq!(state(&ui, id).history(), &given);
+}
+
+#[test]
+fn history_is_bounded_by_its_capacity() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let mut text = String::new();
+    set_text_edit(&mut ui, id, &mut text, Some(History::with_capacity(2)));
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: 0.0, y: 0.0 }));
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    set_text_edit(&mut ui, id, &mut text, None);
+
+    // Each backspace is a separate edit.
+    ui.handle_event(Input::Text("abcd".to_string()));
+    set_text_edit(&mut ui, id, &mut text, None);
+    for _ in 0..3 {
+        press_keys(&mut ui, &[Key::Backspace]);
+        set_text_edit(&mut ui, id, &mut text, None);
+    }
+    assert_eq!(text, "a");
+    let history = state(&ui, id).history();
+    let texts: Vec<_> = history
+        .undo_snapshots()
+        .iter()
+        .map(|s| &s.text[..])
+        .collect();
+    assert_eq!(texts, vec!["abc", "ab"]);
+}
+
+#[test]
+fn history_is_cleared_when_the_text_is_replaced() {
+    let mut ui = UiBuilder::new([400.0, 400.0]).build();
+    ui.fonts.insert(font());
+    let id = ui.widget_id_generator().next();
+    let mut text = String::new();
+    set_text_edit(&mut ui, id, &mut text, None);
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: 0.0, y: 0.0 }));
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    set_text_edit(&mut ui, id, &mut text, None);
+    ui.handle_event(Input::Text("hi".to_string()));
+    set_text_edit(&mut ui, id, &mut text, None);
+    press_keys(&mut ui, &[Key::Backspace]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "h");
+    assert!(state(&ui, id).history().can_undo());
+
+    // The user clears the text, e.g. after submitting it.
+    text.clear();
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert!(!state(&ui, id).history().can_undo());
+
+    // Undoing the next edit does not bring back the replaced text.
+    ui.handle_event(Input::Text("x".to_string()));
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "x");
+    press_keys(&mut ui, &[Key::LCtrl, Key::Z]);
+    set_text_edit(&mut ui, id, &mut text, None);
+    assert_eq!(text, "");
+    assert!(!state(&ui, id).history().can_undo());
+}
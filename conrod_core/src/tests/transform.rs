@@ -0,0 +1,150 @@
+use event::Input;
+use image;
+use input::{Button, Motion, MouseButton};
+use mesh::{self, Mesh};
+use position::Transform;
+use std::f64::consts::PI;
+use widget;
+use {Point, Positionable, Rect, Scalar, Ui, UiBuilder, Widget};
+
+struct Image;
+
+impl mesh::ImageDimensions for Image {
+    fn dimensions(&self) -> [u32; 2] {
+        [1, 1]
+    }
+}
+
+fn windowless_ui() -> Ui {
+    UiBuilder::new([800.0, 600.0]).build()
+}
+
+fn assert_near(a: Point, b: Point) {
+    assert!(
+        (a[0] - b[0]).abs() < 1e-9 && (a[1] - b[1]).abs() < 1e-9,
+        "{:?} != {:?}",
+        a,
+        b
+    );
+}
+
+fn move_mouse_to(ui: &mut Ui, xy: Point) {
+    ui.handle_event(Input::Motion(Motion::MouseCursor { x: xy[0], y: xy[1] }));
+}
+
+#[test]
+fn transform_about_pivot() {
+    let transform = Transform::about([10.0, 0.0], PI / 2.0, [2.0, 2.0]);
+    assert_near(transform.apply([10.0, 0.0]), [10.0, 0.0]);
+    assert_near(transform.apply([11.0, 0.0]), [10.0, 2.0]);
+    let inverse = transform.inverse().unwrap();
+    assert_near(inverse.apply(transform.apply([3.0, -7.0])), [3.0, -7.0]);
+    assert!(Transform::scale([0.0, 1.0]).inverse().is_none());
+}
+
+#[test]
+fn pick_rotated_widget() {
+    let mut ui = windowless_ui();
+    let rect = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 20.0])
+            .middle()
+            .rotate(PI / 2.0)
+            .set(rect, ui);
+    }
+
+    // The rotated rectangle is now tall and thin.
+    move_mouse_to(&mut ui, [0.0, 80.0]);
+    assert_eq!(ui.global_input().current.widget_under_mouse, Some(rect));
+    move_mouse_to(&mut ui, [80.0, 0.0]);
+    assert!(ui.global_input().current.widget_under_mouse != Some(rect));
+}
+
+#[test]
+fn pick_scaled_child() {
+    let mut ui = windowless_ui();
+    let parent = ui.widget_id_generator().next();
+    let child = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0])
+            .middle()
+            .scale(2.0)
+            .set(parent, ui);
+        widget::Rectangle::fill([20.0, 20.0])
+            .x_y_relative_to(parent, 40.0, 0.0)
+            .parent(parent)
+            .set(child, ui);
+    }
+
+    // The child is scaled about the centre of the parent, doubling its offset.
+    move_mouse_to(&mut ui, [80.0, 15.0]);
+    assert_eq!(ui.global_input().current.widget_under_mouse, Some(child));
+    move_mouse_to(&mut ui, [40.0, 0.0]);
+    assert_eq!(ui.global_input().current.widget_under_mouse, Some(parent));
+
+    // Widget input is relative to the child's untransformed `Rect`.
+    move_mouse_to(&mut ui, [80.0, 15.0]);
+    ui.handle_event(Input::Press(Button::Mouse(MouseButton::Left)));
+    ui.handle_event(Input::Release(Button::Mouse(MouseButton::Left)));
+    let click = ui
+        .widget_input(child)
+        .clicks()
+        .left()
+        .next()
+        .expect("expected a click on the child");
+    assert_near(click.xy, [0.0, 7.5]);
+}
+
+#[test]
+fn mesh_rotates_rectangle() {
+    let mut ui = windowless_ui();
+    let rect = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([200.0, 20.0])
+            .middle()
+            .rotate(PI / 2.0)
+            .set(rect, ui);
+    }
+    let mut mesh = Mesh::new();
+    let viewport = Rect::from_xy_dim([0.0, 0.0], [800.0, 600.0]);
+    let image_map = image::Map::<Image>::new();
+    mesh.fill(viewport, 1.0, &image_map, ui.draw()).unwrap();
+
+    // The first six vertices describe the window.
+    let vertices = &mesh.vertices()[6..];
+    assert_eq!(vertices.len(), 6);
+    for v in vertices {
+        let [x, y] = v.position;
+        let (x, y) = (x as Scalar * 400.0, -y as Scalar * 300.0);
+        assert!((x.abs() - 10.0).abs() < 1e-3);
+        assert!((y.abs() - 100.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn transform_vertex_positions() {
+    let transform = Transform::about([100.0, 0.0], PI / 2.0, [2.0, 2.0]);
+    // Positions are given in single precision.
+    let to_point = |[x, y]: [f32; 2]| {
+        [
+            (x as Scalar * 1e6).round() / 1e6,
+            (y as Scalar * 1e6).round() / 1e6,
+        ]
+    };
+
+    // With *y* pointing upwards, `[200.0, 0.0]` within an 800x600 window maps to `[100.0, 200.0]`.
+    let half_window = [400.0, 300.0];
+    let position = transform.apply_ndc([0.5, 0.0], half_window);
+    assert_near(to_point(position), [0.25, 0.666667]);
+
+    // With *y* pointing downwards, the same point is found on the opposite side.
+    let position = transform.apply_ndc([0.5, 0.0], [400.0, -300.0]);
+    assert_near(to_point(position), [0.25, -0.666667]);
+
+    // Positions are unchanged by the identity transform.
+    let position = Transform::IDENTITY.apply_ndc([-0.75, 0.5], half_window);
+    assert_near(to_point(position), [-0.75, 0.5]);
+}
@@ -34,10 +34,50 @@ pub enum Justify {
     Center,
     /// Align text to the end of the bounding `Rect`'s *x* axis.
     Right,
-    // /// Align wrapped text to both the start and end of the bounding `Rect`s *x* axis.
-    // ///
-    // /// Extra space is added between words in order to achieve this alignment.
-    // TODO: Full,
+    /// Align wrapped text to both the start and end of the bounding `Rect`s *x* axis.
+    ///
+    /// Extra space is added between words in order to achieve this alignment. The last line of
+    /// each paragraph, i.e. any line that is not wrapped, is aligned to the start.
+    Full,
+}
+
+/// The direction in which text is laid out along the *x* axis.
+///
+/// Each paragraph takes the direction of its first strongly directional character, as described by
+/// the Unicode Bidirectional Algorithm. Runs of text with the opposite direction are reordered
+/// within each line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Left-to-right text, e.g. Latin or Cyrillic.
+    LeftToRight,
+    /// Right-to-left text, e.g. Hebrew or Arabic.
+    RightToLeft,
+}
+
+/// The positions to which each tab character advances the text that follows it.
+///
+/// Stops are measured from the start of the line, or from the start of the run for lines mixing
+/// left-to-right and right-to-left text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TabStops {
+    /// A stop after every given number of space widths.
+    Spaces(u32),
+    /// A stop at each of the given distances from the start of the line, in ascending order.
+    ///
+    /// Tabs following the last stop advance by the width of a single space.
+    Positions(Vec<Scalar>),
+}
+
+/// The number of space widths between each of the default `TabStops`.
+pub const DEFAULT_TAB_SPACES: u32 = 4;
+
+/// The `TabStops` used unless others are given.
+pub static DEFAULT_TAB_STOPS: TabStops = TabStops::Spaces(DEFAULT_TAB_SPACES);
+
+impl Default for TabStops {
+    fn default() -> Self {
+        DEFAULT_TAB_STOPS.clone()
+    }
 }
 
 /// Determine the total height of a block of text with the given number of lines, font size and
@@ -102,6 +142,7 @@ where
 pub mod font {
     use fnv;
     use std;
+    use Scalar;
 
     /// A type-safe wrapper around the `FontId`.
     ///
@@ -112,11 +153,150 @@ pub mod font {
     #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
     pub struct Id(usize);
 
+    /// A unique identifier for a font family registered within a `font::Map`.
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct FamilyId(usize);
+
+    /// The weight of a font within a family, following the CSS numeric scale of 1 to 1000.
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Weight(pub u16);
+
+    /// The slant of a font within a family.
+    #[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
+    pub enum Style {
+        /// Upright glyphs.
+        #[default]
+        Normal,
+        /// Italic or oblique glyphs.
+        Italic,
+    }
+
+    /// The underline and strikeout metrics of a font in font units, as read from its `post` and
+    /// `OS/2` tables.
+    ///
+    /// Each metric is a `(position, thickness)` pair where the position is the offset of the top
+    /// of the stroke above the baseline.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Decorations {
+        /// The underline from the `post` table, if the font has one.
+        pub underline: Option<(i16, i16)>,
+        /// The strikeout from the `OS/2` table, if the font has one.
+        pub strikeout: Option<(i16, i16)>,
+    }
+
+    /// A named collection of fonts that differ only by weight and style.
+    #[derive(Clone, Debug)]
+    struct Family {
+        name: String,
+        members: Vec<(Weight, Style, Id)>,
+    }
+
     /// A collection of mappings from `font::Id`s to `rusttype::Font`s.
     #[derive(Debug)]
     pub struct Map {
         next_index: usize,
         map: fnv::FnvHashMap<Id, super::Font>,
+        fallbacks: fnv::FnvHashMap<Id, Vec<(Id, super::Font)>>,
+        families: Vec<Family>,
+        tab_stops: super::TabStops,
+        decorations: fnv::FnvHashMap<Id, Decorations>,
+        #[cfg(feature = "shaping")]
+        faces: fnv::FnvHashMap<Id, super::shape::Face>,
+    }
+
+    /// A font along with the fonts that are used, in order, for any characters that it does not
+    /// cover.
+    ///
+    /// Text layout selects a font glyph by glyph, using the first font in the chain that covers the
+    /// character. Characters that no font covers are displayed using the first font's notdef glyph.
+    ///
+    /// A `Chain` may be produced from a single `Font`, in which case it has no fallbacks.
+    ///
+    /// With the `shaping` feature enabled, text is shaped using the first font of the chain if it
+    /// has a shaping `Face`.
+    #[derive(Copy, Clone, Debug)]
+    pub struct Chain<'a> {
+        font: &'a super::Font,
+        fallbacks: &'a [(Id, super::Font)],
+        tab_stops: &'a super::TabStops,
+        dpi_factor: f32,
+        #[cfg(feature = "shaping")]
+        face: Option<&'a super::shape::Face>,
+        #[cfg(feature = "shaping")]
+        shaped: Option<(&'a str, super::Scale, &'a [Scalar])>,
+    }
+
+    /// Yields each glyph of a line laid out using a font `Chain`.
+    ///
+    /// Each glyph is yielded along with the index of the character from which it was produced and
+    /// the `Id` of the fallback font that produced it, or `None` if it was produced by the chain's
+    /// first font.
+    ///
+    /// Unless the line is shaped, a glyph is produced for each character in order and kerning is
+    /// only applied between glyphs of the same font. Shaped glyphs are yielded in visual order.
+    #[derive(Clone)]
+    pub struct Layout<'a, 'b> {
+        kind: LayoutKind<'a, 'b>,
+    }
+
+    #[derive(Clone)]
+    enum LayoutKind<'a, 'b> {
+        Chars {
+            chain: Chain<'a>,
+            chars: std::str::Chars<'b>,
+            // The number of chars yielded so far and the total, for indexing reversed chars.
+            yielded: usize,
+            len: usize,
+            rtl: bool,
+            scale: super::Scale,
+            start: super::rt::Point<f32>,
+            caret: f32,
+            last_glyph: Option<(Option<Id>, super::GlyphId)>,
+        },
+        #[cfg(feature = "shaping")]
+        Shaped {
+            chain: Chain<'a>,
+            glyphs: std::vec::IntoIter<super::shape::Glyph>,
+            scale: super::Scale,
+            start: super::rt::Point<f32>,
+        },
+    }
+
+    /// Yields the advance width of each character of a line laid out using a font `Chain`,
+    /// including any kerning with the preceding character.
+    ///
+    /// When the line is shaped, the advance of each cluster is shared between the grapheme clusters
+    /// that it contains.
+    #[derive(Clone)]
+    pub struct Advances<'a, 'b> {
+        chain: Chain<'a>,
+        chars: std::str::Chars<'b>,
+        scale: super::Scale,
+        last_glyph: Option<(Option<Id>, super::GlyphId)>,
+        // The sum of the advances yielded so far, locating any tab stops.
+        x: f32,
+        #[cfg(feature = "shaping")]
+        shaped: std::borrow::Cow<'a, [Scalar]>,
+        #[cfg(feature = "shaping")]
+        shaped_index: usize,
+    }
+
+    /// Yields the right edge of each character of a line laid out using a font `Chain`, i.e. the
+    /// right of its glyph's pixel bounding box if it has one or otherwise the caret following it,
+    /// along with its advance width.
+    #[derive(Clone)]
+    pub struct CharEdges<'a, 'b> {
+        kind: CharEdgesKind<'a, 'b>,
+    }
+
+    #[derive(Clone)]
+    enum CharEdgesKind<'a, 'b> {
+        Glyphs(Layout<'a, 'b>),
+        #[cfg(feature = "shaping")]
+        Shaped {
+            advances: std::vec::IntoIter<Scalar>,
+            x: Scalar,
+        },
     }
 
     /// An iterator yielding an `Id` for each new `rusttype::Font` inserted into the `Map` via the
@@ -149,15 +329,143 @@ pub mod font {
         }
     }
 
+    impl FamilyId {
+        /// Returns the inner `usize` from the `FamilyId`.
+        pub fn index(self) -> usize {
+            self.0
+        }
+    }
+
+    impl Weight {
+        /// Thin (Hairline).
+        pub const THIN: Weight = Weight(100);
+        /// Extra Light (Ultra Light).
+        pub const EXTRA_LIGHT: Weight = Weight(200);
+        /// Light.
+        pub const LIGHT: Weight = Weight(300);
+        /// Normal (Regular).
+        pub const NORMAL: Weight = Weight(400);
+        /// Medium.
+        pub const MEDIUM: Weight = Weight(500);
+        /// Semi Bold (Demi Bold).
+        pub const SEMI_BOLD: Weight = Weight(600);
+        /// Bold.
+        pub const BOLD: Weight = Weight(700);
+        /// Extra Bold (Ultra Bold).
+        pub const EXTRA_BOLD: Weight = Weight(800);
+        /// Black (Heavy).
+        pub const BLACK: Weight = Weight(900);
+
+        /// Guess the weight and style of a font from its name, e.g. `"NotoSans-SemiBoldItalic"`.
+        ///
+        /// Names without any recognised weight are assumed to be `Weight::NORMAL`.
+        pub fn and_style_from_name(name: &str) -> (Weight, Style) {
+            let name: String = name
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(|c| c.to_lowercase())
+                .collect();
+            let style = if name.contains("italic") || name.contains("oblique") {
+                Style::Italic
+            } else {
+                Style::Normal
+            };
+            // Compound names must be checked before the names that they contain.
+            let weights = [
+                (&["extrabold", "ultrabold"][..], Weight::EXTRA_BOLD),
+                (&["semibold", "demibold"][..], Weight::SEMI_BOLD),
+                (&["extralight", "ultralight"][..], Weight::EXTRA_LIGHT),
+                (&["thin", "hairline"][..], Weight::THIN),
+                (&["light"][..], Weight::LIGHT),
+                (&["medium"][..], Weight::MEDIUM),
+                (&["bold"][..], Weight::BOLD),
+                (&["black", "heavy"][..], Weight::BLACK),
+            ];
+            let weight = weights
+                .iter()
+                .find(|&&(names, _)| names.iter().any(|n| name.contains(n)))
+                .map_or(Weight::NORMAL, |&(_, weight)| weight);
+            (weight, style)
+        }
+    }
+
+    impl Default for Weight {
+        fn default() -> Self {
+            Weight::NORMAL
+        }
+    }
+
+    impl Decorations {
+        /// Read the decorations of the first font within the given font data.
+        ///
+        /// Any metric whose table is missing or malformed is `None`.
+        pub fn from_bytes(data: &[u8]) -> Self {
+            fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+                let b = data.get(at..at + 4)?;
+                Some(
+                    u32::from(b[0]) << 24
+                        | u32::from(b[1]) << 16
+                        | u32::from(b[2]) << 8
+                        | u32::from(b[3]),
+                )
+            }
+            fn read_i16(data: &[u8], at: usize) -> Option<i16> {
+                let b = data.get(at..at + 2)?;
+                Some((u16::from(b[0]) << 8 | u16::from(b[1])) as i16)
+            }
+            // Find the offset of the first font of a collection.
+            let font = match data.get(0..4) {
+                Some(b"ttcf") => read_u32(data, 12).map_or(0, |offset| offset as usize),
+                _ => 0,
+            };
+            // Find the offset of the table with the given tag, within the table directory.
+            let table = |tag: &[u8]| -> Option<usize> {
+                let num_tables = read_i16(data, font + 4)? as u16 as usize;
+                (0..num_tables)
+                    .map(|i| font + 12 + i * 16)
+                    .find(|&record| data.get(record..record + 4) == Some(tag))
+                    .and_then(|record| read_u32(data, record + 8))
+                    .map(|offset| offset as usize)
+            };
+            let pair = |tag: &[u8], at: usize| -> Option<(i16, i16)> {
+                let table = table(tag)?;
+                Some((read_i16(data, table + at)?, read_i16(data, table + at + 2)?))
+            };
+            // `post` stores the position then the thickness, `OS/2` the size then the position.
+            let underline = pair(b"post", 8);
+            let strikeout = pair(b"OS/2", 26).map(|(size, position)| (position, size));
+            Decorations {
+                underline: underline,
+                strikeout: strikeout,
+            }
+        }
+    }
+
     impl Map {
         /// Construct the new, empty `Map`.
         pub fn new() -> Self {
             Map {
                 next_index: 0,
                 map: fnv::FnvHashMap::default(),
+                fallbacks: fnv::FnvHashMap::default(),
+                families: Vec::new(),
+                tab_stops: super::TabStops::default(),
+                decorations: fnv::FnvHashMap::default(),
+                #[cfg(feature = "shaping")]
+                faces: fnv::FnvHashMap::default(),
             }
         }
 
+        /// The `TabStops` of the text laid out using the `Chain`s produced by the map.
+        pub fn tab_stops(&self) -> &super::TabStops {
+            &self.tab_stops
+        }
+
+        /// Set the `TabStops` of the text laid out using the `Chain`s produced by the map.
+        pub fn set_tab_stops(&mut self, tab_stops: super::TabStops) {
+            self.tab_stops = tab_stops;
+        }
+
         /// Borrow the `rusttype::Font` associated with the given `font::Id`.
         pub fn get(&self, id: Id) -> Option<&super::Font> {
             self.map.get(&id)
@@ -173,12 +481,143 @@ pub mod font {
         }
 
         /// Insert a single `Font` into the map by loading it from the given file path.
+        ///
+        /// The font's `Decorations` are read from its data. With the `shaping` feature enabled,
+        /// the font's data is also retained for shaping.
         pub fn insert_from_file<P>(&mut self, path: P) -> Result<Id, Error>
         where
             P: AsRef<std::path::Path>,
         {
-            let font = from_file(path)?;
-            Ok(self.insert(font))
+            let bytes = std::fs::read(path)?;
+            self.insert_from_bytes(bytes)
+        }
+
+        /// Insert a single `Font` into the map by loading it from the given bytes.
+        ///
+        /// The font's `Decorations` are read from its data. With the `shaping` feature enabled,
+        /// the font's data is also retained for shaping. The data is shared with the loaded `Font`
+        /// rather than copied.
+        pub fn insert_from_bytes(&mut self, bytes: Vec<u8>) -> Result<Id, Error> {
+            let data: std::sync::Arc<[u8]> = bytes.into();
+            let font = super::Font::from_bytes(data.clone())?;
+            let id = self.insert(font);
+            let decorations = Decorations::from_bytes(&data);
+            if decorations != Decorations::default() {
+                self.decorations.insert(id, decorations);
+            }
+            #[cfg(feature = "shaping")]
+            {
+                if let Some(face) = super::shape::Face::from_bytes(data, 0) {
+                    self.faces.insert(id, face);
+                }
+            }
+            Ok(id)
+        }
+
+        /// The underline and strikeout metrics of the font with the given `id`.
+        ///
+        /// These are only known for fonts loaded from bytes or files, as a `rusttype::Font` does
+        /// not expose its tables. The default, empty `Decorations` is returned otherwise.
+        pub fn decorations(&self, id: Id) -> Decorations {
+            self.decorations.get(&id).cloned().unwrap_or_default()
+        }
+
+        /// Produce the `FamilyId` for the family with the given name, registering a new, empty
+        /// family if there is no family by that name yet.
+        pub fn insert_family(&mut self, name: &str) -> FamilyId {
+            if let Some(family) = self.family_id(name) {
+                return family;
+            }
+            self.families.push(Family {
+                name: name.to_string(),
+                members: Vec::new(),
+            });
+            FamilyId(self.families.len() - 1)
+        }
+
+        /// The `FamilyId` of the family with the given name, if one has been registered.
+        pub fn family_id(&self, name: &str) -> Option<FamilyId> {
+            self.families
+                .iter()
+                .position(|family| family.name == name)
+                .map(FamilyId)
+        }
+
+        /// The name of the family with the given `FamilyId`.
+        pub fn family_name(&self, family: FamilyId) -> Option<&str> {
+            self.families.get(family.0).map(|family| &family.name[..])
+        }
+
+        /// Register the font with the given `id` as the given `weight` and `style` of a family.
+        ///
+        /// Any font previously registered for the same weight and style is replaced.
+        pub fn add_to_family(&mut self, family: FamilyId, weight: Weight, style: Style, id: Id) {
+            if let Some(family) = self.families.get_mut(family.0) {
+                family
+                    .members
+                    .retain(|&(w, s, _)| w != weight || s != style);
+                family.members.push((weight, style, id));
+            }
+        }
+
+        /// Load every `.ttf` and `.otf` file within the given directory as a member of the family
+        /// with the given name.
+        ///
+        /// The weight and style of each font is determined from its file name (see
+        /// `Weight::and_style_from_name`). Returns `Error::NoFont` if the directory contains no
+        /// font files.
+        pub fn insert_family_from_dir<P>(&mut self, name: &str, dir: P) -> Result<FamilyId, Error>
+        where
+            P: AsRef<std::path::Path>,
+        {
+            let mut paths = vec![];
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                let is_font = match path.extension().and_then(|ext| ext.to_str()) {
+                    Some(ext) => ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf"),
+                    None => false,
+                };
+                if is_font {
+                    paths.push(path);
+                }
+            }
+            if paths.is_empty() {
+                return Err(Error::NoFont);
+            }
+            paths.sort();
+
+            let family = self.insert_family(name);
+            for path in paths {
+                let (weight, style) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map_or_else(Default::default, Weight::and_style_from_name);
+                let id = self.insert_from_file(&path)?;
+                self.add_to_family(family, weight, style, id);
+            }
+            Ok(family)
+        }
+
+        /// Select the font within the given family that best matches the given weight and style.
+        ///
+        /// A font of the requested style is always preferred. Among those, the closest weight is
+        /// chosen, preferring heavier fonts for weights above `Weight::MEDIUM` and lighter fonts
+        /// otherwise. Returns `None` if the family is unknown or has no members.
+        pub fn select(&self, family: FamilyId, weight: Weight, style: Style) -> Option<Id> {
+            let family = self.families.get(family.0)?;
+            family
+                .members
+                .iter()
+                .min_by_key(|&&(w, s, _)| {
+                    let distance = (i32::from(w.0) - i32::from(weight.0)).abs();
+                    let wrong_direction = if weight > Weight::MEDIUM {
+                        w < weight
+                    } else {
+                        w > weight
+                    };
+                    (s != style, distance, wrong_direction)
+                })
+                .map(|&(_, _, id)| id)
         }
 
         /// Produces an iterator yielding the `Id` for each `Font` within the `Map`.
@@ -187,6 +626,436 @@ pub mod font {
                 keys: self.map.keys(),
             }
         }
+
+        /// Specify the fonts used, in order, for characters that the font with the given `id` does
+        /// not cover. Any previously specified fallbacks are replaced.
+        ///
+        /// Fallbacks are not followed transitively and any `Id`s not within the `Map` are ignored.
+        pub fn set_fallbacks<I>(&mut self, id: Id, fallbacks: I)
+        where
+            I: IntoIterator<Item = Id>,
+        {
+            let fallbacks: Vec<_> = {
+                let map = &self.map;
+                fallbacks
+                    .into_iter()
+                    .filter(|&fallback| fallback != id)
+                    .filter_map(|fallback| map.get(&fallback).map(|font| (fallback, font.clone())))
+                    .collect()
+            };
+            if fallbacks.is_empty() {
+                self.fallbacks.remove(&id);
+            } else {
+                self.fallbacks.insert(id, fallbacks);
+            }
+        }
+
+        /// The `Id`s of the fallback fonts for the font with the given `id`, in order.
+        pub fn fallbacks(&self, id: Id) -> Vec<Id> {
+            self.fallbacks
+                .get(&id)
+                .map(|fallbacks| fallbacks.iter().map(|&(id, _)| id).collect())
+                .unwrap_or_default()
+        }
+
+        /// The font with the given `id` along with its fallbacks.
+        pub fn chain(&self, id: Id) -> Option<Chain<'_>> {
+            self.get(id).map(|font| Chain {
+                font: font,
+                fallbacks: self.fallbacks.get(&id).map_or(&[][..], |f| &f[..]),
+                tab_stops: &self.tab_stops,
+                dpi_factor: 1.0,
+                #[cfg(feature = "shaping")]
+                face: self.faces.get(&id),
+                #[cfg(feature = "shaping")]
+                shaped: None,
+            })
+        }
+    }
+
+    impl<'a> Chain<'a> {
+        /// A chain of the given `font` followed by the given `fallbacks`.
+        pub fn new(font: &'a super::Font, fallbacks: &'a [(Id, super::Font)]) -> Self {
+            Chain {
+                font: font,
+                fallbacks: fallbacks,
+                tab_stops: &super::DEFAULT_TAB_STOPS,
+                dpi_factor: 1.0,
+                #[cfg(feature = "shaping")]
+                face: None,
+                #[cfg(feature = "shaping")]
+                shaped: None,
+            }
+        }
+
+        /// Lay out tab characters using the given `tab_stops`.
+        pub fn with_tab_stops(mut self, tab_stops: &'a super::TabStops) -> Self {
+            self.tab_stops = tab_stops;
+            self
+        }
+
+        /// The `TabStops` with which tab characters are laid out.
+        pub fn tab_stops(&self) -> &'a super::TabStops {
+            self.tab_stops
+        }
+
+        /// Lay out text that is scaled by the given "dots per inch" factor, as is the case when
+        /// positioning glyphs for rendering.
+        ///
+        /// This is used to scale the distances given by `TabStops::Positions` to pixels.
+        pub fn with_dpi_factor(mut self, dpi_factor: f32) -> Self {
+            self.dpi_factor = dpi_factor;
+            self
+        }
+
+        /// The advance of a tab character at the given distance `x` from the start of the line,
+        /// i.e. the distance to the next tab stop.
+        ///
+        /// A caret within a fraction of a pixel of a stop is considered to be at it, so that the
+        /// rounding error accumulated by preceding advances does not produce a sliver of a tab.
+        pub fn tab_advance(&self, x: f32, scale: super::Scale) -> f32 {
+            const TOLERANCE: f32 = 1e-3;
+            let space = self.font.glyph(' ').scaled(scale).h_metrics().advance_width;
+            match *self.tab_stops {
+                super::TabStops::Spaces(n) => {
+                    let interval = space * n as f32;
+                    if interval <= 0.0 {
+                        return 0.0;
+                    }
+                    ((x + TOLERANCE) / interval).floor() * interval + interval - x
+                }
+                super::TabStops::Positions(ref positions) => positions
+                    .iter()
+                    .map(|&stop| stop as f32 * self.dpi_factor)
+                    .find(|&stop| stop > x + TOLERANCE)
+                    .map_or(space, |stop| stop - x),
+            }
+        }
+
+        // A glyph for a tab character at the given distance `x` from the start of the line.
+        //
+        // This is a space, stretched so that its advance reaches the next tab stop.
+        fn tab_glyph(&self, x: f32, scale: super::Scale) -> ::rusttype::ScaledGlyph<'static> {
+            let space = self.font.glyph(' ');
+            let space_advance = space.clone().scaled(scale).h_metrics().advance_width;
+            if space_advance <= 0.0 {
+                return space.scaled(scale);
+            }
+            let stretch = self.tab_advance(x, scale) / space_advance;
+            let scale = super::Scale {
+                x: scale.x * stretch,
+                y: scale.y,
+            };
+            space.scaled(scale)
+        }
+
+        /// Shape text laid out with the chain using the given `face`, which should contain the data
+        /// of the chain's first font.
+        #[cfg(feature = "shaping")]
+        pub fn with_face(mut self, face: Option<&'a super::shape::Face>) -> Self {
+            self.face = face;
+            self
+        }
+
+        /// Yield the given `advances`, already shaped from the given `line` at the given `scale`,
+        /// rather than shaping the line again when the advances of text beginning with it are
+        /// requested.
+        ///
+        /// This allows the lines wrapped from a paragraph to share the advances of the whole
+        /// paragraph, which is shaped only once.
+        #[cfg(feature = "shaping")]
+        pub fn with_shaped_advances(
+            mut self,
+            line: &'a str,
+            scale: super::Scale,
+            advances: &'a [Scalar],
+        ) -> Self {
+            self.shaped = Some((line, scale, advances));
+            self
+        }
+
+        /// The face with which text laid out using the chain is shaped, if any.
+        #[cfg(feature = "shaping")]
+        pub fn face(&self) -> Option<&'a super::shape::Face> {
+            self.face
+        }
+
+        /// The first font of the chain.
+        pub fn font(&self) -> &'a super::Font {
+            self.font
+        }
+
+        /// The fallback fonts of the chain along with their `Id`s.
+        pub fn fallbacks(&self) -> &'a [(Id, super::Font)] {
+            self.fallbacks
+        }
+
+        /// The first font within the chain that covers the given character, along with the `Id` of
+        /// the fallback font if it is not the first font.
+        pub fn font_for(&self, ch: char) -> (Option<Id>, &'a super::Font) {
+            let covers = |font: &super::Font| font.glyph(ch).id() != super::GlyphId(0);
+            if self.fallbacks.is_empty() || covers(self.font) {
+                return (None, self.font);
+            }
+            self.fallbacks
+                .iter()
+                .find(|&(_, font)| covers(font))
+                .map_or((None, self.font), |&(id, ref font)| (Some(id), font))
+        }
+
+        // The font of the chain with the given fallback `id`, or the first font if `None`.
+        #[cfg(feature = "shaping")]
+        fn font_by_id(&self, id: Option<Id>) -> &'a super::Font {
+            id.and_then(|id| self.fallbacks.iter().find(|&(f_id, _)| *f_id == id))
+                .map_or(self.font, |(_, font)| font)
+        }
+
+        /// Shape the given line of `text`, returning `None` if the chain has no shaping `Face`.
+        ///
+        /// Lines containing tab characters are not shaped, so that each tab may reach its stop.
+        #[cfg(feature = "shaping")]
+        pub fn shape(&self, text: &str, scale: super::Scale) -> Option<super::shape::Line> {
+            if text.contains('\t') {
+                return None;
+            }
+            self.face
+                .and_then(|face| super::shape::line(*self, face, text, scale))
+        }
+
+        /// Lay out the given line of `text` starting from the given point.
+        ///
+        /// The text is laid out left-to-right, unless it is shaped in which case its direction is
+        /// guessed from its script.
+        pub fn layout<'b>(
+            self,
+            text: &'b str,
+            scale: super::Scale,
+            start: super::rt::Point<f32>,
+        ) -> Layout<'a, 'b> {
+            self.layout_run(text, scale, start, None)
+        }
+
+        /// Lay out a run of `text` with the given `direction` starting from the given point.
+        ///
+        /// The glyphs of a right-to-left run are yielded from left to right, i.e. starting from the
+        /// run's last character. If `direction` is `None`, the direction is the same as for
+        /// `layout`.
+        pub fn layout_run<'b>(
+            self,
+            text: &'b str,
+            scale: super::Scale,
+            start: super::rt::Point<f32>,
+            direction: Option<super::Direction>,
+        ) -> Layout<'a, 'b> {
+            #[cfg(feature = "shaping")]
+            {
+                let line = self
+                    .face
+                    .filter(|_| !text.contains('\t'))
+                    .and_then(|face| super::shape::run(self, face, text, scale, direction));
+                if let Some(line) = line {
+                    let kind = LayoutKind::Shaped {
+                        chain: self,
+                        glyphs: line.glyphs.into_iter(),
+                        scale: scale,
+                        start: start,
+                    };
+                    return Layout { kind: kind };
+                }
+            }
+            let rtl = direction == Some(super::Direction::RightToLeft);
+            let kind = LayoutKind::Chars {
+                chain: self,
+                chars: text.chars(),
+                yielded: 0,
+                len: if rtl { text.chars().count() } else { 0 },
+                rtl: rtl,
+                scale: scale,
+                start: start,
+                caret: 0.0,
+                last_glyph: None,
+            };
+            Layout { kind: kind }
+        }
+
+        /// The advance width of each character of the given `text`.
+        ///
+        /// When shaping, only the text preceding the first line break is shaped.
+        pub fn advances<'b>(self, text: &'b str, scale: super::Scale) -> Advances<'a, 'b> {
+            Advances {
+                chain: self,
+                chars: text.chars(),
+                scale: scale,
+                last_glyph: None,
+                x: 0.0,
+                #[cfg(feature = "shaping")]
+                shaped: match self.shaped {
+                    Some((line, shaped_scale, advances))
+                        if text.as_ptr() == line.as_ptr()
+                            && text.len() >= line.len()
+                            && scale == shaped_scale =>
+                    {
+                        std::borrow::Cow::Borrowed(advances)
+                    }
+                    _ => {
+                        let end = text.find(['\r', '\n']);
+                        let line = &text[..end.unwrap_or(text.len())];
+                        let advances = self.shape(line, scale).map(|line| line.advances);
+                        std::borrow::Cow::Owned(advances.unwrap_or_default())
+                    }
+                },
+                #[cfg(feature = "shaping")]
+                shaped_index: 0,
+            }
+        }
+
+        /// The right edge of each character of the given line of `text` starting from the given
+        /// point.
+        pub fn char_edges<'b>(
+            self,
+            text: &'b str,
+            scale: super::Scale,
+            start: super::rt::Point<f32>,
+        ) -> CharEdges<'a, 'b> {
+            // Shaped glyphs do not correspond to characters, so their advances are used instead.
+            #[cfg(feature = "shaping")]
+            {
+                if let Some(line) = self.shape(text, scale) {
+                    let kind = CharEdgesKind::Shaped {
+                        advances: line.advances.into_iter(),
+                        x: start.x as Scalar,
+                    };
+                    return CharEdges { kind: kind };
+                }
+            }
+            CharEdges {
+                kind: CharEdgesKind::Glyphs(self.layout(text, scale, start)),
+            }
+        }
+    }
+
+    impl<'a> From<&'a super::Font> for Chain<'a> {
+        fn from(font: &'a super::Font) -> Self {
+            Chain::new(font, &[])
+        }
+    }
+
+    impl<'a, 'b> Iterator for Layout<'a, 'b> {
+        type Item = (usize, Option<Id>, super::PositionedGlyph);
+        fn next(&mut self) -> Option<Self::Item> {
+            match self.kind {
+                LayoutKind::Chars {
+                    chain,
+                    ref mut chars,
+                    ref mut yielded,
+                    len,
+                    rtl,
+                    scale,
+                    start,
+                    ref mut caret,
+                    ref mut last_glyph,
+                } => {
+                    let (i, ch) = if rtl {
+                        let ch = chars.next_back()?;
+                        (len - 1 - *yielded, ch)
+                    } else {
+                        (*yielded, chars.next()?)
+                    };
+                    *yielded += 1;
+                    if ch == '\t' {
+                        let g = chain.tab_glyph(*caret, scale);
+                        let g = g.positioned(super::rt::point(start.x + *caret, start.y));
+                        *caret += g.unpositioned().h_metrics().advance_width;
+                        *last_glyph = None;
+                        return Some((i, None, g));
+                    }
+                    let (id, font) = chain.font_for(ch);
+                    let g = font.glyph(ch).scaled(scale);
+                    if let Some((last_id, last)) = *last_glyph {
+                        if last_id == id {
+                            *caret += font.pair_kerning(scale, last, g.id());
+                        }
+                    }
+                    let g = g.positioned(super::rt::point(start.x + *caret, start.y));
+                    *caret += g.unpositioned().h_metrics().advance_width;
+                    *last_glyph = Some((id, g.id()));
+                    Some((i, id, g))
+                }
+                #[cfg(feature = "shaping")]
+                LayoutKind::Shaped {
+                    chain,
+                    ref mut glyphs,
+                    scale,
+                    start,
+                } => {
+                    let glyph = glyphs.next()?;
+                    let font = chain.font_by_id(glyph.font_id);
+                    let g = font
+                        .glyph(glyph.id)
+                        .scaled(scale)
+                        .positioned(start + glyph.position);
+                    Some((glyph.char_index, glyph.font_id, g))
+                }
+            }
+        }
+    }
+
+    impl<'a, 'b> Iterator for Advances<'a, 'b> {
+        type Item = Scalar;
+        fn next(&mut self) -> Option<Self::Item> {
+            let ch = self.chars.next()?;
+            #[cfg(feature = "shaping")]
+            {
+                if let Some(&advance) = self.shaped.get(self.shaped_index) {
+                    self.shaped_index += 1;
+                    return Some(advance);
+                }
+            }
+            if ch == '\t' {
+                let advance = self.chain.tab_advance(self.x, self.scale);
+                self.x += advance;
+                self.last_glyph = None;
+                return Some(advance as Scalar);
+            }
+            // Adapted from the rusttype::LayoutIter::next src.
+            let (id, font) = self.chain.font_for(ch);
+            let g = font.glyph(ch).scaled(self.scale);
+            let kern = match self.last_glyph {
+                Some((last_id, last)) if last_id == id => {
+                    font.pair_kerning(self.scale, last, g.id())
+                }
+                _ => 0.0,
+            };
+            self.last_glyph = Some((id, g.id()));
+            let advance = kern + g.h_metrics().advance_width;
+            self.x += advance;
+            Some(advance as Scalar)
+        }
+    }
+
+    impl<'a, 'b> Iterator for CharEdges<'a, 'b> {
+        type Item = (Option<Scalar>, Scalar);
+        fn next(&mut self) -> Option<Self::Item> {
+            match self.kind {
+                CharEdgesKind::Glyphs(ref mut layout) => layout.next().map(|(_, _, g)| {
+                    let advance = g.unpositioned().h_metrics().advance_width;
+                    // Blank glyphs such as tabs end at the caret following them.
+                    let right = match g.pixel_bounding_box() {
+                        Some(bb) => bb.max.x as Scalar,
+                        None => (g.position().x + advance) as Scalar,
+                    };
+                    (Some(right), advance as Scalar)
+                }),
+                #[cfg(feature = "shaping")]
+                CharEdgesKind::Shaped {
+                    ref mut advances,
+                    ref mut x,
+                } => advances.next().map(|advance| {
+                    *x += advance;
+                    (Some(*x), advance)
+                }),
+            }
+        }
     }
 
     /// Load a single `Font` from a file at the given path.
@@ -246,6 +1115,214 @@ pub mod font {
     }
 }
 
+/// Shaping of lines of text for ligatures, contextual forms and complex scripts.
+///
+/// Requires the `shaping` feature. Text is shaped using the first font of a `font::Chain`, given
+/// the `Face` holding that font's data. Glyphs missing from the font are substituted by the first
+/// of the chain's fallbacks that covers the character, in which case they are not shaped.
+#[cfg(feature = "shaping")]
+pub mod shape {
+    use rustybuzz;
+    use std;
+    use unicode_segmentation::UnicodeSegmentation;
+    use Scalar;
+
+    /// The data of a font, retained in order to shape text with it.
+    #[derive(Clone)]
+    pub struct Face {
+        // The face is parsed once, as doing so builds its substitution and positioning tables. It
+        // borrows from `data` and so is declared first, in order that it is dropped first.
+        parsed: rustybuzz::Face<'static>,
+        data: std::sync::Arc<[u8]>,
+        index: u32,
+    }
+
+    /// A single glyph of a shaped line.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Glyph {
+        /// The glyph within its font.
+        pub id: super::GlyphId,
+        /// The fallback font that produced the glyph, or `None` for the chain's first font.
+        pub font_id: Option<super::font::Id>,
+        /// The index of the first character of the cluster from which the glyph was produced.
+        pub char_index: usize,
+        /// The position of the glyph's origin relative to the start of the line, with *y* down.
+        pub position: super::rt::Vector<f32>,
+    }
+
+    /// A shaped line of text.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Line {
+        /// The glyphs of the line in visual order, i.e. from left to right.
+        pub glyphs: Vec<Glyph>,
+        /// The advance width of each character of the line in logical order.
+        ///
+        /// The advance of each cluster is shared evenly between the grapheme clusters that begin
+        /// within it, so that a cursor may be placed between them. All other characters have an
+        /// advance of `0.0`.
+        pub advances: Vec<Scalar>,
+    }
+
+    impl Face {
+        /// Retain the given font data for shaping, where `index` is that of the font within a
+        /// font collection or `0`.
+        ///
+        /// Returns `None` if the data could not be parsed.
+        pub fn from_bytes<B>(data: B, index: u32) -> Option<Self>
+        where
+            B: Into<std::sync::Arc<[u8]>>,
+        {
+            let data: std::sync::Arc<[u8]> = data.into();
+            // The data is never mutated nor moved out of its allocation, which is shared by every
+            // clone of the `Face` and outlives `parsed`, so it may be borrowed for as long as the
+            // `Face` lives.
+            #[allow(unsafe_code)]
+            let bytes: &'static [u8] = unsafe { &*(&*data as *const [u8]) };
+            let parsed = rustybuzz::Face::from_slice(bytes, index)?;
+            Some(Face {
+                parsed: parsed,
+                data: data,
+                index: index,
+            })
+        }
+
+        // The parsed face, borrowing no longer than `self`.
+        fn rustybuzz(&self) -> &rustybuzz::Face<'_> {
+            &self.parsed
+        }
+    }
+
+    impl std::fmt::Debug for Face {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.debug_struct("Face")
+                .field("len", &self.data.len())
+                .field("index", &self.index)
+                .finish()
+        }
+    }
+
+    /// Shape the given line of `text` using the `face` of the chain's first font.
+    ///
+    /// The direction and script of the line are guessed from its contents.
+    pub fn line(
+        chain: super::font::Chain,
+        face: &Face,
+        text: &str,
+        scale: super::Scale,
+    ) -> Option<Line> {
+        run(chain, face, text, scale, None)
+    }
+
+    /// Shape a run of `text` with the given `direction` using the `face` of the chain's first font.
+    ///
+    /// If `direction` is `None` it is guessed from the text, as is the script of the run.
+    pub fn run(
+        chain: super::font::Chain,
+        face: &Face,
+        text: &str,
+        scale: super::Scale,
+        direction: Option<super::Direction>,
+    ) -> Option<Line> {
+        // Shaped positions are in font units, which rusttype scales by the font's height.
+        let v_metrics = chain.font().v_metrics_unscaled();
+        let height = v_metrics.ascent - v_metrics.descent;
+        if height <= 0.0 {
+            return None;
+        }
+        let unit_y = scale.y / height;
+        let unit_x = unit_y * scale.x / scale.y;
+
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+        match direction {
+            Some(super::Direction::LeftToRight) => {
+                buffer.set_direction(rustybuzz::Direction::LeftToRight)
+            }
+            Some(super::Direction::RightToLeft) => {
+                buffer.set_direction(rustybuzz::Direction::RightToLeft)
+            }
+            None => (),
+        }
+        let output = rustybuzz::shape(face.rustybuzz(), &[], buffer);
+
+        let char_starts: Vec<usize> = text.char_indices().map(|(byte, _)| byte).collect();
+        let char_index = |byte: usize| match char_starts.binary_search(&byte) {
+            Ok(i) => i,
+            Err(i) => i.saturating_sub(1),
+        };
+
+        let mut glyphs = Vec::with_capacity(output.len());
+        let mut cluster_advances = vec![0.0; char_starts.len()];
+        let mut is_cluster_start = vec![false; char_starts.len()];
+        let mut x = 0.0;
+        let infos = output.glyph_infos().iter();
+        for (info, pos) in infos.zip(output.glyph_positions()) {
+            let byte = info.cluster as usize;
+            let char_i = char_index(byte);
+            let fallback = match info.glyph_id {
+                0 => text[byte..].chars().next().map(|ch| chain.font_for(ch)),
+                _ => None,
+            };
+            let (id, font_id, advance, offset) = match fallback {
+                Some((Some(font_id), font)) => {
+                    let ch = text[byte..].chars().next().unwrap_or(' ');
+                    let g = font.glyph(ch).scaled(scale);
+                    let advance = g.h_metrics().advance_width;
+                    (g.id(), Some(font_id), advance, super::rt::vector(0.0, 0.0))
+                }
+                _ => {
+                    let advance = pos.x_advance as f32 * unit_x;
+                    let offset = super::rt::vector(
+                        pos.x_offset as f32 * unit_x,
+                        -pos.y_offset as f32 * unit_y,
+                    );
+                    (super::GlyphId(info.glyph_id), None, advance, offset)
+                }
+            };
+            glyphs.push(Glyph {
+                id: id,
+                font_id: font_id,
+                char_index: char_i,
+                position: super::rt::vector(x, 0.0) + offset,
+            });
+            x += advance;
+            if let Some(cluster) = cluster_advances.get_mut(char_i) {
+                *cluster += advance as Scalar;
+                is_cluster_start[char_i] = true;
+            }
+        }
+
+        // Share the advance of each cluster between the grapheme clusters that begin within it.
+        let mut is_grapheme_start = vec![false; char_starts.len()];
+        for (byte, _) in text.grapheme_indices(true) {
+            is_grapheme_start[char_index(byte)] = true;
+        }
+        let mut advances = vec![0.0; char_starts.len()];
+        let mut start = 0;
+        while start < char_starts.len() {
+            let end = (start + 1..char_starts.len())
+                .find(|&i| is_cluster_start[i])
+                .unwrap_or(char_starts.len());
+            let graphemes: Vec<usize> = (start..end).filter(|&i| is_grapheme_start[i]).collect();
+            match graphemes.len() {
+                0 => advances[start] = cluster_advances[start],
+                n => {
+                    for &i in &graphemes {
+                        advances[i] = cluster_advances[start] / n as Scalar;
+                    }
+                }
+            }
+            start = end;
+        }
+
+        Some(Line {
+            glyphs: glyphs,
+            advances: advances,
+        })
+    }
+}
+
 /// Logic and types specific to individual glyph layout.
 pub mod glyph {
     use std;
@@ -265,15 +1342,23 @@ pub mod glyph {
         y: Range,
         /// The position of the next `Rect`'s left edge along the *x* axis.
         next_left: Scalar,
-        /// `PositionedGlyphs` yielded by the RustType `LayoutIter`.
-        layout: super::LayoutIter<'font, 'b>,
+        /// `PositionedGlyphs` yielded by the font `Layout`.
+        edges: super::font::CharEdges<'font, 'b>,
+        /// The `char` for each glyph yielded by the `layout`.
+        chars: std::iter::Enumerate<std::str::Chars<'b>>,
+        /// The extra space between words if the line is justified.
+        spacing: super::line::WordSpacing,
+        /// The total extra space inserted before the next glyph.
+        offset: Scalar,
+        /// The `x` `Range` of each character, if the line contains right-to-left text.
+        visual: Option<std::vec::IntoIter<Range>>,
     }
 
     /// An iterator that, for every `(line, line_rect)` pair yielded by the given iterator,
     /// produces an iterator that yields a `Rect` for every character in that line.
     pub struct RectsPerLine<'a, I> {
         lines_with_rects: I,
-        font: &'a super::Font,
+        font: super::font::Chain<'a>,
         font_size: FontSize,
     }
 
@@ -326,17 +1411,18 @@ pub mod glyph {
     ///
     /// This is useful when information about character positioning is needed when reasoning about
     /// text layout.
-    pub fn rects_per_line<'a, I>(
+    pub fn rects_per_line<'a, I, C>(
         lines_with_rects: I,
-        font: &'a super::Font,
+        font: C,
         font_size: FontSize,
     ) -> RectsPerLine<'a, I>
     where
         I: Iterator<Item = (&'a str, Rect)>,
+        C: Into<super::font::Chain<'a>>,
     {
         RectsPerLine {
             lines_with_rects: lines_with_rects,
-            font: font,
+            font: font.into(),
             font_size: font_size,
         }
     }
@@ -348,15 +1434,16 @@ pub mod glyph {
     /// will be produced.
     ///
     /// All lines that have no selected `Rect`s will be skipped.
-    pub fn selected_rects_per_line<'a, I>(
+    pub fn selected_rects_per_line<'a, I, C>(
         lines_with_rects: I,
-        font: &'a super::Font,
+        font: C,
         font_size: FontSize,
         start: super::cursor::Index,
         end: super::cursor::Index,
     ) -> SelectedRectsPerLine<'a, I>
     where
         I: Iterator<Item = (&'a str, Rect)>,
+        C: Into<super::font::Chain<'a>>,
     {
         SelectedRectsPerLine {
             enumerated_rects_per_line: rects_per_line(lines_with_rects, font, font_size)
@@ -381,10 +1468,31 @@ pub mod glyph {
             lines_with_rects.next().map(|(line, line_rect)| {
                 let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
                 let point = super::rt::Point { x: x, y: y };
+                let width = super::line::natural_width(line, font, font_size);
+                let spacing = super::line::WordSpacing::new(line, width, line_rect.w());
+
+                // Lines containing right-to-left text are laid out run by run. Without the line's
+                // `Info`, its own first strong character determines the paragraph direction.
+                let direction = super::line::base_direction(line);
+                let runs = super::line::visual_runs(line, direction);
+                let visual = if runs.iter().any(super::line::VisualRun::is_right_to_left) {
+                    let left = line_rect.x.start;
+                    let ranges = super::line::visual_char_ranges(
+                        line, &runs, font, font_size, left, spacing,
+                    );
+                    Some(ranges.into_iter())
+                } else {
+                    None
+                };
+
                 Rects {
                     next_left: line_rect.x.start,
-                    layout: font.layout(line, scale, point),
+                    edges: font.char_edges(line, scale, point),
                     y: line_rect.y,
+                    chars: line.chars().enumerate(),
+                    spacing: spacing,
+                    offset: 0.0,
+                    visual: visual,
                 }
             })
         }
@@ -437,15 +1545,25 @@ pub mod glyph {
         fn next(&mut self) -> Option<Self::Item> {
             let Rects {
                 ref mut next_left,
-                ref mut layout,
+                ref mut edges,
+                ref mut chars,
+                ref mut offset,
+                ref mut visual,
+                spacing,
                 y,
             } = *self;
-            layout.next().map(|g| {
+            if let Some(ref mut visual) = *visual {
+                return visual.next().map(|x| Rect { x: x, y: y });
+            }
+            edges.next().map(|(edge, advance)| {
                 let left = *next_left;
-                let right = g
-                    .pixel_bounding_box()
-                    .map(|bb| bb.max.x as Scalar)
-                    .unwrap_or_else(|| left + g.unpositioned().h_metrics().advance_width as Scalar);
+                let glyph_offset = *offset;
+                let extra = chars.next().map_or(0.0, |(i, ch)| spacing.after(i, ch));
+                *offset += extra;
+                let right = edge
+                    .map(|edge| edge + glyph_offset)
+                    .unwrap_or_else(|| left + advance)
+                    + extra;
                 *next_left = right;
                 let x = Range::new(left, right);
                 Rect { x: x, y: y }
@@ -477,6 +1595,7 @@ pub mod glyph {
 pub mod cursor {
     use position::{Align, Point, Range, Rect, Scalar};
     use std;
+    use unicode_segmentation::UnicodeSegmentation;
     use FontSize;
 
     /// Every possible cursor position within each line of text yielded by the given iterator.
@@ -486,7 +1605,7 @@ pub mod cursor {
     #[derive(Clone)]
     pub struct XysPerLine<'a, I> {
         lines_with_rects: I,
-        font: &'a super::Font,
+        font: super::font::Chain<'a>,
         text: &'a str,
         font_size: FontSize,
     }
@@ -516,7 +1635,67 @@ pub mod cursor {
     /// `Xs` iterators are produced by the `XysPerLine` iterator.
     pub struct Xs<'font, 'b> {
         next_x: Option<Scalar>,
-        layout: super::LayoutIter<'font, 'b>,
+        edges: super::font::CharEdges<'font, 'b>,
+        chars: std::iter::Enumerate<std::str::Chars<'b>>,
+        spacing: super::line::WordSpacing,
+        offset: Scalar,
+        // Each cursor position, if the line contains right-to-left text.
+        visual: Option<std::vec::IntoIter<Scalar>>,
+    }
+
+    // The position of the cursor before each character of a line and after its last character,
+    // given the `x` `Range` of each character. The cursor before a character lies on its leading
+    // edge, i.e. its right edge if it belongs to a right-to-left run.
+    fn visual_xs(runs: &[super::line::VisualRun], ranges: &[Range], left: Scalar) -> Vec<Scalar> {
+        let mut rtl = vec![false; ranges.len()];
+        for run in runs.iter().filter(|run| run.is_right_to_left()) {
+            for i in run.char_range.clone() {
+                rtl[i] = true;
+            }
+        }
+        let leading = |i: usize| {
+            if rtl[i] {
+                ranges[i].end
+            } else {
+                ranges[i].start
+            }
+        };
+        let mut xs: Vec<Scalar> = (0..ranges.len()).map(leading).collect();
+        let end = match ranges.len() {
+            0 => left,
+            n if rtl[n - 1] => ranges[n - 1].start,
+            n => ranges[n - 1].end,
+        };
+        xs.push(end);
+        xs
+    }
+
+    // The index of the first character of each extended grapheme cluster within the given line.
+    fn grapheme_starts(line: &str) -> impl Iterator<Item = usize> + '_ {
+        line.graphemes(true).scan(0, |char_i, grapheme| {
+            let start = *char_i;
+            *char_i += grapheme.chars().count();
+            Some(start)
+        })
+    }
+
+    // The range of characters occupied by each segment between the Unicode word boundaries of
+    // the given line, along with whether or not the segment is a word, i.e. contains a letter or
+    // digit rather than only whitespace and punctuation.
+    fn word_segments(line: &str) -> impl Iterator<Item = (std::ops::Range<usize>, bool)> + '_ {
+        line.split_word_bounds().scan(0, |char_i, segment| {
+            let start = *char_i;
+            *char_i += segment.chars().count();
+            let is_word = segment.chars().any(char::is_alphanumeric);
+            Some((start..*char_i, is_word))
+        })
+    }
+
+    // The range of characters occupied by each word within the given line, in order.
+    fn word_ranges(line: &str) -> impl Iterator<Item = std::ops::Range<usize>> + '_ {
+        word_segments(line)
+            .filter(|&(_, is_word)| is_word)
+            .map(|(range, _)| range)
     }
 
     /// An index representing the position of a cursor within some text.
@@ -532,13 +1711,16 @@ pub mod cursor {
     }
 
     impl Index {
-        /// The cursor index of the beginning of the word (block of non-whitespace) before `self`.
+        /// The cursor index of the beginning of the word before `self`.
+        ///
+        /// Words are found via Unicode word segmentation (UAX #29), so punctuation separates
+        /// words and each CJK ideograph forms its own word.
         ///
         /// If `self` is at the beginning of the line, call previous, which returns the last
         /// index position of the previous line, or None if it's the first line
         ///
-        /// If `self` points to whitespace, skip past that whitespace, then return the index of
-        /// the start of the word that precedes the whitespace
+        /// If `self` points to whitespace or punctuation, skip past it, then return the index of
+        /// the start of the word that precedes it
         ///
         /// If `self` is in the middle or end of a word, return the index of the start of that word
         pub fn previous_word_start<I>(self, text: &str, mut line_infos: I) -> Option<Self>
@@ -547,43 +1729,32 @@ pub mod cursor {
         {
             let Index { line, char } = self;
             if char > 0 {
-                line_infos.nth(line).and_then(|line_info| {
-                    let line_count = line_info.char_range().count();
-                    let mut chars_rev = (&text[line_info.byte_range()]).chars().rev();
-                    if char != line_count {
-                        chars_rev.nth(line_count - char - 1);
-                    }
-                    let mut new_char = 0;
-                    let mut hit_non_whitespace = false;
-                    for (i, char_) in chars_rev.enumerate() {
-                        // loop until word starts, then continue until the word ends
-                        if !char_.is_whitespace() {
-                            hit_non_whitespace = true;
-                        }
-                        if char_.is_whitespace() && hit_non_whitespace {
-                            new_char = char - i;
-                            break;
-                        }
-                    }
-                    Some(Index {
+                line_infos.nth(line).map(|line_info| {
+                    let new_char = word_ranges(&text[line_info.byte_range()])
+                        .take_while(|word| word.start < char)
+                        .last()
+                        .map_or(0, |word| word.start);
+                    Index {
                         line: line,
                         char: new_char,
-                    })
+                    }
                 })
             } else {
                 self.previous(line_infos)
             }
         }
 
-        /// The cursor index of the end of the first word (block of non-whitespace) after `self`.
+        /// The cursor index of the end of the first word after `self`.
+        ///
+        /// Words are found via Unicode word segmentation (UAX #29), as for `previous_word_start`.
         ///
         /// If `self` is at the end of the text, this returns `None`.
         ///
         /// If `self` is at the end of a line other than the last, this returns the first index of
         /// the next line.
         ///
-        /// If `self` points to whitespace, skip past that whitespace, then return the index of
-        /// the end of the word after the whitespace
+        /// If `self` points to whitespace or punctuation, skip past it, then return the index of
+        /// the end of the word after it
         ///
         /// If `self` is in the middle or start of a word, return the index of the end of that word
         pub fn next_word_end<I>(self, text: &str, mut line_infos: I) -> Option<Self>
@@ -594,22 +1765,9 @@ pub mod cursor {
             line_infos.nth(line).and_then(|line_info| {
                 let line_count = line_info.char_range().count();
                 if char < line_count {
-                    let mut chars = (&text[line_info.byte_range()]).chars();
-                    let mut new_char = line_count;
-                    let mut hit_non_whitespace = false;
-                    if char != 0 {
-                        chars.nth(char - 1);
-                    }
-                    for (i, char_) in chars.enumerate() {
-                        // loop until word starts, then continue until the word ends
-                        if !char_.is_whitespace() {
-                            hit_non_whitespace = true;
-                        }
-                        if char_.is_whitespace() && hit_non_whitespace {
-                            new_char = char + i;
-                            break;
-                        }
-                    }
+                    let new_char = word_ranges(&text[line_info.byte_range()])
+                        .find(|word| word.end > char)
+                        .map_or(line_count, |word| word.end);
                     Some(Index {
                         line: line,
                         char: new_char,
@@ -623,6 +1781,42 @@ pub mod cursor {
             })
         }
 
+        /// The cursor indices at the start and end of the word at `self`, e.g. for selecting the
+        /// word upon a double-click.
+        ///
+        /// A word touching `self` is preferred over the whitespace or punctuation beside it. If
+        /// there is no such word, the bounds of the run of whitespace or punctuation at `self`
+        /// are returned instead.
+        ///
+        /// Returns `None` if `self` lies beyond the given lines.
+        pub fn word_bounds<I>(self, text: &str, mut line_infos: I) -> Option<(Self, Self)>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line).map(|line_info| {
+                let segments: Vec<_> = word_segments(&text[line_info.byte_range()]).collect();
+                let within =
+                    |range: &std::ops::Range<usize>| range.start <= char && char < range.end;
+                let range = segments
+                    .iter()
+                    .find(|(range, is_word)| *is_word && within(range))
+                    .or_else(|| {
+                        segments
+                            .iter()
+                            .find(|(range, is_word)| *is_word && range.end == char)
+                    })
+                    .or_else(|| segments.iter().find(|(range, _)| within(range)))
+                    .or_else(|| segments.last())
+                    .map_or(char..char, |(range, _)| range.clone());
+                let index = |char| Index {
+                    line: line,
+                    char: char,
+                };
+                (index(range.start), index(range.end))
+            })
+        }
+
         /// The cursor index that comes before `self`.
         ///
         /// If `self` is at the beginning of the text, this returns `None`.
@@ -659,37 +1853,230 @@ pub mod cursor {
                     }
                 })
             } else {
-                None
+                None
+            }
+        }
+
+        /// The cursor index that follows `self`.
+        ///
+        /// If `self` is at the end of the text, this returns `None`.
+        ///
+        /// If `self` is at the end of a line other than the last, this returns the first index of
+        /// the next line.
+        ///
+        /// If `self` is a position other than the end of a line, it will return the position that
+        /// is immediately to the right.
+        pub fn next<I>(self, mut line_infos: I) -> Option<Self>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line).and_then(|info| {
+                if char >= info.char_range().count() {
+                    line_infos.next().map(|_| Index {
+                        line: line + 1,
+                        char: 0,
+                    })
+                } else {
+                    Some(Index {
+                        line: line,
+                        char: char + 1,
+                    })
+                }
+            })
+        }
+
+        /// The cursor index at the start of the grapheme cluster that comes before `self`.
+        ///
+        /// This is the same as `previous`, however a grapheme cluster made up of several
+        /// characters, e.g. a letter followed by a combining accent, is passed over as a whole.
+        pub fn previous_grapheme<I>(self, text: &str, mut line_infos: I) -> Option<Self>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            let Index { line, char } = self;
+            if char == 0 {
+                return self.previous(line_infos);
+            }
+            line_infos.nth(line).and_then(|info| {
+                if char > info.char_range().count() {
+                    return None;
+                }
+                let new_char = grapheme_starts(&text[info.byte_range()])
+                    .take_while(|&start| start < char)
+                    .last()
+                    .unwrap_or(0);
+                Some(Index {
+                    line: line,
+                    char: new_char,
+                })
+            })
+        }
+
+        /// The cursor index at the end of the grapheme cluster that follows `self`.
+        ///
+        /// This is the same as `next`, however a grapheme cluster made up of several characters,
+        /// e.g. a letter followed by a combining accent, is passed over as a whole.
+        pub fn next_grapheme<I>(self, text: &str, mut line_infos: I) -> Option<Self>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            let Index { line, char } = self;
+            line_infos.nth(line).and_then(|info| {
+                let line_count = info.char_range().count();
+                if char >= line_count {
+                    line_infos.next().map(|_| Index {
+                        line: line + 1,
+                        char: 0,
+                    })
+                } else {
+                    let new_char = grapheme_starts(&text[info.byte_range()])
+                        .find(|&start| start > char)
+                        .unwrap_or(line_count);
+                    Some(Index {
+                        line: line,
+                        char: new_char,
+                    })
+                }
+            })
+        }
+
+        /// The cursor index that is visually to the left of `self`.
+        ///
+        /// This is the same as `previous_grapheme`, however within lines containing right-to-left
+        /// text the cursor moves according to the visual order of the characters. For example,
+        /// within a right-to-left run the cursor moves towards the end of the text.
+        ///
+        /// If `self` is at the left edge of a line, this moves to the end of the previous line, or
+        /// to the start of the next line if the line belongs to a right-to-left paragraph.
+        pub fn left<I>(self, text: &str, line_infos: I) -> Option<Self>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            self.step_visually(text, line_infos, false)
+        }
+
+        /// The cursor index that is visually to the right of `self`.
+        ///
+        /// This is the same as `next_grapheme`, however within lines containing right-to-left
+        /// text the cursor moves according to the visual order of the characters. For example,
+        /// within a right-to-left run the cursor moves towards the start of the text.
+        ///
+        /// If `self` is at the right edge of a line, this moves to the start of the next line, or
+        /// to the end of the previous line if the line belongs to a right-to-left paragraph.
+        pub fn right<I>(self, text: &str, line_infos: I) -> Option<Self>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            self.step_visually(text, line_infos, true)
+        }
+
+        // Move to the next grapheme boundary in the given visual direction.
+        fn step_visually<I>(self, text: &str, line_infos: I, rightwards: bool) -> Option<Self>
+        where
+            I: Iterator<Item = super::line::Info>,
+        {
+            let Index { line, char } = self;
+            let mut line_infos = line_infos.enumerate();
+            let mut previous = None;
+            let info = loop {
+                match line_infos.next() {
+                    Some((i, info)) if i == line => break info,
+                    Some((_, info)) => previous = Some(info),
+                    None => return None,
+                }
+            };
+            let n = info.char_range().len();
+            if char > n {
+                return None;
+            }
+
+            // The logical index of the char at each visual position, and whether each char is
+            // right-to-left.
+            let mut order = Vec::with_capacity(n);
+            let mut rtl = vec![false; n];
+            for run in info.visual_runs(text) {
+                if run.is_right_to_left() {
+                    order.extend(run.char_range.clone().rev());
+                    rtl[run.char_range].iter_mut().for_each(|rtl| *rtl = true);
+                } else {
+                    order.extend(run.char_range);
+                }
+            }
+            let mut position = vec![0; n];
+            for (visual, &logical) in order.iter().enumerate() {
+                position[logical] = visual;
+            }
+
+            // The visual gap in which each cursor index lies, i.e. the leading edge of the
+            // following char, or the trailing edge of the last char.
+            let gap = |c: usize| match c {
+                c if c < n && rtl[c] => position[c] + 1,
+                c if c < n => position[c],
+                0 => 0,
+                _ if rtl[n - 1] => position[n - 1],
+                _ => position[n - 1] + 1,
+            };
+            let mut index_at_gap = vec![None; n + 1];
+            for c in (0..n + 1).rev() {
+                index_at_gap[gap(c)] = Some(c);
+            }
+            let mut is_boundary = vec![false; n + 1];
+            for start in grapheme_starts(&text[info.byte_range()]) {
+                is_boundary[start] = true;
+            }
+            is_boundary[n] = true;
+
+            let mut g = gap(char);
+            while (rightwards && g < n) || (!rightwards && g > 0) {
+                g = if rightwards { g + 1 } else { g - 1 };
+                if let Some(c) = index_at_gap[g] {
+                    if is_boundary[c] {
+                        return Some(Index {
+                            line: line,
+                            char: c,
+                        });
+                    }
+                }
+            }
+
+            // Cross to the adjacent line in logical order.
+            let forwards = rightwards == (info.direction == super::Direction::LeftToRight);
+            if forwards {
+                line_infos.next().map(|_| Index {
+                    line: line + 1,
+                    char: 0,
+                })
+            } else {
+                previous.map(|info| Index {
+                    line: line - 1,
+                    char: info.char_range().len(),
+                })
             }
         }
 
-        /// The cursor index that follows `self`.
-        ///
-        /// If `self` is at the end of the text, this returns `None`.
-        ///
-        /// If `self` is at the end of a line other than the last, this returns the first index of
-        /// the next line.
+        /// Moves `self` back to the start of the grapheme cluster within which it lies, so that
+        /// the cursor never separates the characters of a single grapheme cluster.
         ///
-        /// If `self` is a position other than the end of a line, it will return the position that
-        /// is immediately to the right.
-        pub fn next<I>(self, mut line_infos: I) -> Option<Self>
+        /// Returns `self` if it does not lie within the given lines.
+        pub fn snap_to_grapheme<I>(self, text: &str, mut line_infos: I) -> Self
         where
             I: Iterator<Item = super::line::Info>,
         {
             let Index { line, char } = self;
-            line_infos.nth(line).and_then(|info| {
-                if char >= info.char_range().count() {
-                    line_infos.next().map(|_| Index {
-                        line: line + 1,
-                        char: 0,
-                    })
-                } else {
-                    Some(Index {
+            match line_infos.nth(line) {
+                Some(ref info) if char < info.char_range().count() => {
+                    let new_char = grapheme_starts(&text[info.byte_range()])
+                        .take_while(|&start| start <= char)
+                        .last()
+                        .unwrap_or(0);
+                    Index {
                         line: line,
-                        char: char + 1,
-                    })
+                        char: new_char,
+                    }
                 }
-            })
+                _ => self,
+            }
         }
 
         /// Clamps `self` to the given lines.
@@ -728,15 +2115,18 @@ pub mod cursor {
     ///
     /// Yields `(xs, y_range)`, where `y_range` is the `Range` occupied by the line across the *y*
     /// axis and `xs` is every possible cursor position along the *x* axis
-    pub fn xys_per_line<'a, I>(
+    pub fn xys_per_line<'a, I, C>(
         lines_with_rects: I,
-        font: &'a super::Font,
+        font: C,
         text: &'a str,
         font_size: FontSize,
-    ) -> XysPerLine<'a, I> {
+    ) -> XysPerLine<'a, I>
+    where
+        C: Into<super::font::Chain<'a>>,
+    {
         XysPerLine {
             lines_with_rects: lines_with_rects,
-            font: font,
+            font: font.into(),
             text: text,
             font_size: font_size,
         }
@@ -751,16 +2141,19 @@ pub mod cursor {
     ///
     /// Yields `(xs, y_range)`, where `y_range` is the `Range` occupied by the line across the *y*
     /// axis and `xs` is every possible cursor position along the *x* axis.
-    pub fn xys_per_line_from_text<'a>(
+    pub fn xys_per_line_from_text<'a, C>(
         text: &'a str,
         line_infos: &'a [super::line::Info],
-        font: &'a super::Font,
+        font: C,
         font_size: FontSize,
         x_align: super::Justify,
         y_align: Align,
         line_spacing: Scalar,
         rect: Rect,
-    ) -> XysPerLineFromText<'a> {
+    ) -> XysPerLineFromText<'a>
+    where
+        C: Into<super::font::Chain<'a>>,
+    {
         let line_infos = line_infos.iter().cloned();
         let line_rects = super::line::rects(
             line_infos.clone(),
@@ -874,13 +2267,12 @@ pub mod cursor {
         let first_diff = (x_pos - first_x).abs();
         let mut closest = (first_idx, first_x);
         let mut closest_diff = first_diff;
+        // Cursor positions are not ordered along the *x* axis within bidirectional lines.
         for (i, x) in xs_enumerated {
             let diff = (x_pos - x).abs();
             if diff < closest_diff {
                 closest = (i, x);
                 closest_diff = diff;
-            } else {
-                break;
             }
         }
         closest
@@ -906,10 +2298,24 @@ pub mod cursor {
                 let (x, y) = (line_rect.left() as f32, line_rect.top() as f32);
                 let point = super::rt::Point { x: x, y: y };
                 let y = line_rect.y;
-                let layout = font.layout(line, scale, point);
+                let spacing = super::line::WordSpacing::new(line, line_info.width, line_rect.w());
+                let runs = line_info.visual_runs(text);
+                let visual = if runs.iter().any(super::line::VisualRun::is_right_to_left) {
+                    let left = line_rect.x.start;
+                    let ranges = super::line::visual_char_ranges(
+                        line, &runs, font, font_size, left, spacing,
+                    );
+                    Some(visual_xs(&runs, &ranges, left).into_iter())
+                } else {
+                    None
+                };
                 let xs = Xs {
                     next_x: Some(line_rect.x.start),
-                    layout: layout,
+                    edges: font.char_edges(line, scale, point),
+                    chars: line.chars().enumerate(),
+                    spacing: spacing,
+                    offset: 0.0,
+                    visual: visual,
                 };
                 (xs, y)
             })
@@ -927,11 +2333,25 @@ pub mod cursor {
         // Each possible cursor position along the *x* axis.
         type Item = Scalar;
         fn next(&mut self) -> Option<Self::Item> {
+            if let Some(ref mut visual) = self.visual {
+                return visual.next();
+            }
             self.next_x.map(|x| {
-                self.next_x = self.layout.next().map(|g| {
-                    g.pixel_bounding_box()
-                        .map(|r| r.max.x as Scalar)
-                        .unwrap_or_else(|| x + g.unpositioned().h_metrics().advance_width as Scalar)
+                let Xs {
+                    ref mut next_x,
+                    ref mut edges,
+                    ref mut chars,
+                    ref mut offset,
+                    spacing,
+                    ..
+                } = *self;
+                *next_x = edges.next().map(|(edge, advance)| {
+                    let glyph_offset = *offset;
+                    let extra = chars.next().map_or(0.0, |(i, ch)| spacing.after(i, ch));
+                    *offset += extra;
+                    edge.map(|edge| edge + glyph_offset)
+                        .unwrap_or_else(|| x + advance)
+                        + extra
                 });
                 x
             })
@@ -945,6 +2365,8 @@ pub mod cursor {
 pub mod line {
     use position::{Align, Range, Rect, Scalar};
     use std;
+    use unicode_bidi;
+    use unicode_linebreak::{self, BreakOpportunity};
     use FontSize;
 
     /// The two types of **Break** indices returned by the **WrapIndicesBy** iterators.
@@ -995,6 +2417,8 @@ pub mod line {
         pub end_break: Break,
         /// The total width of all characters within the line.
         pub width: Scalar,
+        /// The direction of the paragraph to which the line belongs.
+        pub direction: super::Direction,
     }
 
     /// An iterator yielding an `Info` struct for each line in the given `text` wrapped by the
@@ -1007,7 +2431,7 @@ pub mod line {
     /// [wrap_by_whitespace](./struct.Infos.html#method.wrap_by_whitespace).
     pub struct Infos<'a, F> {
         text: &'a str,
-        font: &'a super::Font,
+        font: super::font::Chain<'a>,
         font_size: FontSize,
         max_width: Scalar,
         next_break_fn: F,
@@ -1017,6 +2441,21 @@ pub mod line {
         start_char: usize,
         /// The break type of the previously yielded line
         last_break: Option<Break>,
+        /// The direction of the current paragraph.
+        direction: super::Direction,
+        /// The advances of the current paragraph, shaped once for all of its lines.
+        #[cfg(feature = "shaping")]
+        paragraph: ShapedParagraph,
+    }
+
+    // The advance of each character of a paragraph, i.e. the text up to a line break.
+    #[cfg(feature = "shaping")]
+    #[derive(Clone, Debug, Default)]
+    struct ShapedParagraph {
+        start_byte: usize,
+        end_byte: usize,
+        start_char: usize,
+        advances: Vec<Scalar>,
     }
 
     /// An iterator yielding a `Rect` for each line in
@@ -1024,22 +2463,53 @@ pub mod line {
     pub struct Rects<I> {
         infos: I,
         x_align: super::Justify,
+        bounding_x: Range,
         line_spacing: Scalar,
         next: Option<Rect>,
     }
 
+    /// The extra space inserted after each gap between the words of a line.
+    ///
+    /// When a line's `Rect` is wider than the natural width of its text, as is the case for
+    /// wrapped lines of `Justify::Full` text, the difference is distributed evenly between the
+    /// whitespace characters that separate its words. Leading and trailing whitespace is never
+    /// stretched.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct WordSpacing {
+        per_gap: Scalar,
+        first_word_char: usize,
+        last_word_char: usize,
+    }
+
+    /// A run of characters within a line that share the same direction.
+    ///
+    /// The runs of a line are produced in visual order, i.e. from left to right.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct VisualRun {
+        /// The range of bytes occupied by the run within the line.
+        pub byte_range: std::ops::Range<usize>,
+        /// The range of characters occupied by the run within the line.
+        pub char_range: std::ops::Range<usize>,
+        /// The direction in which the characters of the run are laid out.
+        pub direction: super::Direction,
+    }
+
     /// An iterator yielding a `Rect` for each selected line in a block of text.
     ///
-    /// The yielded `Rect`s represent the selected range within each line of text.
+    /// The yielded `Rect`s represent the selected range within each line of text. A line
+    /// containing both left-to-right and right-to-left text may yield a `Rect` for each of the
+    /// visually separate parts of its selection.
     ///
     /// Lines that do not contain any selected text will be skipped.
     pub struct SelectedRects<'a, I> {
         selected_char_rects_per_line: super::glyph::SelectedRectsPerLine<'a, I>,
+        /// The remaining selected character `Rect`s of the current line.
+        line: Option<std::iter::Peekable<super::glyph::SelectedRects<'a, 'a>>>,
     }
 
     /// An alias for function pointers that are compatible with the `Block`'s required text
     /// wrapping function.
-    pub type NextBreakFnPtr = fn(&str, &super::Font, FontSize, Scalar) -> (Break, Scalar);
+    pub type NextBreakFnPtr = fn(&str, super::font::Chain, FontSize, Scalar) -> (Break, Scalar);
 
     impl Break {
         /// Return the index at which the break occurs.
@@ -1077,6 +2547,9 @@ pub mod line {
                 start_byte: self.start_byte,
                 start_char: self.start_char,
                 last_break: None,
+                direction: self.direction,
+                #[cfg(feature = "shaping")]
+                paragraph: self.paragraph.clone(),
             }
         }
     }
@@ -1101,6 +2574,13 @@ pub mod line {
         pub fn char_range(self) -> std::ops::Range<usize> {
             self.start_char..self.end_char()
         }
+
+        /// The runs of the line within the given `text` in visual order.
+        ///
+        /// See the [visual_runs function](./fn.visual_runs.html).
+        pub fn visual_runs(self, text: &str) -> Vec<VisualRun> {
+            visual_runs(&text[self.byte_range()], self.direction)
+        }
     }
 
     impl<'a> Infos<'a, NextBreakFnPtr> {
@@ -1112,8 +2592,12 @@ pub mod line {
             self
         }
 
-        /// Converts `Self` into an `Infos` whose lines are wrapped at the whitespace prior to the
-        /// character that causes the line width to exceed the given `max_width`.
+        /// Converts `Self` into an `Infos` whose lines are wrapped at the last line break
+        /// opportunity prior to the character that causes the line width to exceed the given
+        /// `max_width`.
+        ///
+        /// Break opportunities follow the Unicode line breaking algorithm, i.e. they typically
+        /// follow whitespace and hyphens, and lie between the characters of scripts such as CJK.
         pub fn wrap_by_whitespace(mut self, max_width: Scalar) -> Self {
             self.next_break_fn = next_break_by_whitespace;
             self.max_width = max_width;
@@ -1121,37 +2605,14 @@ pub mod line {
         }
     }
 
-    /// A function for finding the advance width between the given character that also considers
-    /// the kerning for some previous glyph.
-    ///
-    /// This also updates the `last_glyph` with the glyph produced for the given `char`.
-    ///
-    /// This is primarily for use within the `next_break` functions below.
-    ///
-    /// The following code is adapted from the rusttype::LayoutIter::next src.
-    fn advance_width(
-        ch: char,
-        font: &super::Font,
-        scale: super::Scale,
-        last_glyph: &mut Option<super::GlyphId>,
-    ) -> Scalar {
-        let g = font.glyph(ch).scaled(scale);
-        let kern = last_glyph
-            .map(|last| font.pair_kerning(scale, last, g.id()))
-            .unwrap_or(0.0);
-        let advance_width = g.h_metrics().advance_width;
-        *last_glyph = Some(g.id());
-        (kern + advance_width) as Scalar
-    }
-
     /// Returns the next index at which the text naturally breaks via a newline character,
     /// along with the width of the line.
-    fn next_break(text: &str, font: &super::Font, font_size: FontSize) -> (Break, Scalar) {
+    fn next_break(text: &str, font: super::font::Chain, font_size: FontSize) -> (Break, Scalar) {
         let scale = super::pt_to_scale(font_size);
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
+        let mut advances = font.advances(text, scale);
         while let Some((byte_i, ch)) = char_indices.next() {
             // Check for a newline.
             if ch == '\r' {
@@ -1173,7 +2634,7 @@ pub mod line {
             }
 
             // Update the width.
-            width += advance_width(ch, font, scale, &mut last_glyph);
+            width += advances.next().unwrap_or(0.0);
             char_i += 1;
         }
         let break_ = Break::End {
@@ -1190,7 +2651,7 @@ pub mod line {
     /// Also returns the width of each line alongside the Break.
     fn next_break_by_character(
         text: &str,
-        font: &super::Font,
+        font: super::font::Chain,
         font_size: FontSize,
         max_width: Scalar,
     ) -> (Break, Scalar) {
@@ -1198,7 +2659,7 @@ pub mod line {
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
+        let mut advances = font.advances(text, scale);
         while let Some((byte_i, ch)) = char_indices.next() {
             // Check for a newline.
             if ch == '\r' {
@@ -1220,7 +2681,7 @@ pub mod line {
             }
 
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + advances.next().unwrap_or(0.0);
 
             // Check for a line wrap.
             if new_width > max_width {
@@ -1245,29 +2706,48 @@ pub mod line {
 
     /// Returns the next index at which the text will break by either:
     /// - A newline character.
-    /// - A line wrap at the beginning of the whitespace that precedes the first word
-    /// exceeding the `max_width`.
+    /// - A line wrap at the last line break opportunity before the `max_width` is exceeded.
     /// - A line wrap at the beginning of the first character exceeding the `max_width`,
-    /// if no whitespace appears for `max_width` characters.
+    /// if no break opportunity appears for `max_width` characters.
+    ///
+    /// Break opportunities are determined by the Unicode line breaking algorithm (UAX #14). A
+    /// whitespace character preceding the opportunity is consumed by the break.
     ///
     /// Also returns the width the line alongside the Break.
     fn next_break_by_whitespace(
         text: &str,
-        font: &super::Font,
+        font: super::font::Chain,
         font_size: FontSize,
         max_width: Scalar,
     ) -> (Break, Scalar) {
+        let scale = super::pt_to_scale(font_size);
+        break_by_whitespace(text, font.advances(text, scale), max_width)
+    }
+
+    /// Returns the next index at which the given `text` breaks as by `Infos::wrap_by_whitespace`,
+    /// where the advance of each of its characters is yielded in order by `advances`.
+    ///
+    /// This allows text whose characters are not all laid out with the same font and size to be
+    /// wrapped via `infos_wrapped_by`.
+    pub fn break_by_whitespace<I>(text: &str, advances: I, max_width: Scalar) -> (Break, Scalar)
+    where
+        I: IntoIterator<Item = Scalar>,
+    {
         struct Last {
             byte: usize,
             char: usize,
             width_before: Scalar,
         }
-        let scale = super::pt_to_scale(font_size);
-        let mut last_whitespace_start = None;
+        let mut last_break = None;
+        let mut last_whitespace = None;
         let mut width = 0.0;
         let mut char_i = 0;
         let mut char_indices = text.char_indices().peekable();
-        let mut last_glyph = None;
+        let mut advances = advances.into_iter();
+        let mut opportunities = unicode_linebreak::linebreaks(text)
+            .filter(|&(_, opportunity)| opportunity == BreakOpportunity::Allowed)
+            .map(|(byte, _)| byte)
+            .peekable();
         while let Some((byte_i, ch)) = char_indices.next() {
             // Check for a newline.
             if ch == '\r' {
@@ -1288,12 +2768,29 @@ pub mod line {
                 return (break_, width);
             }
 
+            // Check for a break opportunity before this character, breaking at the whitespace
+            // preceding it if there is any.
+            if opportunities.peek() == Some(&byte_i) {
+                opportunities.next();
+                last_break = match last_whitespace.take() {
+                    Some(whitespace) => Some(whitespace),
+                    None => Some((
+                        Last {
+                            byte: byte_i,
+                            char: char_i,
+                            width_before: width,
+                        },
+                        0,
+                    )),
+                };
+            }
+
             // Add the character's width to the width so far.
-            let new_width = width + advance_width(ch, font, scale, &mut last_glyph);
+            let new_width = width + advances.next().unwrap_or(0.0);
 
             // Check for a line wrap.
             if width > max_width {
-                match last_whitespace_start {
+                match last_break {
                     Some((
                         Last {
                             byte,
@@ -1320,17 +2817,19 @@ pub mod line {
                 }
             }
 
-            // Check for a new whitespace.
-            if ch.is_whitespace() {
-                last_whitespace_start = Some((
+            // Track the whitespace that directly precedes the next character.
+            last_whitespace = if ch.is_whitespace() {
+                Some((
                     Last {
                         byte: byte_i,
                         char: char_i,
                         width_before: width,
                     },
                     ch.len_utf8(),
-                ));
-            }
+                ))
+            } else {
+                None
+            };
 
             width = new_width;
             char_i += 1;
@@ -1344,15 +2843,19 @@ pub mod line {
     }
 
     /// Produce the width of the given line of text including spaces (i.e. ' ').
-    pub fn width(text: &str, font: &super::Font, font_size: FontSize) -> Scalar {
+    pub fn width<'a, C>(text: &str, font: C, font_size: FontSize) -> Scalar
+    where
+        C: Into<super::font::Chain<'a>>,
+    {
         let scale = super::Scale::uniform(super::pt_to_px(font_size));
         let point = super::rt::Point { x: 0.0, y: 0.0 };
 
         let mut total_w = 0.0;
-        for g in font.layout(text, scale, point) {
+        for (_, _, g) in font.into().layout(text, scale, point) {
             match g.pixel_bounding_box() {
                 Some(bb) => total_w = bb.max.x as f32,
-                None => total_w += g.unpositioned().h_metrics().advance_width,
+                // Measure from the caret so that tabs end at their stop.
+                None => total_w = g.position().x + g.unpositioned().h_metrics().advance_width,
             }
         }
 
@@ -1360,25 +2863,29 @@ pub mod line {
     }
 
     /// Produce an `Infos` iterator wrapped by the given `next_break_fn`.
-    pub fn infos_wrapped_by<'a, F>(
+    pub fn infos_wrapped_by<'a, C, F>(
         text: &'a str,
-        font: &'a super::Font,
+        font: C,
         font_size: FontSize,
         max_width: Scalar,
         next_break_fn: F,
     ) -> Infos<'a, F>
     where
-        F: for<'b> FnMut(&'b str, &'b super::Font, FontSize, Scalar) -> (Break, Scalar),
+        C: Into<super::font::Chain<'a>>,
+        F: for<'b> FnMut(&'b str, super::font::Chain<'b>, FontSize, Scalar) -> (Break, Scalar),
     {
         Infos {
             text: text,
-            font: font,
+            font: font.into(),
             font_size: font_size,
             max_width: max_width,
             next_break_fn: next_break_fn,
             start_byte: 0,
             start_char: 0,
             last_break: None,
+            direction: super::Direction::LeftToRight,
+            #[cfg(feature = "shaping")]
+            paragraph: ShapedParagraph::default(),
         }
     }
 
@@ -1386,14 +2893,13 @@ pub mod line {
     ///
     /// The produced `Infos` iterator will not wrap the text, and only break each line via newline
     /// characters within the text (either `\n` or `\r\n`).
-    pub fn infos<'a>(
-        text: &'a str,
-        font: &'a super::Font,
-        font_size: FontSize,
-    ) -> Infos<'a, NextBreakFnPtr> {
+    pub fn infos<'a, C>(text: &'a str, font: C, font_size: FontSize) -> Infos<'a, NextBreakFnPtr>
+    where
+        C: Into<super::font::Chain<'a>>,
+    {
         fn no_wrap(
             text: &str,
-            font: &super::Font,
+            font: super::font::Chain,
             font_size: FontSize,
             _max_width: Scalar,
         ) -> (Break, Scalar) {
@@ -1421,12 +2927,7 @@ pub mod line {
         let num_lines = infos.len();
         let first_rect = infos.next().map(|first_info| {
             // Calculate the `x` `Range` of the first line `Rect`.
-            let range = Range::new(0.0, first_info.width);
-            let x = match x_align {
-                super::Justify::Left => range.align_start_of(bounding_rect.x),
-                super::Justify::Center => range.align_middle_of(bounding_rect.x),
-                super::Justify::Right => range.align_end_of(bounding_rect.x),
-            };
+            let x = x_range(&first_info, x_align, bounding_rect.x);
 
             // Calculate the `y` `Range` of the first line `Rect`.
             let total_text_height = super::height(num_lines, font_size, line_spacing);
@@ -1446,6 +2947,7 @@ pub mod line {
             infos: infos,
             next: first_rect,
             x_align: x_align,
+            bounding_x: bounding_rect.x,
             line_spacing: line_spacing,
         }
     }
@@ -1456,15 +2958,16 @@ pub mod line {
     /// The yielded `Rect`s represent the selected range within each line of text.
     ///
     /// Lines that do not contain any selected text will be skipped.
-    pub fn selected_rects<'a, I>(
+    pub fn selected_rects<'a, I, C>(
         lines_with_rects: I,
-        font: &'a super::Font,
+        font: C,
         font_size: FontSize,
         start: super::cursor::Index,
         end: super::cursor::Index,
     ) -> SelectedRects<'a, I>
     where
         I: Iterator<Item = (&'a str, Rect)>,
+        C: Into<super::font::Chain<'a>>,
     {
         SelectedRects {
             selected_char_rects_per_line: super::glyph::selected_rects_per_line(
@@ -1474,12 +2977,200 @@ pub mod line {
                 start,
                 end,
             ),
+            line: None,
+        }
+    }
+
+    // The `x` `Range` occupied by the line with the given `info` within the `bounding_x` range.
+    fn x_range(info: &Info, x_align: super::Justify, bounding_x: Range) -> Range {
+        let range = Range::new(0.0, info.width);
+        match x_align {
+            super::Justify::Left => range.align_start_of(bounding_x),
+            super::Justify::Center => range.align_middle_of(bounding_x),
+            super::Justify::Right => range.align_end_of(bounding_x),
+            super::Justify::Full => match info.end_break {
+                // Only wrapped lines are stretched to fill the bounding range.
+                Break::Wrap { .. } if info.width < bounding_x.len() => bounding_x,
+                _ => range.align_start_of(bounding_x),
+            },
+        }
+    }
+
+    /// The width of the given line of text, measured in the same manner as the `width` of each
+    /// line `Info`.
+    pub fn natural_width<'a, C>(text: &str, font: C, font_size: FontSize) -> Scalar
+    where
+        C: Into<super::font::Chain<'a>>,
+    {
+        let font = font.into();
+        let scale = super::pt_to_scale(font_size);
+        font.advances(text, scale).sum()
+    }
+
+    impl VisualRun {
+        /// Whether or not the run is laid out from right to left.
+        pub fn is_right_to_left(&self) -> bool {
+            self.direction == super::Direction::RightToLeft
+        }
+    }
+
+    /// The direction of the paragraph beginning at the start of the given `text`.
+    ///
+    /// This is the direction of the paragraph's first strongly directional character, or
+    /// left-to-right if it has none.
+    pub fn base_direction(text: &str) -> super::Direction {
+        use unicode_bidi::BidiClass;
+        for ch in text.chars() {
+            match unicode_bidi::bidi_class(ch) {
+                BidiClass::L => return super::Direction::LeftToRight,
+                BidiClass::R | BidiClass::AL => return super::Direction::RightToLeft,
+                BidiClass::B => break,
+                _ => (),
+            }
+        }
+        super::Direction::LeftToRight
+    }
+
+    /// Divide the given `line` into runs of characters that share the same direction, ordered
+    /// from left to right using the Unicode Bidirectional Algorithm.
+    ///
+    /// The `direction` is that of the paragraph to which the line belongs. A left-to-right line
+    /// without any right-to-left characters produces a single run. An empty line produces no runs.
+    pub fn visual_runs(line: &str, direction: super::Direction) -> Vec<VisualRun> {
+        if line.is_empty() {
+            return Vec::new();
+        }
+        let is_rtl = |ch| {
+            use unicode_bidi::BidiClass::*;
+            matches!(unicode_bidi::bidi_class(ch), R | AL | AN | RLE | RLO | RLI)
+        };
+        if direction == super::Direction::LeftToRight && !line.chars().any(is_rtl) {
+            let run = VisualRun {
+                byte_range: 0..line.len(),
+                char_range: 0..line.chars().count(),
+                direction: direction,
+            };
+            return vec![run];
+        }
+
+        let level = match direction {
+            super::Direction::LeftToRight => unicode_bidi::Level::ltr(),
+            super::Direction::RightToLeft => unicode_bidi::Level::rtl(),
+        };
+        let info = unicode_bidi::BidiInfo::new(line, Some(level));
+        let char_index = |byte: usize| line[..byte].chars().count();
+        let mut runs = Vec::new();
+        for paragraph in &info.paragraphs {
+            let (levels, level_runs) = info.visual_runs(paragraph, paragraph.range.clone());
+            for byte_range in level_runs {
+                let direction = if levels[byte_range.start].is_rtl() {
+                    super::Direction::RightToLeft
+                } else {
+                    super::Direction::LeftToRight
+                };
+                runs.push(VisualRun {
+                    char_range: char_index(byte_range.start)..char_index(byte_range.end),
+                    byte_range: byte_range,
+                    direction: direction,
+                });
+            }
+        }
+        runs
+    }
+
+    /// The range occupied along the *x* axis by each character of the given `line`, in logical
+    /// order, when its visual `runs` are laid out from left to right starting at `left`.
+    ///
+    /// The extra `spacing` following each character is included within its range.
+    pub fn visual_char_ranges<'a, C>(
+        line: &str,
+        runs: &[VisualRun],
+        font: C,
+        font_size: FontSize,
+        left: Scalar,
+        spacing: WordSpacing,
+    ) -> Vec<Range>
+    where
+        C: Into<super::font::Chain<'a>>,
+    {
+        let font = font.into();
+        let scale = super::pt_to_scale(font_size);
+        let chars: Vec<char> = line.chars().collect();
+        let mut ranges = vec![Range::new(left, left); chars.len()];
+        let mut x = left;
+        for run in runs {
+            let advances: Vec<Scalar> = font
+                .advances(&line[run.byte_range.clone()], scale)
+                .collect();
+            let start = run.char_range.start;
+            let mut place = |i: usize| {
+                let char_i = start + i;
+                let w = advances[i] + spacing.after(char_i, chars[char_i]);
+                ranges[char_i] = Range::new(x, x + w);
+                x += w;
+            };
+            match run.direction {
+                super::Direction::LeftToRight => (0..advances.len()).for_each(&mut place),
+                super::Direction::RightToLeft => (0..advances.len()).rev().for_each(&mut place),
+            }
+        }
+        ranges
+    }
+
+    impl WordSpacing {
+        /// The spacing required for the given `line`, with the given natural `width`, to fill a
+        /// `Rect` of the given `line_width`.
+        pub fn new(line: &str, width: Scalar, line_width: Scalar) -> Self {
+            // Allow for imprecision in the alignment of unjustified lines.
+            const EPSILON: Scalar = 1e-6;
+            let none = WordSpacing {
+                per_gap: 0.0,
+                first_word_char: 0,
+                last_word_char: 0,
+            };
+            if line_width - width <= EPSILON {
+                return none;
+            }
+            let is_word = |&(_, ch): &(usize, char)| !ch.is_whitespace();
+            let mut words = line.chars().enumerate().filter(is_word).map(|(i, _)| i);
+            let first_word_char = match words.next() {
+                Some(i) => i,
+                None => return none,
+            };
+            let last_word_char = words.last().unwrap_or(first_word_char);
+            let spacing = WordSpacing {
+                per_gap: 1.0,
+                first_word_char: first_word_char,
+                last_word_char: last_word_char,
+            };
+            let gaps = line
+                .chars()
+                .enumerate()
+                .filter(|&(i, ch)| spacing.after(i, ch) > 0.0)
+                .count();
+            if gaps == 0 {
+                return none;
+            }
+            WordSpacing {
+                per_gap: (line_width - width) / gaps as Scalar,
+                ..spacing
+            }
+        }
+
+        /// The extra space following the given character at the given index within the line.
+        pub fn after(&self, char_index: usize, ch: char) -> Scalar {
+            let is_gap = self.first_word_char < char_index && char_index < self.last_word_char;
+            if is_gap && ch.is_whitespace() {
+                self.per_gap
+            } else {
+                0.0
+            }
         }
     }
 
     impl<'a, F> Iterator for Infos<'a, F>
     where
-        F: for<'b> FnMut(&'b str, &'b super::Font, FontSize, Scalar) -> (Break, Scalar),
+        F: for<'b> FnMut(&'b str, super::font::Chain<'b>, FontSize, Scalar) -> (Break, Scalar),
     {
         type Item = Info;
         fn next(&mut self) -> Option<Self::Item> {
@@ -1492,8 +3183,40 @@ pub mod line {
                 ref mut start_byte,
                 ref mut start_char,
                 ref mut last_break,
+                ref mut direction,
+                #[cfg(feature = "shaping")]
+                ref mut paragraph,
             } = *self;
 
+            // Each paragraph takes the direction of its first strongly directional character.
+            if let None | Some(Break::Newline { .. }) = *last_break {
+                *direction = base_direction(&text[*start_byte..]);
+            }
+
+            // Shape each paragraph once, rather than the remainder of it upon every line break.
+            #[cfg(feature = "shaping")]
+            let font = {
+                let scale = super::pt_to_scale(font_size);
+                if *start_byte < paragraph.start_byte || *start_byte >= paragraph.end_byte {
+                    let rest = &text[*start_byte..];
+                    let end = *start_byte + rest.find(['\r', '\n']).unwrap_or(rest.len());
+                    let line = &text[*start_byte..end];
+                    *paragraph = ShapedParagraph {
+                        start_byte: *start_byte,
+                        end_byte: end,
+                        start_char: *start_char,
+                        advances: font
+                            .shape(line, scale)
+                            .map(|line| line.advances)
+                            .unwrap_or_default(),
+                    };
+                }
+                let line = &text[*start_byte..paragraph.end_byte];
+                let skip = *start_char - paragraph.start_char;
+                let advances = paragraph.advances.get(skip..).unwrap_or(&[]);
+                font.with_shaped_advances(line, scale, advances)
+            };
+
             match next_break_fn(&text[*start_byte..], font, font_size, max_width) {
                 (next @ Break::Newline { .. }, width) | (next @ Break::Wrap { .. }, width) => {
                     let next_break = match next {
@@ -1523,6 +3246,7 @@ pub mod line {
                         start_char: *start_char,
                         end_break: next_break,
                         width: width,
+                        direction: *direction,
                     };
 
                     match next {
@@ -1568,6 +3292,7 @@ pub mod line {
                             start_char: *start_char,
                             end_break: end_break,
                             width: width,
+                            direction: *direction,
                         };
                         *start_byte = total_bytes;
                         *start_char = total_chars;
@@ -1591,6 +3316,7 @@ pub mod line {
                 ref mut next,
                 ref mut infos,
                 x_align,
+                bounding_x,
                 line_spacing,
             } = *self;
             next.map(|line_rect| {
@@ -1601,14 +3327,7 @@ pub mod line {
                         Range::from_pos_and_len(y, h)
                     };
 
-                    let x = {
-                        let range = Range::new(0.0, info.width);
-                        match x_align {
-                            super::Justify::Left => range.align_start_of(line_rect.x),
-                            super::Justify::Center => range.align_middle_of(line_rect.x),
-                            super::Justify::Right => range.align_end_of(line_rect.x),
-                        }
-                    };
+                    let x = x_range(&info, x_align, bounding_x);
 
                     Rect { x: x, y: y }
                 });
@@ -1624,16 +3343,342 @@ pub mod line {
     {
         type Item = Rect;
         fn next(&mut self) -> Option<Self::Item> {
-            while let Some(mut rects) = self.selected_char_rects_per_line.next() {
-                if let Some(first_rect) = rects.next() {
-                    let total_selected_rect = rects.fold(first_rect, |mut total, next| {
-                        total.x.end = next.x.end;
-                        total
-                    });
-                    return Some(total_selected_rect);
+            loop {
+                if let Some(ref mut rects) = self.line {
+                    if let Some(mut total) = rects.next() {
+                        // Join the rects of consecutive characters that are visually adjacent.
+                        while let Some(&next) = rects.peek() {
+                            if next.x.start == total.x.end {
+                                total.x.end = next.x.end;
+                            } else if next.x.end == total.x.start {
+                                total.x.start = next.x.start;
+                            } else {
+                                break;
+                            }
+                            rects.next();
+                        }
+                        return Some(total);
+                    }
+                }
+                let rects = self.selected_char_rects_per_line.next()?;
+                self.line = Some(rects.peekable());
+            }
+        }
+    }
+}
+
+/// A cache of text layouts that persists between frames.
+///
+/// Laying out text requires measuring every glyph within it, which becomes costly for screens
+/// displaying a lot of text. As text rarely changes between frames, the `Ui` retains the line
+/// `Info`s and positioned glyphs of recently laid out text within a `layout::Cache`, evicting the
+/// least recently used layouts once it is full.
+pub mod layout {
+    use fnv;
+    use position::{Align, Dimensions};
+    use std;
+    use std::hash::{Hash, Hasher};
+    use widget::primitive::text::Wrap;
+    use {FontSize, Scalar};
+
+    /// The number of layouts retained by a `Cache` constructed via `Cache::new`.
+    pub const DEFAULT_CAPACITY: usize = 1_024;
+
+    /// Identifies the layout of a string of text.
+    ///
+    /// The text itself is represented by its hash, while the `Cache` retains the text in order to
+    /// tell apart strings whose hashes collide.
+    #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+    pub struct Key {
+        text_hash: u64,
+        font_id: super::font::Id,
+        font_size: FontSize,
+        wrap: Option<(Wrap, u64)>,
+    }
+
+    /// Describes where the glyphs of a layout are placed within the window.
+    ///
+    /// Glyphs cached for one `Placement` may be reused for another that differs only by its
+    /// `origin`, in which case they are translated rather than laid out again.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Placement {
+        /// The top-left corner of the text's `Rect` in pixel coordinates.
+        pub origin: [f32; 2],
+        /// The dimensions of the text's `Rect`.
+        pub dim: Dimensions,
+        /// The alignment of each line along the *x* axis.
+        pub justify: super::Justify,
+        /// The alignment of the lines along the *y* axis.
+        pub y_align: Align,
+        /// The spacing between each line.
+        pub line_spacing: Scalar,
+        /// The factor by which the glyphs are scaled to pixels.
+        pub dpi_factor: f32,
+    }
+
+    /// A least-recently-used cache of text layouts, keyed by `Key`.
+    #[derive(Debug)]
+    pub struct Cache {
+        capacity: usize,
+        entries: std::collections::HashMap<Key, Entry, fnv::FnvBuildHasher>,
+        // Each entry's `Key` by the tick at which it was last used.
+        recency: std::collections::BTreeMap<u64, Key>,
+        tick: u64,
+    }
+
+    // Positioned glyphs shared with the `Text` primitives drawing them.
+    type Glyphs = std::sync::Arc<Vec<(super::font::Id, super::PositionedGlyph)>>;
+
+    #[derive(Debug)]
+    struct Entry {
+        text: String,
+        line_infos: Vec<super::line::Info>,
+        // The tab stops with which the `line_infos` were computed, if known.
+        tab_stops: Option<super::TabStops>,
+        // The `fingerprint` of the chain with which the `line_infos` were computed, if known.
+        chain: Option<u64>,
+        glyphs: Option<(Placement, Glyphs)>,
+        last_used: u64,
+    }
+
+    impl Key {
+        /// The key for `text` laid out with the given font and size.
+        ///
+        /// When `wrap` is given, the lines are wrapped to the given width.
+        pub fn new(
+            text: &str,
+            font_id: super::font::Id,
+            font_size: FontSize,
+            wrap: Option<(Wrap, Scalar)>,
+        ) -> Self {
+            let mut hasher = fnv::FnvHasher::default();
+            text.hash(&mut hasher);
+            Key {
+                text_hash: hasher.finish(),
+                font_id: font_id,
+                font_size: font_size,
+                wrap: wrap.map(|(wrap, width)| (wrap, width.to_bits())),
+            }
+        }
+
+        /// The `Id` of the font with which the text is laid out.
+        pub fn font_id(&self) -> super::font::Id {
+            self.font_id
+        }
+    }
+
+    impl Cache {
+        /// Construct an empty `Cache` retaining up to `DEFAULT_CAPACITY` layouts.
+        pub fn new() -> Self {
+            Self::with_capacity(DEFAULT_CAPACITY)
+        }
+
+        /// Construct an empty `Cache` retaining up to `capacity` layouts.
+        pub fn with_capacity(capacity: usize) -> Self {
+            Cache {
+                capacity: capacity,
+                entries: Default::default(),
+                recency: Default::default(),
+                tick: 0,
+            }
+        }
+
+        /// The maximum number of layouts retained by the cache.
+        pub fn capacity(&self) -> usize {
+            self.capacity
+        }
+
+        /// Change the maximum number of layouts retained, evicting the least recently used
+        /// layouts if there are now too many.
+        pub fn set_capacity(&mut self, capacity: usize) {
+            self.capacity = capacity;
+            self.evict(0);
+        }
+
+        /// The number of layouts currently retained.
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        /// Whether or not the cache retains no layouts.
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+
+        /// Whether or not the layout for `key` is retained.
+        pub fn contains(&self, key: &Key) -> bool {
+            self.entries.contains_key(key)
+        }
+
+        /// Remove all layouts from the cache.
+        pub fn clear(&mut self) {
+            self.entries.clear();
+            self.recency.clear();
+        }
+
+        /// The line `Info`s for the given `text` laid out as described by the `key`, which must
+        /// have been produced for the same `text`.
+        ///
+        /// The `font` should be the chain for the key's `font_id`. The infos are only computed if
+        /// they are not already cached for the same `TabStops`, fallbacks and shaping face.
+        pub fn line_infos(
+            &mut self,
+            key: Key,
+            text: &str,
+            font: super::font::Chain,
+        ) -> &[super::line::Info] {
+            let tab_stops = Some(font.tab_stops());
+            let chain = Some(fingerprint(font));
+            let exists = self.touch(key, text);
+            let stale =
+                |entry: &Entry| entry.tab_stops.as_ref() != tab_stops || entry.chain != chain;
+            if !exists || stale(&self.entries[&key]) {
+                let infos = super::line::infos(text, font, key.font_size);
+                let line_infos = match key.wrap {
+                    None => infos.collect(),
+                    Some((Wrap::Character, width)) => {
+                        infos.wrap_by_character(Scalar::from_bits(width)).collect()
+                    }
+                    Some((Wrap::Whitespace, width)) => {
+                        infos.wrap_by_whitespace(Scalar::from_bits(width)).collect()
+                    }
+                };
+                let tab_stops = tab_stops.cloned();
+                match self.entries.get_mut(&key) {
+                    Some(entry) if exists => {
+                        entry.line_infos = line_infos;
+                        entry.tab_stops = tab_stops;
+                        entry.chain = chain;
+                        entry.glyphs = None;
+                    }
+                    _ => self.insert(key, text, line_infos, tab_stops, chain),
+                }
+            }
+            &self.entries[&key].line_infos
+        }
+
+        /// The glyphs of the given `text` and its `line_infos`, positioned for the `placement`.
+        ///
+        /// The glyphs are only laid out via `layout` if they are not cached for the same lines and
+        /// a `placement` differing at most by its `origin`. The cached glyphs are shared rather
+        /// than copied.
+        pub fn positioned_glyphs<F>(
+            &mut self,
+            key: Key,
+            text: &str,
+            line_infos: &[super::line::Info],
+            placement: Placement,
+            layout: F,
+        ) -> std::sync::Arc<Vec<(super::font::Id, super::PositionedGlyph)>>
+        where
+            F: FnOnce() -> Vec<(super::font::Id, super::PositionedGlyph)>,
+        {
+            if !self.touch(key, text) {
+                self.insert(key, text, line_infos.to_vec(), None, None);
+            }
+            let entry = self.entries.get_mut(&key).expect("no entry for key");
+            if entry.line_infos[..] != *line_infos {
+                entry.line_infos = line_infos.to_vec();
+                entry.tab_stops = None;
+                entry.chain = None;
+                entry.glyphs = None;
+            }
+
+            // Reuse the cached glyphs, translating them if the text has moved.
+            if let Some((ref mut cached, ref mut glyphs)) = entry.glyphs {
+                let unmoved = Placement {
+                    origin: cached.origin,
+                    ..placement
+                };
+                if *cached == unmoved {
+                    let dx = placement.origin[0] - cached.origin[0];
+                    let dy = placement.origin[1] - cached.origin[1];
+                    if dx != 0.0 || dy != 0.0 {
+                        for &mut (_, ref mut g) in std::sync::Arc::make_mut(glyphs).iter_mut() {
+                            let p = g.position();
+                            let p = super::rt::point(p.x + dx, p.y + dy);
+                            *g = g.unpositioned().clone().positioned(p);
+                        }
+                        cached.origin = placement.origin;
+                    }
+                    return glyphs.clone();
+                }
+            }
+
+            let glyphs = std::sync::Arc::new(layout());
+            entry.glyphs = Some((placement, glyphs.clone()));
+            glyphs
+        }
+
+        // Mark the entry for `key` as the most recently used, returning whether or not it exists
+        // for the given `text`. An entry whose text merely shares the hash is removed.
+        fn touch(&mut self, key: Key, text: &str) -> bool {
+            self.tick += 1;
+            let tick = self.tick;
+            let last_used = match self.entries.get_mut(&key) {
+                Some(entry) if entry.text == text => std::mem::replace(&mut entry.last_used, tick),
+                Some(_) => {
+                    let entry = self.entries.remove(&key).unwrap();
+                    self.recency.remove(&entry.last_used);
+                    return false;
                 }
+                None => return false,
+            };
+            self.recency.remove(&last_used);
+            self.recency.insert(tick, key);
+            true
+        }
+
+        // Insert a new entry for `key`, evicting the least recently used entries to make room.
+        fn insert(
+            &mut self,
+            key: Key,
+            text: &str,
+            line_infos: Vec<super::line::Info>,
+            tab_stops: Option<super::TabStops>,
+            chain: Option<u64>,
+        ) {
+            self.evict(1);
+            let entry = Entry {
+                text: text.to_owned(),
+                line_infos: line_infos,
+                tab_stops: tab_stops,
+                chain: chain,
+                glyphs: None,
+                last_used: self.tick,
+            };
+            self.entries.insert(key, entry);
+            self.recency.insert(self.tick, key);
+        }
+
+        // Evict the least recently used entries until there is room for `additional` entries.
+        fn evict(&mut self, additional: usize) {
+            while !self.entries.is_empty() && self.entries.len() + additional > self.capacity {
+                let tick = match self.recency.keys().next() {
+                    Some(&tick) => tick,
+                    None => break,
+                };
+                let key = self.recency.remove(&tick).unwrap();
+                self.entries.remove(&key);
             }
-            None
         }
     }
+
+    impl Default for Cache {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Identifies the fonts other than the first that may lay out text with the given `font`,
+    // being its fallbacks and whether or not it is shaped.
+    fn fingerprint(font: super::font::Chain) -> u64 {
+        let mut hasher = fnv::FnvHasher::default();
+        for &(id, _) in font.fallbacks() {
+            id.hash(&mut hasher);
+        }
+        #[cfg(feature = "shaping")]
+        font.face().is_some().hash(&mut hasher);
+        hasher.finish()
+    }
 }
@@ -38,6 +38,10 @@ pub struct Theme {
     pub label_color: Color,
     /// The `Id` of the default font used for text widgets when one is not specified.
     pub font_id: Option<text::font::Id>,
+    /// The font family used for text widgets when no `font_id` is specified.
+    ///
+    /// The concrete font is selected from the family by the widget's weight and style.
+    pub font_family: Option<text::font::FamilyId>,
     /// A default "large" font size.
     pub font_size_large: u32,
     /// A default "medium" font size.
@@ -73,6 +77,21 @@ pub struct UniqueDefault<'a, T: 'a> {
     pub common: &'a widget::CommonStyle,
 }
 
+/// The options that a widget's style may specify for the font of some text.
+///
+/// Used by `Theme::resolve_font_id` to select the font.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FontOptions {
+    /// The id of the font, taking precedence over all other options.
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The family from which the font is selected by weight and style.
+    pub family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the font within its family.
+    pub weight: Option<text::font::Weight>,
+    /// The style of the font within its family.
+    pub style: Option<text::font::Style>,
+}
+
 impl WidgetDefault {
     /// Constructor for a WidgetDefault.
     pub fn new(style: Box<dyn Any + Send>) -> WidgetDefault {
@@ -97,6 +116,7 @@ impl Theme {
             border_width: 1.0,
             label_color: BLACK,
             font_id: None,
+            font_family: None,
             font_size_large: 26,
             font_size_medium: 18,
             font_size_small: 12,
@@ -106,6 +126,83 @@ impl Theme {
         }
     }
 
+    /// The font used for text when a widget specifies none.
+    ///
+    /// This is the theme's `font_id` if there is one, otherwise the regular font of the theme's
+    /// `font_family`, otherwise any font within the given `fonts`.
+    pub fn default_font_id(&self, fonts: &text::font::Map) -> Option<text::font::Id> {
+        use text::font::{Style, Weight};
+        self.font_id
+            .or_else(|| {
+                self.font_family
+                    .and_then(|family| fonts.select(family, Weight::NORMAL, Style::Normal))
+            })
+            .or_else(|| fonts.ids().next())
+    }
+
+    /// Select the font for some text given the font options specified for its widget, if any.
+    ///
+    /// The font is selected in the following order:
+    ///
+    /// 1. The given `font_id`.
+    /// 2. The font within the given `family` that best matches the weight and style.
+    /// 3. If a weight or style was given, the font within the theme's `font_family` that best
+    ///    matches them.
+    /// 4. The `default_font_id`, i.e. the theme's `font_id` followed by its `font_family`.
+    pub fn select_font_id(
+        &self,
+        fonts: &text::font::Map,
+        font_id: Option<text::font::Id>,
+        family: Option<text::font::FamilyId>,
+        weight: Option<text::font::Weight>,
+        style: Option<text::font::Style>,
+    ) -> Option<text::font::Id> {
+        let select = |family| {
+            fonts.select(
+                family,
+                weight.unwrap_or_default(),
+                style.unwrap_or_default(),
+            )
+        };
+        let weighted_or_styled = weight.is_some() || style.is_some();
+        font_id
+            .or_else(|| family.and_then(&select))
+            .or_else(|| match self.font_family {
+                Some(family) if weighted_or_styled => select(family),
+                _ => None,
+            })
+            .or_else(|| self.default_font_id(fonts))
+    }
+
+    /// Resolve the font for some text of a widget with the given `style`.
+    ///
+    /// Each of the `FontOptions` produced by `options` for the widget's `style` falls back to the
+    /// same option within the theme's default style for the widget, if any. The font is then
+    /// selected as described by `select_font_id`.
+    pub fn resolve_font_id<T, F>(
+        &self,
+        fonts: &text::font::Map,
+        style: &T,
+        options: F,
+    ) -> Option<text::font::Id>
+    where
+        T: widget::Style,
+        F: Fn(&T) -> FontOptions,
+    {
+        let given = options(style);
+        let default = self
+            .widget_style::<T>()
+            .map(|default| options(default.style))
+            .unwrap_or_default();
+        self.select_font_id(
+            fonts,
+            given.font_id.or(default.font_id).and_then(|id| id),
+            given.family.or(default.family).and_then(|family| family),
+            given.weight.or(default.weight),
+            given.style.or(default.style),
+        )
+    }
+
     /// Retrieve the unique default styling for a widget.
     ///
     /// Attempts to cast the `Box<WidgetStyle>` to the **Widget**'s unique associated style **T**.
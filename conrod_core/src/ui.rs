@@ -86,6 +86,10 @@ pub struct Ui {
     pending_scroll_events: Vec<event::Ui>,
     /// Mouse cursor
     mouse_cursor: cursor::MouseCursor,
+    /// The layouts of recently drawn text, retained between frames.
+    ///
+    /// This is borrowed mutably both while widgets are set and while primitives are drawn.
+    text_layouts: std::cell::RefCell<text::layout::Cache>,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
     /// Window width.
@@ -209,6 +213,7 @@ impl Ui {
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
             mouse_cursor: cursor::MouseCursor::Arrow,
+            text_layouts: std::cell::RefCell::new(text::layout::Cache::new()),
         }
     }
 
@@ -222,7 +227,8 @@ impl Ui {
             let bottom_edge = self.win_h / 2.0;
             Rect::from_xy_dim([right_edge, bottom_edge], [0.0, 0.0])
         });
-        input::Widget::for_widget(widget, rect, &self.global_input)
+        let transform = graph::algo::transform_of_widget(&self.widget_graph, widget);
+        input::Widget::for_transformed_widget(widget, rect, transform, &self.global_input)
     }
 
     /// The **Rect** for the widget at the given index.
@@ -295,6 +301,16 @@ impl Ui {
         &self.prev_updated_widgets
     }
 
+    /// Borrow the cache of text layouts shared by all widgets displaying text.
+    ///
+    /// Use this to adjust the cache's capacity, or to lay out text via the cache within custom
+    /// widgets.
+    ///
+    /// **Panics** if the cache is already borrowed.
+    pub fn text_layouts(&self) -> std::cell::RefMut<'_, text::layout::Cache> {
+        self.text_layouts.borrow_mut()
+    }
+
     /// Produces a type that may be used to generate new unique `widget::Id`s.
     ///
     /// See the [**widget::id::Generator**](../widget/id/struct.Generator.html) docs for details on
@@ -1214,6 +1230,7 @@ impl Ui {
             ref depth_order,
             ref theme,
             ref fonts,
+            ref text_layouts,
             win_w,
             win_h,
             ..
@@ -1229,6 +1246,7 @@ impl Ui {
         }
 
         render::Primitives::new(widget_graph, indices, theme, fonts, [win_w, win_h])
+            .with_text_layouts(text_layouts)
     }
 
     /// Same as the `Ui::draw` method, but *only* draws if the `redraw_count` is greater than 0.
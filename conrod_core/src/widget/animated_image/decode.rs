@@ -0,0 +1,323 @@
+//! Decoding of animated GIF and APNG images into frames of RGBA pixels.
+//!
+//! Each decoded frame is fully composited, i.e. it has the dimensions of the whole animation with
+//! the disposal and blending of all prior frames applied. The application should create a texture
+//! for each frame, insert it into its `image::Map` (or an `image::Atlas`) and pair the resulting
+//! `image::Id`s with the decoded delays to produce the `Frame`s of an `AnimatedImage`.
+//!
+//! GIF decoding requires the `gif` feature while APNG decoding requires the `apng` feature.
+
+#[cfg(feature = "gif")]
+use gif;
+#[cfg(feature = "apng")]
+use png;
+use std;
+use std::time::Duration;
+
+/// A fully composited frame of a decoded animation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedFrame {
+    /// The dimensions of the frame in pixels.
+    pub dimensions: [u32; 2],
+    /// The RGBA8 pixels of the frame, row by row from the top left.
+    pub rgba: Vec<u8>,
+    /// The duration for which the frame is displayed.
+    pub delay: Duration,
+}
+
+/// The errors that might occur while decoding an animation.
+#[derive(Debug)]
+pub enum Error {
+    /// The GIF could not be decoded.
+    #[cfg(feature = "gif")]
+    Gif(gif::DecodingError),
+    /// The PNG could not be decoded.
+    #[cfg(feature = "apng")]
+    Png(png::DecodingError),
+    /// The image contains no frames.
+    NoFrames,
+}
+
+/// GIF delays below this are commonly treated as `DEFAULT_GIF_DELAY` by browsers.
+const MIN_GIF_DELAY: u16 = 2;
+
+/// The delay used for GIF frames with a delay below two hundredths of a second.
+pub const DEFAULT_GIF_DELAY: Duration = Duration::from_millis(100);
+
+// The canvas onto which each frame is composited.
+struct Canvas {
+    dimensions: [u32; 2],
+    rgba: Vec<u8>,
+}
+
+// A rectangular region of the canvas in pixels, from the top left.
+#[derive(Copy, Clone)]
+struct Region {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+impl Canvas {
+    fn new([w, h]: [u32; 2]) -> Self {
+        Canvas {
+            dimensions: [w, h],
+            rgba: vec![0; w as usize * h as usize * 4],
+        }
+    }
+
+    // Call `f` with the canvas pixel and source pixel for each pixel of `region` within the
+    // canvas, given the region's `src` pixels row by row.
+    fn each_pixel<F>(&mut self, region: Region, src: &[u8], mut f: F)
+    where
+        F: FnMut(&mut [u8], &[u8]),
+    {
+        let [canvas_w, canvas_h] = self.dimensions;
+        for row in 0..region.h.min(canvas_h.saturating_sub(region.y)) {
+            for col in 0..region.w.min(canvas_w.saturating_sub(region.x)) {
+                let s = (row as usize * region.w as usize + col as usize) * 4;
+                let d =
+                    ((region.y + row) as usize * canvas_w as usize + (region.x + col) as usize) * 4;
+                f(&mut self.rgba[d..d + 4], &src[s..s + 4]);
+            }
+        }
+    }
+
+    // Clear the region to transparent black.
+    fn clear(&mut self, region: Region) {
+        let transparent = vec![0; region.w as usize * region.h as usize * 4];
+        self.each_pixel(region, &transparent, |dst, src| dst.copy_from_slice(src));
+    }
+
+    fn frame(&self, delay: Duration) -> DecodedFrame {
+        DecodedFrame {
+            dimensions: self.dimensions,
+            rgba: self.rgba.clone(),
+            delay: delay,
+        }
+    }
+}
+
+// Composite the `src` pixel over the `dst` pixel.
+#[cfg(feature = "apng")]
+fn blend_over(dst: &mut [u8], src: &[u8]) {
+    let src_a = src[3] as u32;
+    let dst_a = dst[3] as u32 * (255 - src_a) / 255;
+    let out_a = src_a + dst_a;
+    if out_a == 0 {
+        dst.copy_from_slice(&[0; 4]);
+        return;
+    }
+    for i in 0..3 {
+        dst[i] = ((src[i] as u32 * src_a + dst[i] as u32 * dst_a) / out_a) as u8;
+    }
+    dst[3] = out_a as u8;
+}
+
+/// Decode each frame of the given GIF.
+///
+/// Frames with a delay below two hundredths of a second are given the `DEFAULT_GIF_DELAY`, as is
+/// common among browsers.
+#[cfg(feature = "gif")]
+pub fn gif<R>(reader: R) -> Result<Vec<DecodedFrame>, Error>
+where
+    R: std::io::Read,
+{
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options.read_info(reader)?;
+    let mut canvas = Canvas::new([decoder.width() as u32, decoder.height() as u32]);
+    let mut frames = Vec::new();
+    while let Some(frame) = decoder.read_next_frame()? {
+        let region = Region {
+            x: frame.left as u32,
+            y: frame.top as u32,
+            w: frame.width as u32,
+            h: frame.height as u32,
+        };
+        let previous = match frame.dispose {
+            gif::DisposalMethod::Previous => Some(canvas.rgba.clone()),
+            _ => None,
+        };
+
+        // Transparent pixels leave the canvas untouched.
+        canvas.each_pixel(region, &frame.buffer, |dst, src| {
+            if src[3] != 0 {
+                dst.copy_from_slice(src);
+            }
+        });
+
+        let delay = match frame.delay {
+            d if d < MIN_GIF_DELAY => DEFAULT_GIF_DELAY,
+            d => Duration::from_millis(d as u64 * 10),
+        };
+        frames.push(canvas.frame(delay));
+
+        match frame.dispose {
+            gif::DisposalMethod::Background => canvas.clear(region),
+            gif::DisposalMethod::Previous => {
+                if let Some(previous) = previous {
+                    canvas.rgba = previous;
+                }
+            }
+            gif::DisposalMethod::Keep | gif::DisposalMethod::Any => (),
+        }
+    }
+    if frames.is_empty() {
+        return Err(Error::NoFrames);
+    }
+    Ok(frames)
+}
+
+/// Decode each frame of the given APNG.
+///
+/// A PNG that is not animated is decoded as a single frame with no delay.
+#[cfg(feature = "apng")]
+pub fn apng<R>(reader: R) -> Result<Vec<DecodedFrame>, Error>
+where
+    R: std::io::Read,
+{
+    let mut decoder = png::Decoder::new(reader);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let (_, mut reader) = decoder.read_info()?;
+    let (w, h) = (reader.info().width, reader.info().height);
+    let mut canvas = Canvas::new([w, h]);
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let mut rgba = Vec::new();
+
+    // The default image is only part of the animation if it has its own frame control.
+    let (num_frames, skip_default) = match reader.info().animation_control {
+        None => (1, false),
+        Some(animation) => {
+            let skip = reader.info().frame_control.is_none();
+            (animation.num_frames as usize + skip as usize, skip)
+        }
+    };
+
+    let mut frames = Vec::new();
+    for i in 0..num_frames {
+        reader.next_frame(&mut buffer)?;
+        let control = reader.info().frame_control;
+        let (color_type, _) = reader.output_color_type();
+        let region = match control {
+            Some(fc) => Region {
+                x: fc.x_offset,
+                y: fc.y_offset,
+                w: fc.width,
+                h: fc.height,
+            },
+            None => Region {
+                x: 0,
+                y: 0,
+                w: w,
+                h: h,
+            },
+        };
+        to_rgba(
+            color_type,
+            &buffer,
+            region.w as usize * region.h as usize,
+            &mut rgba,
+        );
+        if i == 0 && skip_default {
+            continue;
+        }
+
+        // Per the specification, disposing the first frame to the previous frame clears it.
+        let dispose = match control.map(|fc| fc.dispose_op) {
+            Some(png::DisposeOp::Previous) if frames.is_empty() => png::DisposeOp::Background,
+            Some(op) => op,
+            None => png::DisposeOp::None,
+        };
+        let previous = match dispose {
+            png::DisposeOp::Previous => Some(canvas.rgba.clone()),
+            _ => None,
+        };
+
+        match control.map(|fc| fc.blend_op) {
+            Some(png::BlendOp::Over) => canvas.each_pixel(region, &rgba, blend_over),
+            _ => canvas.each_pixel(region, &rgba, |dst, src| dst.copy_from_slice(src)),
+        }
+
+        let delay = match control {
+            Some(fc) if fc.delay_num > 0 => {
+                // A denominator of zero is treated as one hundredth of a second.
+                let den = if fc.delay_den == 0 { 100 } else { fc.delay_den };
+                Duration::from_nanos(fc.delay_num as u64 * 1_000_000_000 / den as u64)
+            }
+            _ => Duration::from_secs(0),
+        };
+        frames.push(canvas.frame(delay));
+
+        match dispose {
+            png::DisposeOp::Background => canvas.clear(region),
+            png::DisposeOp::Previous => {
+                if let Some(previous) = previous {
+                    canvas.rgba = previous;
+                }
+            }
+            png::DisposeOp::None => (),
+        }
+    }
+    if frames.is_empty() {
+        return Err(Error::NoFrames);
+    }
+    Ok(frames)
+}
+
+// Convert `len` expanded 8-bit pixels of the given color type to RGBA.
+#[cfg(feature = "apng")]
+fn to_rgba(color_type: png::ColorType, src: &[u8], len: usize, rgba: &mut Vec<u8>) {
+    rgba.clear();
+    let samples = color_type.samples();
+    for px in src[..len * samples].chunks(samples) {
+        match color_type {
+            // Palettes are expanded by the decoder, so indices are never produced.
+            png::ColorType::Grayscale | png::ColorType::Indexed => {
+                rgba.extend_from_slice(&[px[0], px[0], px[0], 255])
+            }
+            png::ColorType::GrayscaleAlpha => rgba.extend_from_slice(&[px[0], px[0], px[0], px[1]]),
+            png::ColorType::RGB => rgba.extend_from_slice(&[px[0], px[1], px[2], 255]),
+            png::ColorType::RGBA => rgba.extend_from_slice(px),
+        }
+    }
+}
+
+#[cfg(feature = "gif")]
+impl From<gif::DecodingError> for Error {
+    fn from(e: gif::DecodingError) -> Self {
+        Error::Gif(e)
+    }
+}
+
+#[cfg(feature = "apng")]
+impl From<png::DecodingError> for Error {
+    fn from(e: png::DecodingError) -> Self {
+        Error::Png(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            #[cfg(feature = "gif")]
+            Error::Gif(ref e) => Some(e),
+            #[cfg(feature = "apng")]
+            Error::Png(ref e) => Some(e),
+            Error::NoFrames => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            #[cfg(feature = "gif")]
+            Error::Gif(ref e) => std::fmt::Display::fmt(e, f),
+            #[cfg(feature = "apng")]
+            Error::Png(ref e) => std::fmt::Display::fmt(e, f),
+            Error::NoFrames => write!(f, "The image contains no frames."),
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//! A widget for playing a sequence of images, e.g. the frames of an animated GIF or APNG.
+//!
+//! Each `Frame` refers to an image within the user's `image::Map` along with the duration for
+//! which it should be displayed. The `AnimatedImage` keeps track of the time that it has spent
+//! playing and displays the frame that is due, only triggering a redraw when the displayed frame
+//! changes.
+//!
+//! Conrod only updates its widgets in response to input, so the `AnimatedImage` returns the time
+//! remaining until the next frame is due. The application should ensure the `Ui` is updated again
+//! within this time, e.g. by waking its event loop.
+//!
+//! With the `gif` or `apng` features enabled, the [decode](./decode/index.html) module provides
+//! functions for decoding animations into frames of RGBA pixels.
+
+use image;
+use std::time::Duration;
+use widget;
+use widget::primitive::image::Fit;
+use {Color, Colorable, Positionable, Sizeable, Widget};
+
+#[cfg(any(feature = "gif", feature = "apng"))]
+pub mod decode;
+
+/// Displays a sequence of images, each for its own duration.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct AnimatedImage<'a> {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The frames of the animation.
+    pub frames: &'a [Frame],
+    /// How the animation proceeds once the last frame has been displayed.
+    pub mode: Mode,
+    /// Whether or not the animation is playing.
+    pub playing: bool,
+    /// Unique styling.
+    pub style: Style,
+}
+
+/// A single frame of an `AnimatedImage`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    /// The unique identifier of the frame's image.
+    pub image_id: image::Id,
+    /// The duration for which the frame is displayed.
+    pub delay: Duration,
+}
+
+/// How an `AnimatedImage` proceeds once its last frame has been displayed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Start again from the first frame.
+    Loop,
+    /// Play the frames in reverse back to the first frame, then forwards again, and so on.
+    PingPong,
+    /// Stop at the last frame.
+    Once,
+}
+
+/// Unique styling for the `AnimatedImage` widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// Optionally specify a single color to use for each frame.
+    #[conrod(default = "None")]
+    pub maybe_color: Option<Option<Color>>,
+    /// How each frame is fitted to the widget's `Rect`.
+    #[conrod(default = "Fit::Stretch")]
+    pub fit: Option<Fit>,
+}
+
+widget_ids! {
+    struct Ids {
+        image,
+    }
+}
+
+/// Unique state for the `AnimatedImage` widget.
+pub struct State {
+    ids: Ids,
+    frames: Vec<Frame>,
+    // The time played before the animation was last paused.
+    played: Duration,
+    // The moment at which the animation was last resumed, if it is playing.
+    resumed: Option<instant::Instant>,
+}
+
+impl Frame {
+    /// A frame displaying the given image for the given duration.
+    pub fn new(image_id: image::Id, delay: Duration) -> Self {
+        Frame {
+            image_id: image_id,
+            delay: delay,
+        }
+    }
+}
+
+impl Mode {
+    /// Determine which of the given `frames` is displayed once the animation has been playing for
+    /// the given duration.
+    ///
+    /// Returns the index of the frame along with the time remaining until the next frame is due,
+    /// or `None` if the animation has finished. Returns `None` if there are no frames.
+    pub fn frame_at(
+        self,
+        frames: &[Frame],
+        elapsed: Duration,
+    ) -> Option<(usize, Option<Duration>)> {
+        let n = frames.len();
+        if n == 0 {
+            return None;
+        }
+
+        // The index of the frame at each step of a single cycle of the animation.
+        let (steps, turn) = match self {
+            Mode::PingPong => ((2 * n).saturating_sub(2).max(n), 2 * n - 2),
+            Mode::Loop | Mode::Once => (n, 0),
+        };
+        let frame_index = |step: usize| if step < n { step } else { turn - step };
+        let delay = |step: usize| frames[frame_index(step)].delay.as_nanos();
+
+        let cycle: u128 = (0..steps).map(delay).sum();
+        if cycle == 0 {
+            let last = match self {
+                Mode::Once => n - 1,
+                Mode::Loop | Mode::PingPong => 0,
+            };
+            return Some((last, None));
+        }
+
+        let elapsed = elapsed.as_nanos();
+        let mut t = match self {
+            Mode::Once if elapsed >= cycle => return Some((n - 1, None)),
+            Mode::Once => elapsed,
+            Mode::Loop | Mode::PingPong => elapsed % cycle,
+        };
+        for step in 0..steps {
+            let d = delay(step);
+            if t < d {
+                let remaining = Duration::from_nanos((d - t) as u64);
+                return Some((frame_index(step), Some(remaining)));
+            }
+            t -= d;
+        }
+        unreachable!("the elapsed time within the cycle is always less than its duration")
+    }
+}
+
+impl<'a> AnimatedImage<'a> {
+    /// Construct a new `AnimatedImage` that loops over the given frames.
+    pub fn new(frames: &'a [Frame]) -> Self {
+        AnimatedImage {
+            common: widget::CommonBuilder::default(),
+            frames: frames,
+            mode: Mode::Loop,
+            playing: true,
+            style: Style::default(),
+        }
+    }
+
+    /// Specify how the animation proceeds once the last frame has been displayed.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Play the frames forwards and then in reverse, repeatedly.
+    pub fn ping_pong(self) -> Self {
+        self.mode(Mode::PingPong)
+    }
+
+    /// Play the frames once, stopping at the last frame.
+    pub fn once(self) -> Self {
+        self.mode(Mode::Once)
+    }
+
+    /// Whether or not the animation is playing. When paused, the current frame remains displayed
+    /// and the animation resumes from the same point once playing again.
+    ///
+    /// By default, the animation is playing.
+    pub fn playing(mut self, playing: bool) -> Self {
+        self.playing = playing;
+        self
+    }
+
+    builder_methods! {
+        pub fit { style.fit = Some(Fit) }
+    }
+}
+
+impl<'a> Widget for AnimatedImage<'a> {
+    type State = State;
+    type Style = Style;
+    /// The time remaining until the next frame is due, or `None` if the animation is paused or
+    /// has finished.
+    ///
+    /// While `Some`, the application should ensure that the `Ui` is updated again within the
+    /// given duration so that the next frame is displayed on time.
+    type Event = Option<Duration>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            frames: Vec::new(),
+            played: Duration::from_secs(0),
+            resumed: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id,
+            state,
+            style,
+            rect,
+            ui,
+            ..
+        } = args;
+        let AnimatedImage {
+            frames,
+            mode,
+            playing,
+            ..
+        } = self;
+
+        // Start again from the first frame whenever the sequence changes.
+        if &state.frames[..] != frames {
+            state.update(|state| {
+                state.frames = frames.to_vec();
+                state.played = Duration::from_secs(0);
+                state.resumed = None;
+            });
+        }
+
+        // Only touch the state when pausing or resuming, so that the widget is only redrawn when
+        // the displayed frame changes.
+        let now = instant::Instant::now();
+        match (playing, state.resumed) {
+            (true, None) => state.update(|state| state.resumed = Some(now)),
+            (false, Some(resumed)) => state.update(|state| {
+                state.played += now.duration_since(resumed);
+                state.resumed = None;
+            }),
+            _ => (),
+        }
+        let elapsed = match state.resumed {
+            Some(resumed) => state.played + now.duration_since(resumed),
+            None => state.played,
+        };
+
+        let (frame_index, remaining) = mode.frame_at(frames, elapsed)?;
+        let (x, y, w, h) = rect.x_y_w_h();
+        let mut image = widget::Image::new(frames[frame_index].image_id)
+            .x_y(x, y)
+            .w_h(w, h)
+            .fit(style.fit(&ui.theme))
+            .parent(id)
+            .graphics_for(id);
+        image.style.maybe_color = Some(style.maybe_color(&ui.theme));
+        image.set(state.ids.image, ui);
+
+        if playing {
+            remaining
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> Colorable for AnimatedImage<'a> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(Some(color));
+        self
+    }
+}
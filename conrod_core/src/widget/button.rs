@@ -1,10 +1,14 @@
 //! The `Button` widget and related items.
 
 use image;
-use position::{self, Align, Rect, Scalar};
+use position::{self, Align, Padding, Rect, Scalar};
 use text;
+use theme::FontOptions;
 use widget;
-use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, UiCell, Widget};
+use {
+    Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Theme, UiCell,
+    Widget,
+};
 
 /// A pressable button widget whose reaction is triggered upon release.
 #[derive(Clone, WidgetCommon_)]
@@ -39,8 +43,19 @@ pub struct Style {
     #[conrod(default = "theme.font_size_medium")]
     pub label_font_size: Option<FontSize>,
     /// The ID of the font used to display the label.
-    #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// Whether or not the label may be selected and copied, in which case it receives its own
+    /// input rather than passing it to the Button.
+    #[conrod(default = "false")]
+    pub label_selectable: Option<bool>,
+    /// The font family from which the label's font is selected when no font id is given.
+    pub label_font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub label_font_weight: Option<text::font::Weight>,
+    /// The style of the label's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub label_font_style: Option<text::font::Style>,
     /// The label's typographic alignment over the *x* axis.
     #[conrod(default = "text::Justify::Center")]
     pub label_justify: Option<text::Justify>,
@@ -50,8 +65,20 @@ pub struct Style {
     /// The position of the title bar's `Label` widget over the *y* axis.
     #[conrod(default = "position::Relative::Align(Align::Middle)")]
     pub label_y: Option<position::Relative>,
+    /// The stroke drawn around the label's glyphs, if any.
+    #[conrod(default = "None")]
+    pub label_outline: Option<Option<widget::text::Outline>>,
+    /// The shadow drawn beneath the label's glyphs, if any.
+    #[conrod(default = "None")]
+    pub label_drop_shadow: Option<Option<widget::text::DropShadow>>,
+    /// The way in which the label should be truncated if it is wider than the button.
+    #[conrod(default = "None")]
+    pub label_truncate: Option<Option<widget::text::Truncate>>,
 }
 
+/// The padding between the edges of the button and a truncated label.
+const LABEL_PADDING: Scalar = 4.0;
+
 widget_ids! {
     /// Identifiers for a "flat" button.
     #[allow(missing_docs, missing_copy_implementations)]
@@ -66,6 +93,7 @@ widget_ids! {
     #[allow(missing_docs, missing_copy_implementations)]
     pub struct ImageIds {
         image,
+        nine_slice,
         label,
     }
 }
@@ -96,6 +124,10 @@ pub struct Image {
     pub color: ImageColor,
     /// The rectangular area of the original source image that should be displayed.
     pub src_rect: Option<Rect>,
+    /// If `Some`, the image is drawn as a `NineSlice` with the given insets and mode.
+    ///
+    /// This only applies when a `src_rect` is also given.
+    pub nine_slice: Option<(Padding, widget::nine_slice::Mode)>,
 }
 
 /// The coloring of the `Image`.
@@ -154,6 +186,7 @@ impl<'a> Button<'a, Image> {
             press_image_id: None,
             src_rect: None,
             color: ImageColor::None,
+            nine_slice: None,
         };
         Self::new_internal(image)
     }
@@ -166,6 +199,22 @@ impl<'a> Button<'a, Image> {
         self
     }
 
+    /// Draw the image as a `NineSlice`, keeping the given `insets` of the `source_rectangle`
+    /// unscaled while stretching the edges and centre.
+    ///
+    /// Has no effect unless a `source_rectangle` is also given.
+    pub fn nine_slice(self, insets: Padding) -> Self {
+        self.nine_slice_with_mode(insets, widget::nine_slice::Mode::Stretch)
+    }
+
+    /// Draw the image as a `NineSlice` using the given `insets` and `mode`.
+    ///
+    /// Has no effect unless a `source_rectangle` is also given.
+    pub fn nine_slice_with_mode(mut self, insets: Padding, mode: widget::nine_slice::Mode) -> Self {
+        self.show.nine_slice = Some((insets, mode));
+        self
+    }
+
     /// Map the `Image`'s luminance to the given color.
     pub fn image_color(mut self, color: Color) -> Self {
         self.show.color = ImageColor::Normal(color);
@@ -241,6 +290,14 @@ impl<'a, S> Button<'a, S> {
         self
     }
 
+    /// Allow the label to be selected and copied.
+    ///
+    /// A selectable label receives its own input rather than passing it to the Button.
+    pub fn label_selectable(mut self, selectable: bool) -> Self {
+        self.style.label_selectable = Some(selectable);
+        self
+    }
+
     /// Align the label to the left of the `Button`'s surface.
     pub fn left_justify_label(mut self) -> Self {
         self.style.label_justify = Some(text::Justify::Left);
@@ -273,8 +330,57 @@ impl<'a, S> Button<'a, S> {
         self
     }
 
+    /// Select the label's font from the given family.
+    pub fn label_font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.label_font_family = Some(Some(family));
+        self
+    }
+
+    /// Draw a stroke of the given width and color around the label's glyphs.
+    pub fn label_outline(mut self, width: Scalar, color: Color) -> Self {
+        self.style.label_outline = Some(Some(widget::text::Outline {
+            width: width,
+            color: color,
+        }));
+        self
+    }
+
+    /// Draw a copy of the label in the given color at the given offset beneath it.
+    pub fn label_drop_shadow(mut self, offset: position::Point, color: Color) -> Self {
+        self.style.label_drop_shadow = Some(Some(widget::text::DropShadow {
+            offset: offset,
+            color: color,
+        }));
+        self
+    }
+
+    /// Truncate the label with an ellipsis if it is wider than the button.
+    pub fn label_truncate(mut self, truncate: widget::text::Truncate) -> Self {
+        self.style.label_truncate = Some(Some(truncate));
+        self
+    }
+
     builder_methods! {
         pub enabled { enabled = bool }
+        pub label_font_weight { style.label_font_weight = Some(text::font::Weight) }
+        pub label_font_style { style.label_font_style = Some(text::font::Style) }
+    }
+}
+
+impl Style {
+    /// The id of the font used to display the label's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_label_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.label_font_id,
+            family: style.label_font_family,
+            weight: style.label_font_weight,
+            style: style.label_font_style,
+        })
     }
 }
 
@@ -320,7 +426,7 @@ impl<'a> Widget for Button<'a, Flat> {
 
         // Label widget.
         if let Some(l) = maybe_label {
-            label(id, state.label, l, style, ui);
+            label(id, state.label, rect, l, style, ui);
         }
 
         TimesClicked(times_triggered)
@@ -363,6 +469,7 @@ impl<'a> Widget for Button<'a, Image> {
             hover_image_id,
             src_rect,
             color,
+            nine_slice,
         } = show;
 
         // Determine the correct image to display.
@@ -373,13 +480,7 @@ impl<'a> Widget for Button<'a, Image> {
         };
 
         let (x, y, w, h) = rect.x_y_w_h();
-        let mut image = widget::Image::new(image_id)
-            .x_y(x, y)
-            .w_h(w, h)
-            .parent(id)
-            .graphics_for(id);
-        image.src_rect = src_rect;
-        image.style.maybe_color = match color {
+        let maybe_color = match color {
             ImageColor::Normal(color) => Some(Some(color)),
             ImageColor::WithFeedback(color) => ui
                 .widget_input(id)
@@ -394,10 +495,31 @@ impl<'a> Widget for Button<'a, Image> {
                 .or(Some(Some(color))),
             ImageColor::None => None,
         };
-        image.set(state.image, ui);
+        match (nine_slice, src_rect) {
+            (Some((insets, mode)), Some(src_rect)) => {
+                let mut nine_slice = widget::NineSlice::new(image_id, src_rect, insets)
+                    .mode(mode)
+                    .x_y(x, y)
+                    .w_h(w, h)
+                    .parent(id)
+                    .graphics_for(id);
+                nine_slice.style.maybe_color = maybe_color;
+                nine_slice.set(state.nine_slice, ui);
+            }
+            _ => {
+                let mut image = widget::Image::new(image_id)
+                    .x_y(x, y)
+                    .w_h(w, h)
+                    .parent(id)
+                    .graphics_for(id);
+                image.src_rect = src_rect;
+                image.style.maybe_color = maybe_color;
+                image.set(state.image, ui);
+            }
+        }
 
         if let Some(s) = maybe_label {
-            label(id, state.label, s, style, ui);
+            label(id, state.label, rect, s, style, ui);
         }
 
         TimesClicked(times_triggered)
@@ -458,20 +580,50 @@ fn bordered_rectangle(
         .set(rectangle_id, ui);
 }
 
-fn label(button_id: widget::Id, label_id: widget::Id, label: &str, style: &Style, ui: &mut UiCell) {
+fn label(
+    button_id: widget::Id,
+    label_id: widget::Id,
+    rect: Rect,
+    label: &str,
+    style: &Style,
+    ui: &mut UiCell,
+) {
     let color = style.label_color(&ui.theme);
     let font_size = style.label_font_size(&ui.theme);
     let x = style.label_x(&ui.theme);
     let y = style.label_y(&ui.theme);
     let justify = style.label_justify(&ui.theme);
-    let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+    let font_id = style.resolve_label_font_id(&ui.theme, &ui.fonts);
+    let outline = style.label_outline(&ui.theme);
+    let drop_shadow = style.label_drop_shadow(&ui.theme);
+    let maybe_truncate = style.label_truncate(&ui.theme);
+    // A truncated label must fit between the padded edges of the button, less any margin at
+    // which the label has been placed.
+    let truncated_w = {
+        let padding = style.border(&ui.theme) + LABEL_PADDING;
+        let margin = match x {
+            position::Relative::Place(position::Place::Start(Some(m)))
+            | position::Relative::Place(position::Place::End(Some(m))) => m,
+            _ => 0.0,
+        };
+        (rect.w() - padding * 2.0 - margin).max(0.0)
+    };
+    let selectable = style.label_selectable(&ui.theme);
     widget::Text::new(label)
         .and_then(font_id, widget::Text::font_id)
+        .and_mut(|text| {
+            text.style.outline = Some(outline);
+            text.style.drop_shadow = Some(drop_shadow);
+        })
+        .and_then(maybe_truncate, |text, truncate| {
+            text.truncate(truncate).w(truncated_w)
+        })
         .x_position_relative_to(button_id, x)
         .y_position_relative_to(button_id, y)
         .justify(justify)
         .parent(button_id)
-        .graphics_for(button_id)
+        .and_if(!selectable, |text| text.graphics_for(button_id))
+        .selectable(selectable)
         .color(color)
         .font_size(font_size)
         .set(label_id, ui);
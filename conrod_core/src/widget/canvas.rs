@@ -44,6 +44,7 @@ pub struct State {
 widget_ids! {
     struct Ids {
         rectangle,
+        skin,
         title_bar,
     }
 }
@@ -89,12 +90,21 @@ pub struct Style {
     /// The way in which the title bar's text should wrap.
     #[conrod(default = "Some(widget::text::Wrap::Whitespace)")]
     pub title_bar_maybe_wrap: Option<Option<widget::text::Wrap>>,
+    /// The way in which the title bar's text should be truncated if it exceeds the width.
+    #[conrod(default = "None")]
+    pub title_bar_maybe_truncate: Option<Option<widget::text::Truncate>>,
     /// The distance between lines for multi-line title bar text.
     #[conrod(default = "1.0")]
     pub title_bar_line_spacing: Option<Scalar>,
     /// The label's typographic alignment over the *x* axis.
     #[conrod(default = "text::Justify::Center")]
     pub title_bar_justify: Option<text::Justify>,
+    /// If `Some`, the image drawn as the background in place of the bordered rectangle.
+    #[conrod(default = "None")]
+    pub skin: Option<Option<widget::nine_slice::Skin>>,
+    /// If `Some`, the image drawn as the background of the `TitleBar` if it is visible.
+    #[conrod(default = "None")]
+    pub title_bar_skin: Option<Option<widget::nine_slice::Skin>>,
 }
 
 /// A series of **Canvas** splits along with their unique identifiers.
@@ -210,6 +220,19 @@ impl<'a> Canvas<'a> {
         self.style.title_bar_color = Some(Some(color));
         self
     }
+
+    /// Draw the given nine-slice image as the background in place of the bordered rectangle.
+    pub fn skin(mut self, skin: widget::nine_slice::Skin) -> Self {
+        self.style.skin = Some(Some(skin));
+        self
+    }
+
+    /// Draw the given nine-slice image as the background of the `Canvas`' `TitleBar` if it is
+    /// visible.
+    pub fn title_bar_skin(mut self, skin: widget::nine_slice::Skin) -> Self {
+        self.style.title_bar_skin = Some(Some(skin));
+        self
+    }
 }
 
 impl<'a> Widget for Canvas<'a> {
@@ -284,19 +307,33 @@ impl<'a> Widget for Canvas<'a> {
             ..
         } = self;
 
-        // BorderedRectangle widget as the rectangle backdrop.
+        // BorderedRectangle widget as the rectangle backdrop, or the skin if there is one.
         let dim = rect.dim();
         let color = style.color(ui.theme());
         let border = style.border(ui.theme());
         let border_color = style.border_color(ui.theme());
-        widget::BorderedRectangle::new(dim)
-            .color(color)
-            .border(border)
-            .border_color(border_color)
-            .middle_of(id)
-            .graphics_for(id)
-            .place_on_kid_area(false)
-            .set(state.ids.rectangle, &mut ui);
+        let background = match style.skin(ui.theme()) {
+            Some(skin) => {
+                widget::NineSlice::from_skin(skin)
+                    .wh(dim)
+                    .middle_of(id)
+                    .graphics_for(id)
+                    .place_on_kid_area(false)
+                    .set(state.ids.skin, &mut ui);
+                state.ids.skin
+            }
+            None => {
+                widget::BorderedRectangle::new(dim)
+                    .color(color)
+                    .border(border)
+                    .border_color(border_color)
+                    .middle_of(id)
+                    .graphics_for(id)
+                    .place_on_kid_area(false)
+                    .set(state.ids.rectangle, &mut ui);
+                state.ids.rectangle
+            }
+        };
 
         // TitleBar widget if we were given some label.
         if let Some(label) = maybe_title_bar_label {
@@ -306,10 +343,14 @@ impl<'a> Widget for Canvas<'a> {
             let justify = style.title_bar_justify(&ui.theme);
             let line_spacing = style.title_bar_line_spacing(&ui.theme);
             let maybe_wrap = style.title_bar_maybe_wrap(&ui.theme);
-            widget::TitleBar::new(label, state.ids.rectangle)
+            let maybe_truncate = style.title_bar_maybe_truncate(&ui.theme);
+            let title_bar_skin = style.title_bar_skin(&ui.theme);
+            widget::TitleBar::new(label, background)
                 .and_mut(|title_bar| {
                     title_bar.style.maybe_wrap = Some(maybe_wrap);
+                    title_bar.style.maybe_truncate = Some(maybe_truncate);
                     title_bar.style.justify = Some(justify);
+                    title_bar.style.skin = Some(title_bar_skin);
                 })
                 .color(color)
                 .border(border)
@@ -3,8 +3,9 @@
 use position;
 use std;
 use text;
+use theme::FontOptions;
 use widget;
-use {Borderable, Colorable, Labelable, Positionable, Sizeable, Widget};
+use {Borderable, Colorable, Labelable, Positionable, Sizeable, Theme, Widget};
 use {Color, FontSize, Scalar, UiCell};
 
 /// A vertically collapsible area.
@@ -53,8 +54,15 @@ pub struct Style {
     #[conrod(default = "None")]
     pub label_font_size: Option<Option<FontSize>>,
     /// The ID of the font used to display the label.
-    #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the label's font is selected when no font id is given.
+    pub label_font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub label_font_weight: Option<text::font::Weight>,
+    /// The style of the label's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub label_font_style: Option<text::font::Style>,
 }
 
 /// The event returned when the text bar or triangle is pressed.
@@ -105,6 +113,41 @@ impl<'a> CollapsibleArea<'a> {
         self.style.label_font_id = Some(Some(font_id));
         self
     }
+
+    /// Select the label's font from the given family.
+    pub fn label_font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.label_font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the label's font within its family.
+    pub fn label_font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.label_font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the label's font within its family.
+    pub fn label_font_style(mut self, style: text::font::Style) -> Self {
+        self.style.label_font_style = Some(style);
+        self
+    }
+}
+
+impl Style {
+    /// The id of the font used to display the label's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_label_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.label_font_id,
+            family: style.label_font_family,
+            weight: style.label_font_weight,
+            style: style.label_font_style,
+        })
+    }
 }
 
 impl<'a> Widget for CollapsibleArea<'a> {
@@ -140,7 +183,7 @@ impl<'a> Widget for CollapsibleArea<'a> {
         let border = style.border(&ui.theme);
         let border_color = style.border_color(&ui.theme);
         let label_color = style.label_color(&ui.theme);
-        let label_font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        let label_font_id = style.resolve_label_font_id(&ui.theme, &ui.fonts);
         let label_font_size = match style.label_font_size(&ui.theme) {
             Some(font_size) => font_size,
             None => std::cmp::max((h / 2.5) as FontSize, 10),
@@ -0,0 +1,160 @@
+//! Types and functions related to cropping a widget's children to a non-rectangular shape.
+//!
+//! By default, a widget that crops its children does so to the axis-aligned `Rect` of its
+//! `kid_area`. The `Shape` type allows for describing other convex shapes within the `kid_area`.
+//! Cropping to these shapes is realised geometrically by the `mesh::Mesh` type, which clips all
+//! triangles against the shape before they are uploaded to the GPU.
+
+use widget;
+use {Point, Rect, Scalar};
+
+/// A function that produces the points of a convex polygon for the given `kid_area`.
+///
+/// The points may be given in either clockwise or counter-clockwise order.
+pub type PolygonFn = fn(Rect, &mut Vec<Point>);
+
+/// The shape to which the children of a widget are cropped.
+#[derive(Copy, Clone, Debug)]
+pub enum Shape {
+    /// Crop to the `kid_area` rectangle.
+    Rectangle,
+    /// Crop to the `kid_area` with each corner rounded.
+    RoundedRectangle {
+        /// The radius of the corner circles.
+        radius: Scalar,
+        /// The number of lines used to describe each corner.
+        corner_resolution: usize,
+    },
+    /// Crop to the oval that fits the `kid_area`, or a circle when the `kid_area` is square.
+    Oval {
+        /// The number of lines used to describe the circumference.
+        resolution: usize,
+    },
+    /// Crop to the convex polygon produced by the given function.
+    Polygon(PolygonFn),
+}
+
+impl Shape {
+    /// A rounded rectangle with the given corner `radius` and the default corner resolution.
+    pub fn rounded_rectangle(radius: Scalar) -> Self {
+        Shape::RoundedRectangle {
+            radius,
+            corner_resolution: widget::rounded_rectangle::DEFAULT_CORNER_RESOLUTION,
+        }
+    }
+
+    /// An oval with the default resolution.
+    pub fn oval() -> Self {
+        Shape::Oval {
+            resolution: widget::oval::DEFAULT_RESOLUTION,
+        }
+    }
+
+    /// Fill `points` with the counter-clockwise outline of the shape within the given `rect`.
+    pub fn points(&self, rect: Rect, points: &mut Vec<Point>) {
+        points.clear();
+        match *self {
+            Shape::Rectangle => {
+                let (l, r, b, t) = rect.l_r_b_t();
+                points.extend_from_slice(&[[l, b], [r, b], [r, t], [l, t]]);
+            }
+            Shape::RoundedRectangle {
+                radius,
+                corner_resolution,
+            } => {
+                let radius = radius.min(rect.w() / 2.0).min(rect.h() / 2.0).max(0.0);
+                points.extend(widget::rounded_rectangle::points(
+                    rect,
+                    radius,
+                    corner_resolution,
+                ));
+            }
+            Shape::Oval { resolution } => {
+                points.extend(widget::oval::circumference(rect, resolution));
+                // The circumference ends where it began.
+                points.pop();
+            }
+            Shape::Polygon(f) => {
+                f(rect, points);
+                if signed_area(points) < 0.0 {
+                    points.reverse();
+                }
+            }
+        }
+    }
+}
+
+/// Twice the signed area of the given polygon.
+///
+/// The result is positive for counter-clockwise polygons and negative for clockwise polygons.
+pub fn signed_area(polygon: &[Point]) -> Scalar {
+    let mut area = 0.0;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area
+}
+
+/// Clip the `subject` polygon against the convex `clip` polygon, writing the result to `output`.
+///
+/// The `clip` polygon may be given in either winding order. `position` retrieves the position of
+/// a vertex and `lerp` produces the vertex at the given fraction between two vertices, allowing
+/// additional vertex attributes like colors or texture coordinates to be interpolated.
+///
+/// The output is empty if the polygons do not intersect.
+pub fn clip_convex<V, P, L>(
+    subject: &[V],
+    clip: &[Point],
+    position: P,
+    lerp: L,
+    output: &mut Vec<V>,
+) where
+    V: Clone,
+    P: Fn(&V) -> Point,
+    L: Fn(&V, &V, Scalar) -> V,
+{
+    output.clear();
+    output.extend_from_slice(subject);
+    if clip.len() < 3 {
+        output.clear();
+        return;
+    }
+    let winding = signed_area(clip).signum();
+    let mut input = Vec::with_capacity(subject.len());
+    for (i, &a) in clip.iter().enumerate() {
+        if output.is_empty() {
+            return;
+        }
+        let b = clip[(i + 1) % clip.len()];
+        // Positive on the inner side of the edge `a -> b`.
+        let side =
+            |p: Point| winding * ((b[0] - a[0]) * (p[1] - a[1]) - (b[1] - a[1]) * (p[0] - a[0]));
+        std::mem::swap(&mut input, output);
+        output.clear();
+        for (j, current) in input.iter().enumerate() {
+            let previous = &input[(j + input.len() - 1) % input.len()];
+            let (d_previous, d_current) = (side(position(previous)), side(position(current)));
+            if d_current >= 0.0 {
+                if d_previous < 0.0 {
+                    let t = d_previous / (d_previous - d_current);
+                    output.push(lerp(previous, current, t));
+                }
+                output.push(current.clone());
+            } else if d_previous >= 0.0 {
+                let t = d_previous / (d_previous - d_current);
+                output.push(lerp(previous, current, t));
+            }
+        }
+    }
+    if output.len() < 3 {
+        output.clear();
+    }
+}
+
+/// Produces the intersection of the `subject` polygon and the convex `clip` polygon.
+pub fn intersect(subject: &[Point], clip: &[Point], output: &mut Vec<Point>) {
+    let lerp =
+        |a: &Point, b: &Point, t: Scalar| [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t];
+    clip_convex(subject, clip, |p| *p, lerp, output);
+}
@@ -62,8 +62,18 @@ pub struct Style {
     #[conrod(default = "None")]
     pub scrollbar_width: Option<Option<Scalar>>,
     /// The ID of the font used to display the labels.
-    #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the label's font is selected when no font id is given.
+    pub label_font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub label_font_weight: Option<text::font::Weight>,
+    /// The style of the label's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub label_font_style: Option<text::font::Style>,
+    /// The way in which labels should be truncated if they are wider than their `Button`s.
+    #[conrod(default = "None")]
+    pub label_truncate: Option<Option<widget::text::Truncate>>,
 }
 
 widget_ids! {
@@ -158,6 +168,24 @@ impl<'a, T> DropDownList<'a, T> {
         self
     }
 
+    /// Select the label's font from the given family.
+    pub fn label_font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.label_font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the label's font within its family.
+    pub fn label_font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.label_font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the label's font within its family.
+    pub fn label_font_style(mut self, style: text::font::Style) -> Self {
+        self.style.label_font_style = Some(style);
+        self
+    }
+
     /// Align the labels to the left of their `Button`s' surface.
     pub fn left_justify_label(mut self) -> Self {
         self.style.label_justify = Some(text::Justify::Left);
@@ -187,6 +215,12 @@ impl<'a, T> DropDownList<'a, T> {
         self.style.label_y = Some(y);
         self
     }
+
+    /// Truncate labels with an ellipsis if they are wider than their `Button`s.
+    pub fn label_truncate(mut self, truncate: widget::text::Truncate) -> Self {
+        self.style.label_truncate = Some(Some(truncate));
+        self
+    }
 }
 
 impl<'a, T> Widget for DropDownList<'a, T>
@@ -382,6 +416,13 @@ impl Style {
             label_x: self.label_x,
             label_y: self.label_y,
             label_font_id: self.label_font_id,
+            label_truncate: self.label_truncate,
+            label_font_family: self.label_font_family,
+            label_font_weight: self.label_font_weight,
+            label_font_style: self.label_font_style,
+            label_outline: None,
+            label_drop_shadow: None,
+            label_selectable: None,
         }
     }
 }
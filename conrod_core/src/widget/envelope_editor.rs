@@ -4,9 +4,10 @@ use num::Float;
 use position::{Direction, Edge, Point, Rect, Scalar};
 use std;
 use text;
+use theme::FontOptions;
 use utils::{clamp, map_range, percentage, val_to_string};
 use widget;
-use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Widget};
+use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Theme, Widget};
 
 /// Used for editing a series of 2D Points on a cartesian (X, Y) plane within some given range.
 ///
@@ -60,8 +61,19 @@ pub struct Style {
     #[conrod(default = "2.0")]
     pub line_thickness: Option<Scalar>,
     /// The ID of the font used to display the label.
-    #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the label's font is selected when no font id is given.
+    pub label_font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub label_font_weight: Option<text::font::Weight>,
+    /// The style of the label's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub label_font_style: Option<text::font::Style>,
+    /// Whether or not the label may be selected and copied, in which case it receives its own
+    /// input rather than passing it to the EnvelopeEditor.
+    #[conrod(default = "false")]
+    pub label_selectable: Option<bool>,
 }
 
 widget_ids! {
@@ -156,6 +168,32 @@ where
         self
     }
 
+    /// Select the label's font from the given family.
+    pub fn label_font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.label_font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the label's font within its family.
+    pub fn label_font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.label_font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the label's font within its family.
+    pub fn label_font_style(mut self, style: text::font::Style) -> Self {
+        self.style.label_font_style = Some(style);
+        self
+    }
+
+    /// Allow the label to be selected and copied.
+    ///
+    /// A selectable label receives its own input rather than passing it to the EnvelopeEditor.
+    pub fn label_selectable(mut self, selectable: bool) -> Self {
+        self.style.label_selectable = Some(selectable);
+        self
+    }
+
     builder_methods! {
         pub point_radius { style.point_radius = Some(Scalar) }
         pub line_thickness { style.line_thickness = Some(Scalar) }
@@ -244,6 +282,23 @@ where
     }
 }
 
+impl Style {
+    /// The id of the font used to display the label's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_label_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.label_font_id,
+            family: style.label_font_family,
+            weight: style.label_font_weight,
+            style: style.label_font_style,
+        })
+    }
+}
+
 impl<'a, E> Widget for EnvelopeEditor<'a, E>
 where
     E: EnvelopePoint,
@@ -525,14 +580,16 @@ where
             .border_color(border_color)
             .set(state.ids.rectangle, ui);
 
-        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        let font_id = style.resolve_label_font_id(&ui.theme, &ui.fonts);
         let label_color = style.label_color(&ui.theme);
         if let Some(label) = maybe_label {
             let font_size = style.label_font_size(&ui.theme);
+            let selectable = style.label_selectable(&ui.theme);
             widget::Text::new(label)
                 .and_then(font_id, widget::Text::font_id)
                 .middle_of(state.ids.rectangle)
-                .graphics_for(id)
+                .and_if(!selectable, |text| text.graphics_for(id))
+                .selectable(selectable)
                 .color(label_color)
                 .font_size(font_size)
                 .set(state.ids.label, ui);
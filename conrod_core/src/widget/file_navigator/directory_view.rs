@@ -340,6 +340,7 @@ impl<'a> Widget for DirectoryView<'a> {
                         .label_color(text_color)
                         .label_font_size(font_size)
                         .label_x(Relative::Place(Place::Start(Some(font_size as Scalar))))
+                        .label_truncate(widget::text::Truncate::Middle)
                         .left_justify_label();
                     item.set(button, ui);
                 }
@@ -6,7 +6,7 @@
 use graph::{Container, UniqueWidgetState};
 use position::{
     Align, Depth, Dimension, Dimensions, Padding, Point, Position, Positionable, Rect, Relative,
-    Sizeable,
+    Scalar, Sizeable,
 };
 use std;
 use text::font;
@@ -18,13 +18,16 @@ pub use self::id::Id;
 pub use self::primitive::image::{self, Image};
 pub use self::primitive::line::{self, Line};
 pub use self::primitive::point_path::{self, PointPath};
+pub use self::primitive::shadow::{self, Shadow};
 pub use self::primitive::shape::circle::{self, Circle};
 pub use self::primitive::shape::oval::{self, Oval};
 pub use self::primitive::shape::polygon::{self, Polygon};
 pub use self::primitive::shape::rectangle::{self, Rectangle};
 pub use self::primitive::shape::triangles::{self, Triangles};
+pub use self::primitive::svg::{self, Svg};
 pub use self::primitive::text::{self, Text};
 
+pub use self::animated_image::AnimatedImage;
 pub use self::bordered_rectangle::BorderedRectangle;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
@@ -37,9 +40,11 @@ pub use self::grid::Grid;
 pub use self::list::List;
 pub use self::list_select::ListSelect;
 pub use self::matrix::Matrix;
+pub use self::nine_slice::NineSlice;
 pub use self::number_dialer::NumberDialer;
 pub use self::plot_path::PlotPath;
 pub use self::range_slider::RangeSlider;
+pub use self::rich_text::RichText;
 pub use self::rounded_rectangle::RoundedRectangle;
 pub use self::scrollbar::Scrollbar;
 pub use self::slider::Slider;
@@ -57,6 +62,7 @@ mod builder;
 // Widget functionality modules.
 #[macro_use]
 pub mod id;
+pub mod crop;
 pub mod scroll;
 
 // Primitive widget modules.
@@ -64,6 +70,7 @@ pub mod primitive;
 
 // Widget modules.
 
+pub mod animated_image;
 pub mod bordered_rectangle;
 pub mod button;
 pub mod canvas;
@@ -76,9 +83,11 @@ pub mod grid;
 pub mod list;
 pub mod list_select;
 pub mod matrix;
+pub mod nine_slice;
 pub mod number_dialer;
 pub mod plot_path;
 pub mod range_slider;
+pub mod rich_text;
 pub mod rounded_rectangle;
 pub mod scrollbar;
 pub mod slider;
@@ -214,6 +223,14 @@ pub struct CommonBuilder {
     /// By default, the kid_area is the size of the entire widget, though it may be specified
     /// otherwise via the `Widget::kid_area` method.
     pub crop_kids: bool,
+    /// The shape within the `kid_area` to which the children are cropped if `crop_kids` is true.
+    pub crop_shape: crop::Shape,
+    /// The opacity of the widget and all of its descendants, where `1.0` is fully opaque.
+    pub opacity: f32,
+    /// The rotation in radians applied to the widget and all of its descendants.
+    pub rotation: Scalar,
+    /// The scale along the *x* and *y* axes applied to the widget and all of its descendants.
+    pub scale: [Scalar; 2],
     /// Arguments to the scrolling of the widget's *x* axis.
     pub maybe_x_scroll: Option<scroll::Scroll>,
     /// Arguments to the scrolling of the widget's *y* axis.
@@ -325,6 +342,14 @@ pub struct PreUpdateCache {
     pub maybe_floating: Option<Floating>,
     /// Whether or not the children of the **Widget** should be cropped to its `kid_area`.
     pub crop_kids: bool,
+    /// The shape within the `kid_area` to which the children of the **Widget** are cropped.
+    pub crop_shape: crop::Shape,
+    /// The opacity of the **Widget**, not including the opacity of its parents.
+    pub opacity: f32,
+    /// The rotation of the **Widget** in radians, not including the rotation of its parents.
+    pub rotation: Scalar,
+    /// The scale of the **Widget**, not including the scale of its parents.
+    pub scale: [Scalar; 2],
     /// Scrolling data for the **Widget**'s *x* axis if there is some.
     pub maybe_x_scroll_state: Option<scroll::StateX>,
     /// Scrolling data for the **Widget**'s *y* axis if there is some.
@@ -781,6 +806,56 @@ pub trait Widget: Common + Sized {
         self
     }
 
+    /// Indicates that all widgets who are children of this widget should be cropped to the given
+    /// `shape` within the `kid_area` of this widget.
+    ///
+    /// Cropping to shapes other than `crop::Shape::Rectangle` is performed geometrically by the
+    /// `mesh::Mesh` type and by the glium and gfx backends. Other backends, such as piston, may
+    /// only crop to the bounding rectangle of the shape.
+    fn crop_kids_to(mut self, shape: crop::Shape) -> Self {
+        self.common_mut().crop_shape = shape;
+        self.crop_kids()
+    }
+
+    /// Set the opacity of the widget and all of its descendants (the default is `1.0`).
+    ///
+    /// The alpha of every primitive produced by the widget and its descendants is multiplied by
+    /// the given opacity. Nested opacities compose, e.g. a widget with an opacity of `0.5` within
+    /// a parent with an opacity of `0.5` is drawn with an opacity of `0.25`.
+    fn opacity(mut self, opacity: f32) -> Self {
+        self.common_mut().opacity = opacity;
+        self
+    }
+
+    /// Rotate the widget and all of its descendants by the given angle in radians.
+    ///
+    /// Rotation is counter-clockwise and pivots about the centre of the widget's `Rect`. The
+    /// widget's `Rect` itself is not affected - the rotation is only applied when rendering and
+    /// when picking the widget under the mouse. Nested rotations compose.
+    ///
+    /// The transform is applied to the vertices produced by the `mesh::Mesh` type. Backends that
+    /// do not use the `Mesh` must apply the `render::Primitive`'s `transform` themselves.
+    fn rotate(mut self, radians: Scalar) -> Self {
+        self.common_mut().rotation = radians;
+        self
+    }
+
+    /// Uniformly scale the widget and all of its descendants by the given factor.
+    ///
+    /// See `scale_x_y` for details.
+    fn scale(self, factor: Scalar) -> Self {
+        self.scale_x_y(factor, factor)
+    }
+
+    /// Scale the widget and all of its descendants by the given factors along each axis.
+    ///
+    /// Scaling pivots about the centre of the widget's `Rect`. Like `rotate`, the scale is only
+    /// applied when rendering and when picking the widget under the mouse. Nested scales compose.
+    fn scale_x_y(mut self, x: Scalar, y: Scalar) -> Self {
+        self.common_mut().scale = [x, y];
+        self
+    }
+
     /// Makes the widget's `KidArea` scrollable.
     ///
     /// If a widget is scrollable and it has children widgets that fall outside of its `KidArea`,
@@ -1148,6 +1223,10 @@ where
 
         // Retrieve whether or not the widget's children should be cropped to it.
         let crop_kids = widget.common().crop_kids;
+        let crop_shape = widget.common().crop_shape;
+        let opacity = widget.common().opacity;
+        let rotation = widget.common().rotation;
+        let scale = widget.common().scale;
 
         // This will cache the given data into the `ui`'s `widget_graph`.
         let ui: &mut Ui = ui::ref_mut_from_ui_cell(ui);
@@ -1165,6 +1244,10 @@ where
                 maybe_dragged_from: maybe_dragged_from,
                 maybe_floating: maybe_floating,
                 crop_kids: crop_kids,
+                crop_shape: crop_shape,
+                opacity: opacity,
+                rotation: rotation,
+                scale: scale,
                 maybe_y_scroll_state: maybe_y_scroll_state,
                 maybe_x_scroll_state: maybe_x_scroll_state,
                 maybe_graphics_for: widget.common().maybe_graphics_for,
@@ -1295,6 +1378,10 @@ impl Default for CommonBuilder {
             maybe_x_scroll: None,
             maybe_y_scroll: None,
             crop_kids: false,
+            crop_shape: crop::Shape::Rectangle,
+            opacity: 1.0,
+            rotation: 0.0,
+            scale: [1.0, 1.0],
         }
     }
 }
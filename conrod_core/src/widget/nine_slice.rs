@@ -0,0 +1,275 @@
+//! A widget for drawing a "nine-slice" (also known as "nine-patch") image.
+//!
+//! The source rectangle of the image is divided into nine slices by the given insets. When drawn,
+//! the four corner slices keep their size while the edge and centre slices are either stretched or
+//! tiled to fill the remaining area. This allows bitmap artwork to be used as the background for
+//! widgets of any size, e.g. via the `Skin` type accepted by the `Button`, `Canvas` and `TitleBar`.
+
+use image;
+use position::{Padding, Range, Rect};
+use widget;
+use {Color, Colorable, Positionable, Scalar, Sizeable, Widget};
+
+/// The greatest number of times the edge and centre slices are repeated along each axis when tiled.
+///
+/// This bounds the number of `Image` widgets used to draw the slices to `MAX_TILES + 2` along
+/// each axis.
+pub const MAX_TILES: usize = 8;
+
+/// Draws an image divided into nine slices, keeping the corners unscaled.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct NineSlice {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The image, source rectangle, insets and mode used to draw the slices.
+    pub skin: Skin,
+    /// Unique styling.
+    pub style: Style,
+}
+
+/// Describes how to draw some image as a nine-slice.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Skin {
+    /// The unique identifier of the image.
+    pub image_id: image::Id,
+    /// The area of the image in pixels, where `[0.0, 0.0]` is the bottom left of the image.
+    ///
+    /// Unlike the `Image` widget, the source rectangle is required as the dimensions of the image
+    /// are not known until rendering.
+    pub src_rect: Rect,
+    /// The distance in pixels from each edge of the `src_rect` to the edge of the centre slice.
+    pub insets: Padding,
+    /// Whether the edge and centre slices are stretched or tiled.
+    pub mode: Mode,
+}
+
+/// How the edge and centre slices are scaled to fill the area between the corners.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// The slices are stretched to fill the area.
+    Stretch,
+    /// The slices are repeated at their original size, with the last repetition along each axis
+    /// cut short to fit.
+    ///
+    /// If more than `MAX_TILES` repetitions would be needed along an axis, `MAX_TILES` evenly
+    /// stretched repetitions are drawn instead.
+    Tile,
+}
+
+/// Unique styling for the `NineSlice` widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// Optionally specify a single color to use for the image.
+    #[conrod(default = "None")]
+    pub maybe_color: Option<Option<Color>>,
+}
+
+widget_ids! {
+    struct Ids {
+        slices[],
+    }
+}
+
+/// Unique state for the `NineSlice` widget.
+pub struct State {
+    ids: Ids,
+    slices: Vec<(Rect, Rect)>,
+}
+
+impl Skin {
+    /// A skin that stretches the given `src_rect` of the image, keeping the `insets` unscaled.
+    pub fn new(image_id: image::Id, src_rect: Rect, insets: Padding) -> Self {
+        Skin {
+            image_id: image_id,
+            src_rect: src_rect,
+            insets: insets,
+            mode: Mode::Stretch,
+        }
+    }
+
+    /// Use the given `Mode` for the edge and centre slices.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Tile the edge and centre slices rather than stretching them.
+    pub fn tile(self) -> Self {
+        self.mode(Mode::Tile)
+    }
+}
+
+impl NineSlice {
+    /// Construct a new `NineSlice` that draws the given `src_rect` of the image.
+    ///
+    /// The `insets` describe the size of the corners in pixels.
+    pub fn new(image_id: image::Id, src_rect: Rect, insets: Padding) -> Self {
+        NineSlice::from_skin(Skin::new(image_id, src_rect, insets))
+    }
+
+    /// Construct a new `NineSlice` from the given `Skin`.
+    pub fn from_skin(skin: Skin) -> Self {
+        NineSlice {
+            common: widget::CommonBuilder::default(),
+            skin: skin,
+            style: Style::default(),
+        }
+    }
+
+    /// Use the given `Mode` for the edge and centre slices.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.skin.mode = mode;
+        self
+    }
+
+    /// Tile the edge and centre slices rather than stretching them.
+    pub fn tile(self) -> Self {
+        self.mode(Mode::Tile)
+    }
+}
+
+impl Widget for NineSlice {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            slices: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id,
+            state,
+            style,
+            rect,
+            ui,
+            ..
+        } = args;
+        let NineSlice { skin, .. } = self;
+
+        let mut slices = Vec::new();
+        skin_slices(rect, &skin, &mut slices);
+        if state.slices != slices {
+            state.update(|state| state.slices = slices);
+        }
+        if state.ids.slices.len() < state.slices.len() {
+            let num_slices = state.slices.len();
+            state.update(|state| {
+                state
+                    .ids
+                    .slices
+                    .resize(num_slices, &mut ui.widget_id_generator())
+            });
+        }
+
+        let maybe_color = style.maybe_color(&ui.theme);
+        for (&slice_id, &(dst, src)) in state.ids.slices.iter().zip(state.slices.iter()) {
+            let (x, y, w, h) = dst.x_y_w_h();
+            let mut image = widget::Image::new(skin.image_id)
+                .source_rectangle(src)
+                .x_y(x, y)
+                .w_h(w, h)
+                .parent(id)
+                .graphics_for(id);
+            image.style.maybe_color = Some(maybe_color);
+            image.set(slice_id, ui);
+        }
+    }
+}
+
+impl Colorable for NineSlice {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(Some(color));
+        self
+    }
+}
+
+/// Produces the destination and source rectangles of each slice of the `skin` drawn over `rect`.
+///
+/// Slices with no area are skipped.
+pub fn skin_slices(rect: Rect, skin: &Skin, slices: &mut Vec<(Rect, Rect)>) {
+    slices.clear();
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+    axis_slices(rect.x, skin.src_rect.x, skin.insets.x, skin.mode, &mut xs);
+    axis_slices(rect.y, skin.src_rect.y, skin.insets.y, skin.mode, &mut ys);
+    for &(dst_y, src_y) in &ys {
+        for &(dst_x, src_x) in &xs {
+            let dst = Rect { x: dst_x, y: dst_y };
+            let src = Rect { x: src_x, y: src_y };
+            slices.push((dst, src));
+        }
+    }
+}
+
+/// Divide the `dst` range into the destination and source ranges of each slice along one axis.
+fn axis_slices(
+    dst: Range,
+    src: Range,
+    insets: Range,
+    mode: Mode,
+    slices: &mut Vec<(Range, Range)>,
+) {
+    let (dst, src) = (dst.undirected(), src.undirected());
+    let src_len = src.len();
+    let start = insets.start.max(0.0).min(src_len);
+    let end = insets.end.max(0.0).min(src_len - start);
+
+    // Shrink the corners if there is not enough room for them.
+    let corners = start + end;
+    let scale = if corners > dst.len() && corners > 0.0 {
+        dst.len() / corners
+    } else {
+        1.0
+    };
+
+    let dst_mid = Range::new(dst.start + start * scale, dst.end - end * scale);
+    let src_mid = Range::new(src.start + start, src.end - end);
+    let mut push = |dst: Range, src: Range| {
+        if dst.len() > 0.0 && src.len() > 0.0 {
+            slices.push((dst, src));
+        }
+    };
+
+    push(
+        Range::new(dst.start, dst_mid.start),
+        Range::new(src.start, src_mid.start),
+    );
+    match mode {
+        Mode::Stretch => push(dst_mid, src_mid),
+        Mode::Tile => {
+            let tile_len = src_mid.len();
+            if tile_len > 0.0 && dst_mid.len() / tile_len > MAX_TILES as Scalar {
+                let tile_len = dst_mid.len() / MAX_TILES as Scalar;
+                for i in 0..MAX_TILES {
+                    let tile_start = dst_mid.start + tile_len * i as Scalar;
+                    push(Range::new(tile_start, tile_start + tile_len), src_mid);
+                }
+            } else if tile_len > 0.0 {
+                let mut tile_start = dst_mid.start;
+                while tile_start < dst_mid.end {
+                    let tile_end = (tile_start + tile_len).min(dst_mid.end);
+                    let src_end = src_mid.start + (tile_end - tile_start);
+                    push(
+                        Range::new(tile_start, tile_end),
+                        Range::new(src_mid.start, src_end),
+                    );
+                    tile_start = tile_end;
+                }
+            }
+        }
+    }
+    push(
+        Range::new(dst_mid.end, dst.end),
+        Range::new(src_mid.end, src.end),
+    );
+}
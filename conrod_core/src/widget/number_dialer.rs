@@ -4,9 +4,12 @@ use num::{Float, NumCast};
 use std::cmp::Ordering;
 use std::iter::repeat;
 use text;
+use theme::FontOptions;
 use utils::clamp;
 use widget;
-use {Borderable, Color, Colorable, FontSize, Labelable, Point, Positionable, Scalar, Widget};
+use {
+    Borderable, Color, Colorable, FontSize, Labelable, Point, Positionable, Scalar, Theme, Widget,
+};
 
 /// A widget for precision control over any digit within a value.
 ///
@@ -45,8 +48,19 @@ pub struct Style {
     #[conrod(default = "theme.font_size_medium")]
     pub label_font_size: Option<FontSize>,
     /// The `Id` associated with the font to use for the `NumberDialer` values.
-    #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the label's and value's font is selected when no font id is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's and value's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the label's and value's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// Whether or not the label may be selected and copied, in which case it receives its own
+    /// input rather than passing it to the NumberDialer.
+    #[conrod(default = "false")]
+    pub label_selectable: Option<bool>,
 }
 
 widget_ids! {
@@ -145,11 +159,54 @@ where
         self
     }
 
+    /// Select the label's and value's font from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the label's and value's font within its family.
+    pub fn font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the label's and value's font within its family.
+    pub fn font_style(mut self, style: text::font::Style) -> Self {
+        self.style.font_style = Some(style);
+        self
+    }
+
+    /// Allow the label to be selected and copied.
+    ///
+    /// A selectable label receives its own input rather than passing it to the NumberDialer.
+    pub fn label_selectable(mut self, selectable: bool) -> Self {
+        self.style.label_selectable = Some(selectable);
+        self
+    }
+
     builder_methods! {
         pub enabled { enabled = bool }
     }
 }
 
+impl Style {
+    /// The id of the font used to display the label's and value's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
 impl<'a, T> Widget for NumberDialer<'a, T>
 where
     T: Float + NumCast + ToString,
@@ -196,7 +253,7 @@ where
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
         //
         // If we've no font to use for text logic, bail out without updating.
-        let font_id = match style.font_id(&ui.theme).or(ui.fonts.ids().next()) {
+        let font_id = match style.resolve_font_id(&ui.theme, &ui.fonts) {
             Some(font_id) => font_id,
             None => return None,
         };
@@ -204,7 +261,7 @@ where
         let font_size = style.label_font_size(ui.theme());
         let label_string = maybe_label.map_or_else(|| String::new(), |text| format!("{}: ", text));
         let label_w = {
-            let font = ui.fonts.get(font_id).unwrap();
+            let font = ui.fonts.chain(font_id).unwrap();
             text::line::width(&label_string, font, font_size)
         };
         let label_dim = [label_w, font_size as f64];
@@ -359,10 +416,12 @@ where
         let label_color = style.label_color(ui.theme());
         let font_size = style.label_font_size(ui.theme());
         if maybe_label.is_some() {
+            let selectable = style.label_selectable(ui.theme());
             widget::Text::new(&label_string)
                 .font_id(font_id)
                 .x_y_relative_to(id, label_rel_x, 0.0)
-                .graphics_for(id)
+                .and_if(!selectable, |text| text.graphics_for(id))
+                .selectable(selectable)
                 .color(label_color)
                 .font_size(font_size)
                 .parent(id)
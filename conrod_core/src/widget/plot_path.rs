@@ -32,6 +32,9 @@ pub struct Style {
     /// The color of the line.
     #[conrod(default = "theme.shape_color")]
     pub color: Option<Color>,
+    /// The style with which the corners between consecutive line segments are drawn.
+    #[conrod(default = "widget::line::Join::default()")]
+    pub join: Option<widget::line::Join>,
 }
 
 widget_ids! {
@@ -64,6 +67,12 @@ impl<X, Y, F> PlotPath<X, Y, F> {
         self.style.thickness = Some(thickness);
         self
     }
+
+    /// The style with which the corners of the point path used to draw the plot are joined.
+    pub fn join(mut self, join: widget::line::Join) -> Self {
+        self.style.join = Some(join);
+        self
+    }
 }
 
 impl<X, Y, F> Widget for PlotPath<X, Y, F>
@@ -128,11 +137,13 @@ where
 
         let thickness = style.thickness(ui.theme());
         let color = style.color(ui.theme());
+        let join = style.join(ui.theme());
         widget::PointPath::new(point_iter)
             .wh(rect.dim())
             .xy(rect.xy())
             .color(color)
             .thickness(thickness)
+            .join(join)
             .parent(id)
             .graphics_for(id)
             .set(state.ids.point_path, ui);
@@ -1,7 +1,7 @@
 //! A simple, non-interactive widget for drawing an `Image`.
 
 use image;
-use position::{Dimension, Rect};
+use position::{Align, Dimension, Range, Rect, Scalar};
 use widget;
 use {Color, Ui, Widget};
 
@@ -36,6 +36,34 @@ pub struct Style {
     /// Optionally specify a single color to use for the image.
     #[conrod(default = "None")]
     pub maybe_color: Option<Option<Color>>,
+    /// How the image is fitted to the widget's `Rect`.
+    #[conrod(default = "Fit::Stretch")]
+    pub fit: Option<Fit>,
+    /// The alignment of the image along the *x* axis when it does not fill the `Rect`.
+    #[conrod(default = "Align::Middle")]
+    pub x_align: Option<Align>,
+    /// The alignment of the image along the *y* axis when it does not fill the `Rect`.
+    #[conrod(default = "Align::Middle")]
+    pub y_align: Option<Align>,
+}
+
+/// Describes how an image is fitted to the `Rect` of its widget.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fit {
+    /// The image is stretched to fill the `Rect`, ignoring its aspect ratio.
+    Stretch,
+    /// The image is scaled to fit within the `Rect`, preserving its aspect ratio.
+    ///
+    /// Any remaining area, e.g. for "letterboxing", is left empty.
+    Contain,
+    /// The image is scaled to cover the whole `Rect`, preserving its aspect ratio.
+    ///
+    /// Parts of the image that lie outside of the `Rect` are not drawn.
+    Cover,
+    /// The image is drawn at its original size and cropped to the `Rect` if necessary.
+    Center,
+    /// The image is repeated at its original size to fill the `Rect`.
+    Tile,
 }
 
 impl Image {
@@ -74,6 +102,8 @@ impl Image {
 
     /// The rectangular area of the image that we wish to display.
     ///
+    /// The `Rect` is given in pixels, where `[0.0, 0.0]` is the bottom left corner of the image.
+    ///
     /// If this method is not called, the entire image will be used.
     pub fn source_rectangle(mut self, rect: Rect) -> Self {
         self.src_rect = Some(rect);
@@ -82,6 +112,14 @@ impl Image {
 
     builder_methods! {
         pub color { style.maybe_color = Some(Option<Color>) }
+        pub fit { style.fit = Some(Fit) }
+        pub x_align { style.x_align = Some(Align) }
+        pub y_align { style.y_align = Some(Align) }
+    }
+
+    /// Align the image within the widget's `Rect` when it does not fill it.
+    pub fn align(self, x: Align, y: Align) -> Self {
+        self.x_align(x).y_align(y)
     }
 }
 
@@ -129,3 +167,90 @@ impl Widget for Image {
         }
     }
 }
+
+impl Fit {
+    /// Produce the destination and source rectangles of each quad required to draw an image
+    /// within the given `rect`.
+    ///
+    /// The `src_rect` is the area of the image in pixels to be drawn, where `[0.0, 0.0]` is the
+    /// bottom left of the image. Backends should use this along with the dimensions of the image
+    /// when drawing a `render::PrimitiveKind::Image`.
+    pub fn quads(
+        self,
+        rect: Rect,
+        src_rect: Rect,
+        x_align: Align,
+        y_align: Align,
+        quads: &mut Vec<(Rect, Rect)>,
+    ) {
+        quads.clear();
+        let (w, h) = rect.w_h();
+        let (src_w, src_h) = src_rect.w_h();
+        if src_w <= 0.0 || src_h <= 0.0 {
+            return;
+        }
+        let (x_scale, y_scale) = match self {
+            Fit::Stretch => (w / src_w, h / src_h),
+            Fit::Contain => {
+                let scale = (w / src_w).min(h / src_h);
+                (scale, scale)
+            }
+            Fit::Cover => {
+                let scale = (w / src_w).max(h / src_h);
+                (scale, scale)
+            }
+            Fit::Center | Fit::Tile => (1.0, 1.0),
+        };
+        let repeat = self == Fit::Tile;
+        let mut xs = Vec::new();
+        let mut ys = Vec::new();
+        axis_quads(rect.x, src_rect.x, x_scale, x_align, repeat, &mut xs);
+        axis_quads(rect.y, src_rect.y, y_scale, y_align, repeat, &mut ys);
+        for &(dst_y, src_y) in &ys {
+            for &(dst_x, src_x) in &xs {
+                let dst = Rect { x: dst_x, y: dst_y };
+                let src = Rect { x: src_x, y: src_y };
+                quads.push((dst, src));
+            }
+        }
+    }
+}
+
+/// Produce the destination and source ranges along a single axis.
+///
+/// The `src` range is scaled by `scale` and aligned within `dst`, then cropped to `dst`.
+fn axis_quads(
+    dst: Range,
+    src: Range,
+    scale: Scalar,
+    align: Align,
+    repeat: bool,
+    quads: &mut Vec<(Range, Range)>,
+) {
+    let (dst, src) = (dst.undirected(), src.undirected());
+    let len = src.len() * scale;
+    if len <= 0.0 {
+        return;
+    }
+    let mut image = Range::new(0.0, len).align_to(align, dst);
+    if repeat {
+        // Step back to the first repetition that overlaps the start of `dst`.
+        let steps = ((image.start - dst.start) / len).ceil();
+        image = image.shift(-steps * len);
+    }
+    loop {
+        if let Some(visible) = image.overlap(dst) {
+            if visible.len() > 0.0 {
+                let to_src = |x: Scalar| src.start + (x - image.start) / scale;
+                quads.push((
+                    visible,
+                    Range::new(to_src(visible.start), to_src(visible.end)),
+                ));
+            }
+        }
+        if !repeat || image.end >= dst.end {
+            break;
+        }
+        image = image.shift(len);
+    }
+}
@@ -42,6 +42,8 @@ pub struct Style {
     pub maybe_thickness: Option<Scalar>,
     /// The style with which the ends of the line are drawn.
     pub maybe_cap: Option<Cap>,
+    /// The style with which the corners between consecutive segments are drawn.
+    pub maybe_join: Option<Join>,
 }
 
 /// The pattern used to draw the line.
@@ -65,8 +67,31 @@ pub enum Cap {
     Round,
 }
 
+/// How the corner between two consecutive segments of a thick line should be drawn.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Join {
+    /// The outer edges of both segments are extended until they meet at a sharp point.
+    ///
+    /// The `Scalar` is the miter limit: the maximum ratio of the miter's length to the line's
+    /// thickness. Corners whose miter would exceed this limit are drawn with a `Bevel` instead.
+    Miter(Scalar),
+    /// The corner is rounded with a circular arc whose diameter is the line's thickness.
+    Round,
+    /// The corner is cut off by a straight edge between the outer edges of both segments.
+    Bevel,
+}
+
+/// The miter limit used by the default **Join**.
+pub const DEFAULT_MITER_LIMIT: Scalar = 4.0;
+
 const DEFAULT_THICKNESS: Scalar = 1.0;
 
+impl Default for Join {
+    fn default() -> Self {
+        Join::Miter(DEFAULT_MITER_LIMIT)
+    }
+}
+
 impl Line {
     /// Build a new **Line** widget with the given style.
     pub fn styled(start: Point, end: Point, style: Style) -> Self {
@@ -163,6 +188,7 @@ impl Style {
             maybe_color: None,
             maybe_thickness: None,
             maybe_cap: None,
+            maybe_join: None,
         }
     }
 
@@ -205,6 +231,12 @@ impl Style {
         self
     }
 
+    /// The style for the corners between consecutive segments of the line.
+    pub fn join(mut self, join: Join) -> Self {
+        self.set_join(join);
+        self
+    }
+
     /// Set the pattern for the line.
     pub fn set_pattern(&mut self, pattern: Pattern) {
         self.maybe_pattern = Some(pattern);
@@ -225,6 +257,11 @@ impl Style {
         self.maybe_cap = Some(cap);
     }
 
+    /// Set the **Join** for the line.
+    pub fn set_join(&mut self, join: Join) {
+        self.maybe_join = Some(join);
+    }
+
     /// The Pattern for the Line.
     pub fn get_pattern(&self, theme: &Theme) -> Pattern {
         const DEFAULT_PATTERN: Pattern = Pattern::Solid;
@@ -270,6 +307,17 @@ impl Style {
             })
             .unwrap_or(DEFAULT_CAP)
     }
+
+    /// The styling for the corners between consecutive segments of the Line.
+    pub fn get_join(&self, theme: &Theme) -> Join {
+        self.maybe_join
+            .or_else(|| {
+                theme
+                    .widget_style::<Style>()
+                    .map(|default| default.style.maybe_join.unwrap_or_default())
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl Widget for Line {
@@ -7,7 +7,9 @@
 pub mod image;
 pub mod line;
 pub mod point_path;
+pub mod shadow;
 pub mod shape;
+pub mod svg;
 pub mod text;
 
 use {Point, Range, Rect};
@@ -6,6 +6,7 @@ use widget;
 use widget::triangles::Triangle;
 use {Color, Colorable, Point, Positionable, Scalar, Sizeable, Theme, Widget};
 
+pub use super::line::Join;
 pub use super::line::Pattern;
 pub use super::line::Style;
 
@@ -32,12 +33,17 @@ pub struct State {
 
 /// An iterator that triangulates a point path.
 #[derive(Clone)]
-pub struct Triangles<I> {
-    next: Option<Triangle<Point>>,
-    prev: Point,
-    points: I,
+pub struct Triangles {
+    points: Vec<Point>,
+    closed: bool,
     half_thickness: Scalar,
-    cap: widget::line::Cap,
+    join: Join,
+    // The index of the next segment to be triangulated.
+    segment: usize,
+    // The corners at which the next segment starts, as produced by the preceding joint.
+    start: [Point; 2],
+    // Triangles that have been produced but not yet yielded.
+    buffer: Vec<Triangle<Point>>,
 }
 
 impl<I> PointPath<I> {
@@ -124,6 +130,12 @@ impl<I> PointPath<I> {
         self
     }
 
+    /// The style with which the corners between consecutive segments are drawn.
+    pub fn join(mut self, join: Join) -> Self {
+        self.style.set_join(join);
+        self
+    }
+
     /// Make a Solid line.
     pub fn solid(mut self) -> Self {
         self.style.set_pattern(Pattern::Solid);
@@ -217,12 +229,12 @@ impl<I> Colorable for PointPath<I> {
 
 /// Triangulate a point path.
 ///
+/// Each segment is drawn as a quad and the corner between consecutive segments is filled in
+/// according to the given `join`. If the path ends where it began, it is treated as closed and
+/// the corner at the first point is joined too.
+///
 /// Returns `None` if the given iterator yields less than one point.
-pub fn triangles<I>(
-    points: I,
-    cap: widget::line::Cap,
-    thickness: Scalar,
-) -> Option<Triangles<I::IntoIter>>
+pub fn triangles<I>(points: I, join: Join, thickness: Scalar) -> Option<Triangles>
 where
     I: IntoIterator<Item = Point>,
 {
@@ -231,41 +243,226 @@ where
         Some(point) => point,
         None => return None,
     };
+
+    // Skip consecutive duplicates as they have no direction to join with.
+    let mut path = vec![first];
+    for point in points {
+        if !points_coincide(path[path.len() - 1], point) {
+            path.push(point);
+        }
+    }
+    let len = path.len();
+    let closed = len > 3 && points_coincide(path[0], path[len - 1]);
+    let half_thickness = thickness / 2.0;
+
+    let mut buffer = Vec::new();
+    let start = if len < 2 {
+        [first, first]
+    } else if closed {
+        // The triangles for the closing joint are produced along with the last segment.
+        let (_, start) = joint(
+            path[len - 2],
+            path[0],
+            path[1],
+            half_thickness,
+            join,
+            &mut buffer,
+        );
+        buffer.clear();
+        start
+    } else {
+        let normal = segment_normal(path[0], path[1], half_thickness);
+        [vec2_add(path[0], normal), vec2_sub(path[0], normal)]
+    };
+
     Some(Triangles {
-        next: None,
-        prev: first,
-        points: points,
-        half_thickness: thickness / 2.0,
-        cap: cap,
+        points: path,
+        closed,
+        half_thickness,
+        join,
+        segment: 0,
+        start,
+        buffer,
     })
 }
 
-impl<I> Iterator for Triangles<I>
-where
-    I: Iterator<Item = Point>,
-{
+impl Triangles {
+    // Triangulate the next segment along with the joint at its end.
+    fn triangulate_next_segment(&mut self) {
+        let Triangles {
+            ref points,
+            closed,
+            half_thickness,
+            join,
+            ref mut segment,
+            ref mut start,
+            ref mut buffer,
+        } = *self;
+        let last = points.len() - 1;
+        let (a, b) = (points[*segment], points[*segment + 1]);
+        let [start_l, start_r] = *start;
+        let end = if *segment + 1 < last {
+            let (end, next_start) = joint(a, b, points[*segment + 2], half_thickness, join, buffer);
+            *start = next_start;
+            end
+        } else if closed {
+            let (end, _) = joint(a, b, points[1], half_thickness, join, buffer);
+            end
+        } else {
+            let normal = segment_normal(a, b, half_thickness);
+            [vec2_add(b, normal), vec2_sub(b, normal)]
+        };
+        let [end_l, end_r] = end;
+        buffer.push(Triangle([start_l, end_l, start_r]));
+        buffer.push(Triangle([start_r, end_l, end_r]));
+        *segment += 1;
+    }
+}
+
+impl Iterator for Triangles {
     type Item = Triangle<Point>;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(triangle) = self.next.take() {
-            return Some(triangle);
+        loop {
+            if let Some(triangle) = self.buffer.pop() {
+                return Some(triangle);
+            }
+            if self.segment + 1 >= self.points.len() {
+                return None;
+            }
+            self.triangulate_next_segment();
         }
-        self.points.next().map(|point| {
-            let (a, b) = (self.prev, point);
-            self.prev = point;
-            let tris = widget::line::triangles(a, b, self.half_thickness);
-            self.next = Some(tris[1]);
-            tris[0]
-        })
     }
 }
 
+// Distances below this are considered to be zero when triangulating.
+const EPSILON: Scalar = 1e-9;
+
+// The maximum angle spanned by a single triangle of a `Join::Round` arc.
+const ROUND_JOIN_STEP_RADIANS: Scalar = std::f64::consts::PI / 16.0;
+
+fn points_coincide(a: Point, b: Point) -> bool {
+    let d = vec2_sub(b, a);
+    (d[0] * d[0] + d[1] * d[1]).sqrt() < EPSILON
+}
+
+fn vec2_scale(v: Point, scale: Scalar) -> Point {
+    [v[0] * scale, v[1] * scale]
+}
+
+// The unit direction and length of the segment `a -> b`.
+fn segment_direction(a: Point, b: Point) -> (Point, Scalar) {
+    let d = vec2_sub(b, a);
+    let len = (d[0] * d[0] + d[1] * d[1]).sqrt();
+    ([d[0] / len, d[1] / len], len)
+}
+
+// The left-hand normal of the segment `a -> b` scaled to `half_thickness`.
+fn segment_normal(a: Point, b: Point, half_thickness: Scalar) -> Point {
+    let (u, _) = segment_direction(a, b);
+    [-u[1] * half_thickness, u[0] * half_thickness]
+}
+
+// Produce the corners for the joint at `p` between the segments `a -> p` and `p -> c`.
+//
+// Returns the `[left, right]` corners at which the incoming segment ends and those at which the
+// outgoing segment starts. Triangles filling the outer side of the corner are pushed to `buffer`.
+//
+// Where possible, the inner corners of both segments are moved to the point at which their inner
+// edges intersect so that the segments do not overlap.
+fn joint(
+    a: Point,
+    p: Point,
+    c: Point,
+    half_thickness: Scalar,
+    join: Join,
+    buffer: &mut Vec<Triangle<Point>>,
+) -> ([Point; 2], [Point; 2]) {
+    let (u0, len0) = segment_direction(a, p);
+    let (u1, len1) = segment_direction(p, c);
+    let n0 = [-u0[1], u0[0]];
+    let n1 = [-u1[1], u1[0]];
+    let cross = u0[0] * u1[1] - u0[1] * u1[0];
+    let dot = u0[0] * u1[0] + u0[1] * u1[1];
+    let h = half_thickness;
+
+    // The segments continue in a straight line, so there is no corner to join.
+    if cross.abs() < EPSILON && dot > 0.0 {
+        let n = vec2_scale(n0, h);
+        let corners = [vec2_add(p, n), vec2_sub(p, n)];
+        return (corners, corners);
+    }
+
+    // Turning left puts the inner side of the corner on the left.
+    let inner_sign = if cross >= 0.0 { 1.0 } else { -1.0 };
+    let outer_sign = -inner_sign;
+    let outer_end = vec2_add(p, vec2_scale(n0, outer_sign * h));
+    let outer_start = vec2_add(p, vec2_scale(n1, outer_sign * h));
+
+    // The sum of both normals scaled so that it reaches the intersection of the offset edges.
+    let miter = if 1.0 + dot > EPSILON {
+        Some(vec2_scale(vec2_add(n0, n1), 1.0 / (1.0 + dot)))
+    } else {
+        None
+    };
+
+    // How far the inner intersection lies along each segment from the corner.
+    let inner_offset = h * cross.abs() / (1.0 + dot);
+    let (pivot, end, start) = match miter {
+        Some(m) if inner_offset <= len0.min(len1) => {
+            let inner = vec2_add(p, vec2_scale(m, inner_sign * h));
+            if inner_sign > 0.0 {
+                (inner, [inner, outer_end], [inner, outer_start])
+            } else {
+                (inner, [outer_end, inner], [outer_start, inner])
+            }
+        }
+        _ => {
+            let (e, s) = (vec2_scale(n0, h), vec2_scale(n1, h));
+            (
+                p,
+                [vec2_add(p, e), vec2_sub(p, e)],
+                [vec2_add(p, s), vec2_sub(p, s)],
+            )
+        }
+    };
+
+    match join {
+        Join::Miter(limit) => match miter {
+            Some(m) if (m[0] * m[0] + m[1] * m[1]).sqrt() <= limit => {
+                let tip = vec2_add(p, vec2_scale(m, outer_sign * h));
+                buffer.push(Triangle([pivot, outer_end, tip]));
+                buffer.push(Triangle([pivot, tip, outer_start]));
+            }
+            _ => buffer.push(Triangle([pivot, outer_end, outer_start])),
+        },
+        Join::Bevel => buffer.push(Triangle([pivot, outer_end, outer_start])),
+        Join::Round => {
+            // The outer edge sweeps through the same angle as the direction of the path.
+            let angle = cross.abs().atan2(dot);
+            let steps = (angle / ROUND_JOIN_STEP_RADIANS).ceil().max(1.0) as usize;
+            let step = inner_sign * angle / steps as Scalar;
+            let start_radians = (outer_sign * n0[1]).atan2(outer_sign * n0[0]);
+            let mut prev = outer_end;
+            for i in 1..steps {
+                let radians = start_radians + step * i as Scalar;
+                let next = [p[0] + h * radians.cos(), p[1] + h * radians.sin()];
+                buffer.push(Triangle([pivot, prev, next]));
+                prev = next;
+            }
+            buffer.push(Triangle([pivot, prev, outer_start]));
+        }
+    }
+
+    (end, start)
+}
+
 /// Returns whether or not the given point `p` lies over the `PointPath` described by the given
-/// points, line cap and thickness.
-pub fn is_over<I>(points: I, cap: widget::line::Cap, thickness: Scalar, p: Point) -> bool
+/// points, line join and thickness.
+pub fn is_over<I>(points: I, join: Join, thickness: Scalar, p: Point) -> bool
 where
     I: IntoIterator<Item = Point>,
 {
-    triangles(points, cap, thickness)
+    triangles(points, join, thickness)
         .map(|ts| widget::triangles::is_over(ts, p))
         .unwrap_or(false)
 }
@@ -275,9 +472,9 @@ pub fn is_over_widget(widget: &graph::Container, point: Point, theme: &Theme) ->
     widget
         .state_and_style::<State, Style>()
         .map(|widget| {
-            let cap = widget.style.get_cap(theme);
+            let join = widget.style.get_join(theme);
             let thickness = widget.style.get_thickness(theme);
-            is_over(widget.state.points.iter().cloned(), cap, thickness, point)
+            is_over(widget.state.points.iter().cloned(), join, thickness, point)
         })
         .unwrap_or_else(|| widget.rect.is_over(point))
         .into()
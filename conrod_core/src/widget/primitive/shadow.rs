@@ -0,0 +1,185 @@
+//! A simple, non-interactive widget for drawing a soft drop shadow beneath some other widget.
+//!
+//! The shadow is described entirely by geometry: a solid core surrounded by a ring of triangles
+//! whose alpha fades to zero at the outer edge. This allows the shadow to be rendered by any
+//! backend without additional shader passes.
+
+use color;
+use graph;
+use position::{Point, Rect, Scalar};
+use std::f64::consts::PI;
+use theme::Theme;
+use widget;
+use widget::triangles::{ColoredPoint, Triangle};
+use {Color, Colorable, Widget};
+
+/// A soft-edged drop shadow for some rectangular or rounded rectangular area.
+///
+/// The `Shadow` should be given the same position and dimensions as the widget that casts it and
+/// instantiated *before* that widget so that it is drawn beneath it.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct Shadow {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// Unique styling for the **Shadow**.
+    pub style: Style,
+}
+
+/// Unique styling for the **Shadow**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of the shadow at its most opaque.
+    #[conrod(default = "color::BLACK.alpha(0.5)")]
+    pub color: Option<Color>,
+    /// The offset of the shadow from the widget that casts it.
+    #[conrod(default = "[0.0, 0.0]")]
+    pub offset: Option<Point>,
+    /// The distance over which the edge of the shadow fades from its color to transparent.
+    #[conrod(default = "8.0")]
+    pub blur: Option<Scalar>,
+    /// The distance by which the shadow is grown (or shrunk if negative) before blurring.
+    #[conrod(default = "0.0")]
+    pub spread: Option<Scalar>,
+    /// The corner radius of the shape casting the shadow.
+    #[conrod(default = "0.0")]
+    pub radius: Option<Scalar>,
+}
+
+/// Unique state for the **Shadow**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct State;
+
+/// The number of lines used to describe each corner of the shadow.
+pub const CORNER_RESOLUTION: usize = widget::rounded_rectangle::DEFAULT_CORNER_RESOLUTION;
+
+impl Shadow {
+    /// Construct a new **Shadow** with the default style.
+    pub fn new() -> Self {
+        Shadow::styled(Style::default())
+    }
+
+    /// Construct a new **Shadow** with the given style.
+    pub fn styled(style: Style) -> Self {
+        Shadow {
+            common: widget::CommonBuilder::default(),
+            style: style,
+        }
+    }
+
+    builder_methods! {
+        pub offset { style.offset = Some(Point) }
+        pub blur { style.blur = Some(Scalar) }
+        pub spread { style.spread = Some(Scalar) }
+        pub radius { style.radius = Some(Scalar) }
+    }
+}
+
+impl Default for Shadow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for Shadow {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn is_over(&self) -> widget::IsOverFn {
+        is_over_widget
+    }
+
+    fn update(self, _args: widget::UpdateArgs<Self>) -> Self::Event {}
+}
+
+impl Colorable for Shadow {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+/// The shadow never captures input, allowing widgets beneath it to be interacted with.
+pub fn is_over_widget(_: &graph::Container, _: Point, _: &Theme) -> widget::IsOver {
+    widget::IsOver::Bool(false)
+}
+
+/// Fill `triangles` with the geometry of the shadow cast by the given `rect`.
+///
+/// The core of the shadow is filled with the shadow's color while the surrounding ring, `blur`
+/// wide, fades to transparent.
+pub fn triangles(
+    rect: Rect,
+    style: &Style,
+    theme: &Theme,
+    triangles: &mut Vec<Triangle<ColoredPoint>>,
+) {
+    let color = style.color(theme).to_rgb();
+    let color::Rgba(r, g, b, _) = color;
+    let transparent = color::Rgba(r, g, b, 0.0);
+    let offset = style.offset(theme);
+    let blur = style.blur(theme).max(0.0);
+    let spread = style.spread(theme);
+
+    // The solid core is grown by the spread and shrunk by half of the blur.
+    let grow = spread - blur / 2.0;
+    let (x, y, w, h) = rect.x_y_w_h();
+    let core = Rect::from_xy_dim(
+        [x + offset[0], y + offset[1]],
+        [(w + grow * 2.0).max(0.0), (h + grow * 2.0).max(0.0)],
+    );
+    let radius = (style.radius(theme) + grow)
+        .min(core.w() / 2.0)
+        .min(core.h() / 2.0)
+        .max(0.0);
+
+    // Walk each corner counter-clockwise from the top right, collecting the inner and outer
+    // points of the ring along with their shared normal.
+    let (l, r, b, t) = core.l_r_b_t();
+    let corners = [
+        [r - radius, t - radius],
+        [l + radius, t - radius],
+        [l + radius, b + radius],
+        [r - radius, b + radius],
+    ];
+    let mut inner = Vec::with_capacity(corners.len() * (CORNER_RESOLUTION + 1));
+    let mut outer = Vec::with_capacity(inner.capacity());
+    for (i, centre) in corners.iter().enumerate() {
+        for step in 0..CORNER_RESOLUTION + 1 {
+            let fraction = step as Scalar / CORNER_RESOLUTION as Scalar;
+            let radians = (i as Scalar + fraction) * PI / 2.0;
+            let (sin, cos) = radians.sin_cos();
+            let p = [centre[0] + cos * radius, centre[1] + sin * radius];
+            inner.push(p);
+            outer.push([p[0] + cos * blur, p[1] + sin * blur]);
+        }
+    }
+
+    let centre = core.xy();
+    for i in 0..inner.len() {
+        let j = (i + 1) % inner.len();
+        triangles.push(Triangle([
+            (centre, color),
+            (inner[i], color),
+            (inner[j], color),
+        ]));
+        if blur > 0.0 {
+            triangles.push(Triangle([
+                (inner[i], color),
+                (outer[i], transparent),
+                (outer[j], transparent),
+            ]));
+            triangles.push(Triangle([
+                (inner[i], color),
+                (outer[j], transparent),
+                (inner[j], color),
+            ]));
+        }
+    }
+}
@@ -0,0 +1,746 @@
+//! Parsing of SVG documents into triangles ready for rendering.
+//!
+//! Only the static subset of SVG commonly used for icons is supported: the `path`, `rect`,
+//! `circle`, `ellipse`, `line`, `polyline` and `polygon` shapes, `g` groups, `transform`s, solid
+//! fills and strokes along with linear and radial gradients. Other elements, such as `text`,
+//! `clipPath` and `mask`, are ignored.
+
+use super::path::{self, SubPath};
+use super::tessellate::{self, FillRule};
+use color::{self, Rgba};
+use position::{Point, Range, Rect, Scalar, Transform};
+use std::collections::HashMap;
+use utils;
+use widget::point_path::Join;
+use widget::triangles::{ColoredPoint, Triangle};
+use xml;
+use xml::reader::{EventReader, XmlEvent};
+
+/// The number of line segments along the larger side of the view box for which curves are
+/// flattened within a single segment's tolerance.
+///
+/// Curves remain smooth when drawn at up to roughly this many pixels in size.
+const CURVE_RESOLUTION: Scalar = 2048.0;
+
+/// The number of steps across the bounding box of a shape at which gradients are sampled.
+const GRADIENT_RESOLUTION: Scalar = 32.0;
+
+/// The maximum depth of gradient `href` chains, guarding against cycles.
+const MAX_HREF_DEPTH: usize = 8;
+
+/// An SVG document tessellated into colored triangles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Document {
+    /// The area of the document's user space that is fitted to the widget's `Rect`.
+    ///
+    /// Note that *y* increases downwards within SVG user space.
+    pub view_box: Rect,
+    /// The triangles describing the document in user space, in the order in which they are drawn.
+    pub triangles: Vec<Triangle<ColoredPoint>>,
+}
+
+/// The errors that might occur while parsing an SVG document.
+#[derive(Debug)]
+pub enum Error {
+    /// The document is not well-formed XML.
+    Xml(xml::reader::Error),
+    /// The root element of the document is not an `svg` element.
+    NotSvg,
+    /// The size of the document could not be determined from its `viewBox` or `width` and
+    /// `height`.
+    NoViewBox,
+}
+
+// A node of the parsed XML tree.
+struct Element {
+    name: String,
+    // Attributes along with the declarations of the `style` attribute, which take precedence.
+    properties: HashMap<String, String>,
+    children: Vec<Element>,
+}
+
+// The paint used to fill or stroke a shape.
+#[derive(Clone, Debug, PartialEq)]
+enum Paint {
+    Color(Rgba),
+    CurrentColor,
+    Url(String),
+}
+
+// Presentation properties inherited from parent elements.
+#[derive(Clone, Debug)]
+struct Context {
+    transform: Transform,
+    fill: Option<Paint>,
+    fill_rule: FillRule,
+    fill_opacity: f32,
+    stroke: Option<Paint>,
+    stroke_width: Scalar,
+    stroke_opacity: f32,
+    join: Join,
+    color: Rgba,
+    opacity: f32,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum GradientKind {
+    Linear { start: Point, end: Point },
+    Radial { centre: Point, radius: Scalar },
+}
+
+#[derive(Clone, Debug)]
+struct Gradient {
+    kind: GradientKind,
+    // Whether coordinates are relative to the bounding box of the shape being painted.
+    bounding_box_units: bool,
+    // Maps points from user space into the coordinate space of the gradient.
+    inverse_transform: Transform,
+    stops: Vec<(f32, Rgba)>,
+}
+
+// A paint resolved for a particular shape.
+enum Fill {
+    Color(Rgba),
+    Gradient(Gradient, Rect, f32),
+}
+
+impl Document {
+    /// Parse and tessellate the given SVG document.
+    pub fn parse(data: &str) -> Result<Self, Error> {
+        let root = parse_tree(data)?;
+        if root.name != "svg" {
+            return Err(Error::NotSvg);
+        }
+        let view_box = view_box(&root).ok_or(Error::NoViewBox)?;
+
+        let mut elements_by_id = HashMap::new();
+        collect_ids(&root, &mut elements_by_id);
+        let mut parser = DocumentParser {
+            elements_by_id: elements_by_id,
+            view_box: view_box,
+            tolerance: view_box.w().max(view_box.h()) / CURVE_RESOLUTION,
+            triangles: Vec::new(),
+            scratch: Vec::new(),
+        };
+        let context = Context {
+            transform: Transform::IDENTITY,
+            fill: Some(Paint::Color(color::BLACK.to_rgb())),
+            fill_rule: FillRule::NonZero,
+            fill_opacity: 1.0,
+            stroke: None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            join: Join::Miter(4.0),
+            color: color::BLACK.to_rgb(),
+            opacity: 1.0,
+        };
+        parser.element(&root, &context);
+        Ok(Document {
+            view_box: view_box,
+            triangles: parser.triangles,
+        })
+    }
+}
+
+impl From<xml::reader::Error> for Error {
+    fn from(e: xml::reader::Error) -> Self {
+        Error::Xml(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Xml(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        let s = match *self {
+            Error::Xml(ref e) => return std::fmt::Display::fmt(e, f),
+            Error::NotSvg => "The root element of the document is not `svg`.",
+            Error::NoViewBox => "The document has neither a `viewBox` nor a `width` and `height`.",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+fn parse_tree(data: &str) -> Result<Element, Error> {
+    let mut stack: Vec<Element> = Vec::new();
+    for event in EventReader::from_str(data) {
+        match event? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let mut properties: HashMap<String, String> = attributes
+                    .into_iter()
+                    .map(|attr| (attr.name.local_name, attr.value))
+                    .collect();
+                let style = properties.get("style").cloned();
+                for declaration in style.iter().flat_map(|s| s.split(';')) {
+                    let mut parts = declaration.splitn(2, ':');
+                    if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+                        properties.insert(key.trim().to_string(), value.trim().to_string());
+                    }
+                }
+                stack.push(Element {
+                    name: name.local_name,
+                    properties: properties,
+                    children: Vec::new(),
+                });
+            }
+            XmlEvent::EndElement { .. } => {
+                let element = stack.pop().expect("unbalanced XML events");
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            _ => (),
+        }
+    }
+    Err(Error::NotSvg)
+}
+
+fn collect_ids<'a>(element: &'a Element, ids: &mut HashMap<&'a str, &'a Element>) {
+    if let Some(id) = element.properties.get("id") {
+        ids.insert(id, element);
+    }
+    for child in &element.children {
+        collect_ids(child, ids);
+    }
+}
+
+fn view_box(root: &Element) -> Option<Rect> {
+    if let Some(view_box) = root.properties.get("viewBox") {
+        let n = path::parse_numbers(view_box);
+        if n.len() == 4 && n[2] > 0.0 && n[3] > 0.0 {
+            return Some(Rect::from_corners([n[0], n[1]], [n[0] + n[2], n[1] + n[3]]));
+        }
+    }
+    let w = root.properties.get("width").and_then(|s| length(s))?;
+    let h = root.properties.get("height").and_then(|s| length(s))?;
+    if w > 0.0 && h > 0.0 {
+        Some(Rect::from_corners([0.0, 0.0], [w, h]))
+    } else {
+        None
+    }
+}
+
+// Parse a length, ignoring any absolute unit. Percentages are returned as a fraction.
+fn length(s: &str) -> Option<Scalar> {
+    let s = s.trim();
+    let (number, scale) = match s.strip_suffix('%') {
+        Some(number) => (number, 0.01),
+        None => (s.trim_end_matches(char::is_alphabetic), 1.0),
+    };
+    number.trim().parse::<Scalar>().ok().map(|n| n * scale)
+}
+
+fn opacity(s: &str) -> Option<f32> {
+    length(s).map(|n| utils::clamp(n as f32, 0.0, 1.0))
+}
+
+// Parse a color, e.g. `#f80`, `#ff8800`, `rgb(255, 128, 0)` or `orange`.
+fn parse_color(s: &str) -> Option<Rgba> {
+    let s = s.trim();
+    let rgb = |r: f32, g: f32, b: f32| Some(Rgba(r, g, b, 1.0));
+    if let Some(hex) = s.strip_prefix('#') {
+        let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+        let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let (r, g, b) = match hex.len() {
+            3 if hex.is_ascii() => (digit(0)? * 17, digit(1)? * 17, digit(2)? * 17),
+            6 if hex.is_ascii() => (byte(0)?, byte(2)?, byte(4)?),
+            _ => return None,
+        };
+        return rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    }
+    if s.starts_with("rgb(") && s.ends_with(')') {
+        let channels: Vec<f32> = s[4..s.len() - 1]
+            .split(',')
+            .filter_map(|c| {
+                let c = c.trim();
+                if c.ends_with('%') {
+                    length(c).map(|n| n as f32)
+                } else {
+                    c.parse::<f32>().ok().map(|n| n / 255.0)
+                }
+            })
+            .collect();
+        return match channels[..] {
+            [r, g, b] => rgb(r, g, b),
+            _ => None,
+        };
+    }
+    let color = match &s.to_ascii_lowercase()[..] {
+        "black" => color::BLACK,
+        "white" => color::WHITE,
+        "red" => color::rgb_bytes(255, 0, 0),
+        "green" => color::rgb_bytes(0, 128, 0),
+        "lime" => color::rgb_bytes(0, 255, 0),
+        "blue" => color::rgb_bytes(0, 0, 255),
+        "navy" => color::rgb_bytes(0, 0, 128),
+        "yellow" => color::rgb_bytes(255, 255, 0),
+        "cyan" | "aqua" => color::rgb_bytes(0, 255, 255),
+        "magenta" | "fuchsia" => color::rgb_bytes(255, 0, 255),
+        "gray" | "grey" => color::rgb_bytes(128, 128, 128),
+        "silver" => color::rgb_bytes(192, 192, 192),
+        "maroon" => color::rgb_bytes(128, 0, 0),
+        "olive" => color::rgb_bytes(128, 128, 0),
+        "teal" => color::rgb_bytes(0, 128, 128),
+        "purple" => color::rgb_bytes(128, 0, 128),
+        "orange" => color::rgb_bytes(255, 165, 0),
+        "transparent" => return Some(Rgba(0.0, 0.0, 0.0, 0.0)),
+        _ => return None,
+    };
+    Some(color.to_rgb())
+}
+
+// Parse the value of a `fill` or `stroke` property. `Some(None)` represents `none`.
+fn parse_paint(s: &str) -> Option<Option<Paint>> {
+    let s = s.trim();
+    match s {
+        "none" => Some(None),
+        "currentColor" => Some(Some(Paint::CurrentColor)),
+        _ if s.starts_with("url(") => {
+            let end = s.find(')')?;
+            let id = s[4..end].trim().trim_start_matches('#');
+            Some(Some(Paint::Url(id.to_string())))
+        }
+        _ => parse_color(s).map(|c| Some(Paint::Color(c))),
+    }
+}
+
+// Parse a list of transform functions, e.g. `translate(10, 20) rotate(45)`.
+fn parse_transform(s: &str) -> Transform {
+    let mut transform = Transform::IDENTITY;
+    for function in s.split(')') {
+        let mut parts = function.splitn(2, '(');
+        let (name, args) = match (parts.next(), parts.next()) {
+            (Some(name), Some(args)) => (
+                name.trim_matches(|c: char| c == ',' || c.is_whitespace()),
+                args,
+            ),
+            _ => continue,
+        };
+        let n = path::parse_numbers(args);
+        let arg = |i: usize, default: Scalar| n.get(i).cloned().unwrap_or(default);
+        let function = match name {
+            "matrix" if n.len() == 6 => Transform {
+                matrix: [[n[0], n[2], n[4]], [n[1], n[3], n[5]]],
+            },
+            "translate" => Transform::translation([arg(0, 0.0), arg(1, 0.0)]),
+            "scale" => Transform::scale([arg(0, 1.0), arg(1, arg(0, 1.0))]),
+            "rotate" => {
+                let pivot = [arg(1, 0.0), arg(2, 0.0)];
+                Transform::about(pivot, arg(0, 0.0).to_radians(), [1.0, 1.0])
+            }
+            "skewX" => Transform {
+                matrix: [[1.0, arg(0, 0.0).to_radians().tan(), 0.0], [0.0, 1.0, 0.0]],
+            },
+            "skewY" => Transform {
+                matrix: [[1.0, 0.0, 0.0], [arg(0, 0.0).to_radians().tan(), 1.0, 0.0]],
+            },
+            _ => continue,
+        };
+        // Functions are applied from right to left.
+        transform = function.then(transform);
+    }
+    transform
+}
+
+// Convert a basic shape to equivalent path data.
+fn shape_path_data(element: &Element) -> Option<String> {
+    let number = |name: &str| {
+        element
+            .properties
+            .get(name)
+            .and_then(|s| length(s))
+            .unwrap_or(0.0)
+    };
+    let data = match &element.name[..] {
+        "path" => element.properties.get("d")?.clone(),
+        "rect" => {
+            let (x, y, w, h) = (number("x"), number("y"), number("width"), number("height"));
+            let (rx, ry) = match (
+                element.properties.get("rx").and_then(|s| length(s)),
+                element.properties.get("ry").and_then(|s| length(s)),
+            ) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(r), None) | (None, Some(r)) => (r, r),
+                (None, None) => (0.0, 0.0),
+            };
+            let (rx, ry) = (rx.min(w / 2.0), ry.min(h / 2.0));
+            format!(
+                "M{} {}H{}A{} {} 0 0 1 {} {}V{}A{} {} 0 0 1 {} {}H{}A{} {} 0 0 1 {} {}V{}A{} {} 0 0 1 {} {}Z",
+                x + rx, y, x + w - rx, rx, ry, x + w, y + ry, y + h - ry, rx, ry, x + w - rx, y + h,
+                x + rx, rx, ry, x, y + h - ry, y + ry, rx, ry, x + rx, y,
+            )
+        }
+        "circle" | "ellipse" => {
+            let (cx, cy) = (number("cx"), number("cy"));
+            let (rx, ry) = if element.name == "circle" {
+                (number("r"), number("r"))
+            } else {
+                (number("rx"), number("ry"))
+            };
+            format!(
+                "M{} {}A{} {} 0 1 0 {} {}A{} {} 0 1 0 {} {}Z",
+                cx - rx,
+                cy,
+                rx,
+                ry,
+                cx + rx,
+                cy,
+                rx,
+                ry,
+                cx - rx,
+                cy,
+            )
+        }
+        "line" => format!(
+            "M{} {}L{} {}",
+            number("x1"),
+            number("y1"),
+            number("x2"),
+            number("y2")
+        ),
+        "polyline" | "polygon" => {
+            let points = element.properties.get("points")?;
+            let close = if element.name == "polygon" { "Z" } else { "" };
+            format!("M{}{}", points, close)
+        }
+        _ => return None,
+    };
+    Some(data)
+}
+
+fn bounding_box(subpaths: &[SubPath]) -> Option<Rect> {
+    let mut points = subpaths.iter().flat_map(|s| s.points.iter());
+    let first = points.next()?;
+    let mut rect = Rect::from_corners(*first, *first);
+    for p in points {
+        rect = rect.stretch_to_point(*p);
+    }
+    Some(rect)
+}
+
+impl Gradient {
+    // The color of the gradient at the given point in the user space of the shape.
+    fn color_at(&self, p: Point, bounding_box: Rect) -> Rgba {
+        let mut p = p;
+        if self.bounding_box_units {
+            let (l, b) = (bounding_box.left(), bounding_box.bottom());
+            p = [
+                (p[0] - l) / bounding_box.w().max(f64::EPSILON),
+                (p[1] - b) / bounding_box.h().max(f64::EPSILON),
+            ];
+        }
+        let p = self.inverse_transform.apply(p);
+        let t = match self.kind {
+            GradientKind::Linear { start, end } => {
+                let d = [end[0] - start[0], end[1] - start[1]];
+                let len_sq = d[0] * d[0] + d[1] * d[1];
+                if len_sq == 0.0 {
+                    1.0
+                } else {
+                    ((p[0] - start[0]) * d[0] + (p[1] - start[1]) * d[1]) / len_sq
+                }
+            }
+            GradientKind::Radial { centre, radius } => {
+                let d = [p[0] - centre[0], p[1] - centre[1]];
+                if radius == 0.0 {
+                    1.0
+                } else {
+                    (d[0] * d[0] + d[1] * d[1]).sqrt() / radius
+                }
+            }
+        };
+        let t = utils::clamp(t, 0.0, 1.0) as f32;
+        let stops = &self.stops;
+        match stops.iter().position(|&(offset, _)| offset >= t) {
+            None => stops[stops.len() - 1].1,
+            Some(0) => stops[0].1,
+            Some(i) => {
+                let ((a_offset, a), (b_offset, b)) = (stops[i - 1], stops[i]);
+                let f = if b_offset > a_offset {
+                    (t - a_offset) / (b_offset - a_offset)
+                } else {
+                    1.0
+                };
+                let lerp = |a: f32, b: f32| a + (b - a) * f;
+                Rgba(
+                    lerp(a.0, b.0),
+                    lerp(a.1, b.1),
+                    lerp(a.2, b.2),
+                    lerp(a.3, b.3),
+                )
+            }
+        }
+    }
+}
+
+impl Fill {
+    fn color_at(&self, p: Point) -> Rgba {
+        match *self {
+            Fill::Color(color) => color,
+            Fill::Gradient(ref gradient, bounding_box, opacity) => {
+                let Rgba(r, g, b, a) = gradient.color_at(p, bounding_box);
+                Rgba(r, g, b, a * opacity)
+            }
+        }
+    }
+}
+
+struct DocumentParser<'a> {
+    elements_by_id: HashMap<&'a str, &'a Element>,
+    view_box: Rect,
+    tolerance: Scalar,
+    triangles: Vec<Triangle<ColoredPoint>>,
+    scratch: Vec<Triangle<Point>>,
+}
+
+impl<'a> DocumentParser<'a> {
+    fn element(&mut self, element: &Element, parent: &Context) {
+        match &element.name[..] {
+            "svg" | "g" | "a" | "path" | "rect" | "circle" | "ellipse" | "line" | "polyline"
+            | "polygon" => (),
+            _ => return,
+        }
+        let props = &element.properties;
+        if props.get("display").map(|s| s.trim()) == Some("none") {
+            return;
+        }
+
+        let mut context = parent.clone();
+        if let Some(transform) = props.get("transform") {
+            context.transform = parse_transform(transform).then(context.transform);
+        }
+        if let Some(color) = props.get("color").and_then(|s| parse_color(s)) {
+            context.color = color;
+        }
+        if let Some(paint) = props.get("fill").and_then(|s| parse_paint(s)) {
+            context.fill = paint;
+        }
+        if let Some(paint) = props.get("stroke").and_then(|s| parse_paint(s)) {
+            context.stroke = paint;
+        }
+        if let Some(rule) = props.get("fill-rule") {
+            context.fill_rule = match rule.trim() {
+                "evenodd" => FillRule::EvenOdd,
+                _ => FillRule::NonZero,
+            };
+        }
+        if let Some(opacity) = props.get("fill-opacity").and_then(|s| opacity(s)) {
+            context.fill_opacity = opacity;
+        }
+        if let Some(opacity) = props.get("stroke-opacity").and_then(|s| opacity(s)) {
+            context.stroke_opacity = opacity;
+        }
+        // Group opacity is approximated by applying it to each descendant.
+        if let Some(opacity) = props.get("opacity").and_then(|s| opacity(s)) {
+            context.opacity *= opacity;
+        }
+        if let Some(width) = props.get("stroke-width").and_then(|s| length(s)) {
+            context.stroke_width = width;
+        }
+        let miter_limit = props.get("stroke-miterlimit").and_then(|s| length(s));
+        match props.get("stroke-linejoin").map(|s| s.trim()) {
+            Some("round") => context.join = Join::Round,
+            Some("bevel") => context.join = Join::Bevel,
+            Some("miter") | Some("miter-clip") | Some("arcs") => {
+                context.join = Join::Miter(miter_limit.unwrap_or(4.0))
+            }
+            _ => {
+                if let (Join::Miter(_), Some(limit)) = (context.join, miter_limit) {
+                    context.join = Join::Miter(limit);
+                }
+            }
+        }
+
+        match shape_path_data(element) {
+            Some(data) => self.shape(&data, &context),
+            None => {
+                for child in &element.children {
+                    self.element(child, &context);
+                }
+            }
+        }
+    }
+
+    fn shape(&mut self, data: &str, context: &Context) {
+        // Flatten curves within the tolerance in document space.
+        let [[a, b, _], [c, d, _]] = context.transform.matrix;
+        let scale = (a * d - b * c).abs().sqrt();
+        if scale == 0.0 {
+            return;
+        }
+        let subpaths = path::parse(data, self.tolerance / scale);
+        let bounding_box = match bounding_box(&subpaths) {
+            Some(rect) => rect,
+            None => return,
+        };
+
+        let fill = context.fill.clone();
+        let opacity = context.fill_opacity * context.opacity;
+        if let Some(fill) =
+            fill.and_then(|paint| self.resolve(&paint, context, bounding_box, opacity))
+        {
+            let max_step = match fill {
+                Fill::Gradient(..) => {
+                    Some(bounding_box.w().max(bounding_box.h()) / GRADIENT_RESOLUTION)
+                }
+                Fill::Color(_) => None,
+            };
+            self.scratch.clear();
+            tessellate::fill(&subpaths, context.fill_rule, max_step, &mut self.scratch);
+            self.push_scratch(&fill, context.transform);
+        }
+
+        let stroke = context.stroke.clone();
+        let opacity = context.stroke_opacity * context.opacity;
+        if context.stroke_width > 0.0 {
+            let stroke_box = bounding_box.pad(-context.stroke_width / 2.0);
+            if let Some(stroke) =
+                stroke.and_then(|paint| self.resolve(&paint, context, stroke_box, opacity))
+            {
+                self.scratch.clear();
+                tessellate::stroke(
+                    &subpaths,
+                    context.stroke_width,
+                    context.join,
+                    &mut self.scratch,
+                );
+                self.push_scratch(&stroke, context.transform);
+            }
+        }
+    }
+
+    fn push_scratch(&mut self, fill: &Fill, transform: Transform) {
+        for triangle in &self.scratch {
+            let [a, b, c] = triangle.0;
+            let vertex = |p: Point| (transform.apply(p), fill.color_at(p));
+            self.triangles
+                .push(Triangle([vertex(a), vertex(b), vertex(c)]));
+        }
+    }
+
+    fn resolve(
+        &self,
+        paint: &Paint,
+        context: &Context,
+        bounding_box: Rect,
+        opacity: f32,
+    ) -> Option<Fill> {
+        let with_opacity = |Rgba(r, g, b, a): Rgba| Fill::Color(Rgba(r, g, b, a * opacity));
+        match *paint {
+            Paint::Color(color) => Some(with_opacity(color)),
+            Paint::CurrentColor => Some(with_opacity(context.color)),
+            Paint::Url(ref id) => {
+                let gradient = self.gradient(id)?;
+                Some(Fill::Gradient(gradient, bounding_box, opacity))
+            }
+        }
+    }
+
+    // Find the given property of a gradient, following its chain of `href`s.
+    fn gradient_property(&self, element: &'a Element, name: &str) -> Option<&'a str> {
+        let mut element = element;
+        for _ in 0..MAX_HREF_DEPTH {
+            if let Some(value) = element.properties.get(name) {
+                return Some(value);
+            }
+            let href = element.properties.get("href")?;
+            element = self.elements_by_id.get(href.trim_start_matches('#'))?;
+        }
+        None
+    }
+
+    fn gradient(&self, id: &str) -> Option<Gradient> {
+        let element = *self.elements_by_id.get(id)?;
+        let bounding_box_units =
+            self.gradient_property(element, "gradientUnits") != Some("userSpaceOnUse");
+        let view_box = self.view_box;
+
+        // Percentages are relative to the bounding box or to the view box.
+        let coord = |name: &str, default: Scalar, range: Range| {
+            self.gradient_property(element, name)
+                .map(|s| {
+                    let value = length(s).unwrap_or(default);
+                    if s.trim().ends_with('%') && !bounding_box_units {
+                        range.start.min(range.end) + value * range.len()
+                    } else {
+                        value
+                    }
+                })
+                .unwrap_or_else(|| {
+                    if bounding_box_units {
+                        default
+                    } else {
+                        range.start.min(range.end) + default * range.len()
+                    }
+                })
+        };
+        let kind = match &element.name[..] {
+            "linearGradient" => GradientKind::Linear {
+                start: [coord("x1", 0.0, view_box.x), coord("y1", 0.0, view_box.y)],
+                end: [coord("x2", 1.0, view_box.x), coord("y2", 0.0, view_box.y)],
+            },
+            "radialGradient" => {
+                let diagonal = Range::new(
+                    0.0,
+                    (view_box.w().powi(2) + view_box.h().powi(2)).sqrt() / 2.0f64.sqrt(),
+                );
+                GradientKind::Radial {
+                    centre: [coord("cx", 0.5, view_box.x), coord("cy", 0.5, view_box.y)],
+                    radius: coord("r", 0.5, diagonal),
+                }
+            }
+            _ => return None,
+        };
+        let inverse_transform = self
+            .gradient_property(element, "gradientTransform")
+            .map(parse_transform)
+            .unwrap_or(Transform::IDENTITY)
+            .inverse()?;
+
+        // Use the stops of the first gradient in the `href` chain that has any.
+        let mut stops = Vec::new();
+        let mut current = element;
+        for _ in 0..MAX_HREF_DEPTH {
+            for stop in current.children.iter().filter(|c| c.name == "stop") {
+                let props = &stop.properties;
+                let offset = props.get("offset").and_then(|s| length(s)).unwrap_or(0.0) as f32;
+                // Offsets may not decrease.
+                let min_offset = stops.last().map(|&(o, _)| o).unwrap_or(0.0);
+                let offset = offset.max(min_offset).min(1.0);
+                let Rgba(r, g, b, a) = props
+                    .get("stop-color")
+                    .and_then(|s| parse_color(s))
+                    .unwrap_or(color::BLACK.to_rgb());
+                let a = a * props
+                    .get("stop-opacity")
+                    .and_then(|s| opacity(s))
+                    .unwrap_or(1.0);
+                stops.push((offset, Rgba(r, g, b, a)));
+            }
+            if !stops.is_empty() {
+                break;
+            }
+            let href = current.properties.get("href")?;
+            current = self.elements_by_id.get(href.trim_start_matches('#'))?;
+        }
+        if stops.is_empty() {
+            return None;
+        }
+        Some(Gradient {
+            kind: kind,
+            bounding_box_units: bounding_box_units,
+            inverse_transform: inverse_transform,
+            stops: stops,
+        })
+    }
+}
@@ -0,0 +1,144 @@
+//! A primitive widget for drawing Scalable Vector Graphics.
+//!
+//! The SVG document is parsed and tessellated into colored triangles once, when the widget is
+//! first instantiated or its data changes. The triangles are cached within the widget's state and
+//! are only scaled to fit the widget's `Rect` when rendering, allowing icons to remain crisp at
+//! any size or DPI.
+
+use color::Rgba;
+use position::Rect;
+use theme::Theme;
+use widget;
+use widget::triangles::{ColoredPoint, Triangle};
+use {Color, Colorable, Widget};
+
+pub use self::document::{Document, Error};
+
+pub mod document;
+mod path;
+mod tessellate;
+
+/// A widget that draws an SVG document scaled to fit its `Rect`.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct Svg<'a> {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The SVG document's source.
+    pub data: &'a str,
+    /// Unique styling for the **Svg**.
+    pub style: Style,
+}
+
+/// Unique styling for the **Svg**.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// Optionally specify a single color with which to draw the entire document.
+    ///
+    /// The alpha of each part of the document is retained, allowing monochrome icons to be
+    /// tinted, e.g. to match the `Theme`.
+    #[conrod(default = "None")]
+    pub maybe_color: Option<Option<Color>>,
+}
+
+/// Unique state for the **Svg**.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    data: String,
+    document: Option<Document>,
+}
+
+impl State {
+    /// The tessellated document, or `None` if the data could not be parsed.
+    pub fn document(&self) -> Option<&Document> {
+        self.document.as_ref()
+    }
+}
+
+impl<'a> Svg<'a> {
+    /// Construct a new **Svg** widget from the source of an SVG document.
+    ///
+    /// If the document cannot be parsed, nothing is drawn. Use `Document::parse` to inspect the
+    /// error.
+    pub fn new(data: &'a str) -> Self {
+        Svg {
+            common: widget::CommonBuilder::default(),
+            data: data,
+            style: Style::default(),
+        }
+    }
+}
+
+impl<'a> Widget for Svg<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State {
+            data: String::new(),
+            document: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { state, .. } = args;
+        let Svg { data, .. } = self;
+
+        // Only parse and tessellate the document when its data changes.
+        if state.data != data {
+            let document = Document::parse(data).ok();
+            state.update(|state| {
+                state.data = data.to_owned();
+                state.document = document;
+            });
+        }
+    }
+}
+
+impl<'a> Colorable for Svg<'a> {
+    fn color(mut self, color: Color) -> Self {
+        self.style.maybe_color = Some(Some(color));
+        self
+    }
+}
+
+/// Fill `triangles` with the document of the given `State` fitted within `rect`.
+///
+/// As with the default `preserveAspectRatio` of SVG, the document's view box is scaled uniformly
+/// to fit within the `rect` and centred.
+pub fn triangles(
+    rect: Rect,
+    state: &State,
+    style: &Style,
+    theme: &Theme,
+    triangles: &mut Vec<Triangle<ColoredPoint>>,
+) {
+    let document = match state.document {
+        Some(ref document) => document,
+        None => return,
+    };
+    let view_box = document.view_box;
+    let scale = (rect.w() / view_box.w()).min(rect.h() / view_box.h());
+    let [view_x, view_y] = view_box.xy();
+    let [x, y] = rect.xy();
+    let tint = style.maybe_color(theme).map(|color| color.to_rgb());
+
+    // Map from SVG user space, where *y* increases downwards.
+    let map = |(p, color): ColoredPoint| {
+        let p = [x + (p[0] - view_x) * scale, y - (p[1] - view_y) * scale];
+        let color = match tint {
+            Some(Rgba(r, g, b, a)) => Rgba(r, g, b, a * color.3),
+            None => color,
+        };
+        (p, color)
+    };
+    triangles.extend(document.triangles.iter().map(|t| {
+        let [a, b, c] = t.0;
+        Triangle([map(a), map(b), map(c)])
+    }));
+}
@@ -0,0 +1,413 @@
+//! Parsing of SVG path data along with the flattening of its curves into line segments.
+
+use position::{Point, Scalar};
+use std::f64::consts::PI;
+
+/// The maximum number of line segments produced when flattening a single curve.
+const MAX_CURVE_SEGMENTS: usize = 1024;
+
+/// A sequence of connected points flattened from SVG path data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubPath {
+    /// The points along the sub-path.
+    pub points: Vec<Point>,
+    /// Whether or not the sub-path was closed with a `Z` command.
+    pub closed: bool,
+}
+
+/// A simple cursor over the bytes of some SVG attribute.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser {
+            bytes: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).cloned()
+    }
+
+    fn skip_separators(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') | Some(b',') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.bytes.len()
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Option<Scalar> {
+        self.skip_separators();
+        let start = self.pos;
+        if let Some(b'-') | Some(b'+') = self.peek() {
+            self.pos += 1;
+        }
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            digits += self.skip_digits();
+        }
+        if digits == 0 {
+            self.pos = start;
+            return None;
+        }
+        // Only consume the exponent if it is followed by digits, e.g. not the `e` of `em`.
+        if let Some(b'e') | Some(b'E') = self.peek() {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if let Some(b'-') | Some(b'+') = self.peek() {
+                self.pos += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.pos = mantissa_end;
+            }
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+    }
+
+    fn numbers(&mut self, numbers: &mut [Scalar]) -> bool {
+        for n in numbers.iter_mut() {
+            match self.number() {
+                Some(number) => *n = number,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    // Arc flags may be written without any separators, e.g. `a1 1 0 00 1 1`.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+}
+
+/// Parse a list of numbers separated by whitespace and/or commas, e.g. a `viewBox` or `points`.
+///
+/// Parsing stops at the first invalid number.
+pub fn parse_numbers(s: &str) -> Vec<Scalar> {
+    let mut parser = Parser::new(s);
+    let mut numbers = Vec::new();
+    while let Some(n) = parser.number() {
+        numbers.push(n);
+    }
+    numbers
+}
+
+/// Parse the given SVG path data, flattening curves so that the line segments produced deviate
+/// from the true curve by no more than roughly `tolerance`.
+///
+/// As recommended by the SVG specification, parsing stops at the first error and the sub-paths
+/// described up until that point are returned.
+pub fn parse(data: &str, tolerance: Scalar) -> Vec<SubPath> {
+    let mut parser = Parser::new(data);
+    let mut subpaths: Vec<SubPath> = Vec::new();
+    let mut points: Vec<Point> = Vec::new();
+    let mut current = [0.0, 0.0];
+    let mut start = [0.0, 0.0];
+    // The reflected control points used by the smooth curve commands.
+    let mut last_cubic_ctrl: Option<Point> = None;
+    let mut last_quad_ctrl: Option<Point> = None;
+    let mut command: Option<u8> = None;
+
+    fn finish(subpaths: &mut Vec<SubPath>, points: &mut Vec<Point>, closed: bool) {
+        if points.len() > 1 {
+            let points = std::mem::take(points);
+            subpaths.push(SubPath { points, closed });
+        }
+        points.clear();
+    }
+
+    while !parser.at_end() {
+        // Commands may be repeated implicitly by following them with more numbers.
+        let c = match parser.peek() {
+            Some(b) if b.is_ascii_alphabetic() => {
+                parser.pos += 1;
+                b
+            }
+            _ => match command {
+                Some(b'M') => b'L',
+                Some(b'm') => b'l',
+                Some(b'Z') | Some(b'z') | None => break,
+                Some(c) => c,
+            },
+        };
+        command = Some(c);
+        let relative = c.is_ascii_lowercase();
+        let offset = |p: Point, current: Point| {
+            if relative {
+                [p[0] + current[0], p[1] + current[1]]
+            } else {
+                p
+            }
+        };
+        // Drawing after a `Z` begins a new sub-path at the start of the last one.
+        if points.is_empty() && !c.eq_ignore_ascii_case(&b'M') {
+            points.push(current);
+        }
+        let mut n = [0.0; 6];
+        let (mut cubic_ctrl, mut quad_ctrl) = (None, None);
+        match c.to_ascii_uppercase() {
+            b'M' => {
+                if !parser.numbers(&mut n[..2]) {
+                    break;
+                }
+                finish(&mut subpaths, &mut points, false);
+                current = offset([n[0], n[1]], current);
+                start = current;
+                points.push(current);
+            }
+            b'L' => {
+                if !parser.numbers(&mut n[..2]) {
+                    break;
+                }
+                current = offset([n[0], n[1]], current);
+                line_to(&mut points, current);
+            }
+            b'H' => {
+                if !parser.numbers(&mut n[..1]) {
+                    break;
+                }
+                current[0] = if relative { current[0] + n[0] } else { n[0] };
+                line_to(&mut points, current);
+            }
+            b'V' => {
+                if !parser.numbers(&mut n[..1]) {
+                    break;
+                }
+                current[1] = if relative { current[1] + n[0] } else { n[0] };
+                line_to(&mut points, current);
+            }
+            b'C' | b'S' => {
+                let ctrl_a = if c.eq_ignore_ascii_case(&b'C') {
+                    if !parser.numbers(&mut n[..6]) {
+                        break;
+                    }
+                    offset([n[0], n[1]], current)
+                } else {
+                    if !parser.numbers(&mut n[2..6]) {
+                        break;
+                    }
+                    reflect(last_cubic_ctrl, current)
+                };
+                let ctrl_b = offset([n[2], n[3]], current);
+                let end = offset([n[4], n[5]], current);
+                cubic_to(&mut points, current, ctrl_a, ctrl_b, end, tolerance);
+                cubic_ctrl = Some(ctrl_b);
+                current = end;
+            }
+            b'Q' | b'T' => {
+                let ctrl = if c.eq_ignore_ascii_case(&b'Q') {
+                    if !parser.numbers(&mut n[..4]) {
+                        break;
+                    }
+                    offset([n[0], n[1]], current)
+                } else {
+                    if !parser.numbers(&mut n[2..4]) {
+                        break;
+                    }
+                    reflect(last_quad_ctrl, current)
+                };
+                let end = offset([n[2], n[3]], current);
+                quad_to(&mut points, current, ctrl, end, tolerance);
+                quad_ctrl = Some(ctrl);
+                current = end;
+            }
+            b'A' => {
+                if !parser.numbers(&mut n[..3]) {
+                    break;
+                }
+                let (large_arc, sweep) = match (parser.flag(), parser.flag()) {
+                    (Some(large_arc), Some(sweep)) => (large_arc, sweep),
+                    _ => break,
+                };
+                if !parser.numbers(&mut n[3..5]) {
+                    break;
+                }
+                let end = offset([n[3], n[4]], current);
+                let flags = (large_arc, sweep);
+                arc_to(
+                    &mut points,
+                    current,
+                    [n[0], n[1]],
+                    n[2],
+                    flags,
+                    end,
+                    tolerance,
+                );
+                current = end;
+            }
+            b'Z' => {
+                if !points.is_empty() {
+                    line_to(&mut points, start);
+                }
+                finish(&mut subpaths, &mut points, true);
+                current = start;
+            }
+            _ => break,
+        }
+        last_cubic_ctrl = cubic_ctrl;
+        last_quad_ctrl = quad_ctrl;
+    }
+
+    finish(&mut subpaths, &mut points, false);
+    subpaths
+}
+
+// Add a line segment to `p`, skipping it if it would have no length.
+fn line_to(points: &mut Vec<Point>, p: Point) {
+    if points.last() != Some(&p) {
+        points.push(p);
+    }
+}
+
+// Reflect the previous control point about the current point.
+fn reflect(ctrl: Option<Point>, current: Point) -> Point {
+    match ctrl {
+        Some(ctrl) => [2.0 * current[0] - ctrl[0], 2.0 * current[1] - ctrl[1]],
+        None => current,
+    }
+}
+
+fn len(v: [Scalar; 2]) -> Scalar {
+    (v[0] * v[0] + v[1] * v[1]).sqrt()
+}
+
+// The number of segments required to flatten a curve whose control polygon has the given
+// maximum second difference.
+fn segment_count(second_difference: Scalar, scale: Scalar, tolerance: Scalar) -> usize {
+    let n = (second_difference * scale / tolerance).sqrt().ceil();
+    if n.is_finite() && n > 1.0 {
+        (n as usize).min(MAX_CURVE_SEGMENTS)
+    } else {
+        1
+    }
+}
+
+fn quad_to(points: &mut Vec<Point>, a: Point, b: Point, c: Point, tolerance: Scalar) {
+    let dd = len([a[0] - 2.0 * b[0] + c[0], a[1] - 2.0 * b[1] + c[1]]);
+    let n = segment_count(dd, 0.25, tolerance);
+    for i in 1..n + 1 {
+        let t = i as Scalar / n as Scalar;
+        let mt = 1.0 - t;
+        let p = [
+            mt * mt * a[0] + 2.0 * mt * t * b[0] + t * t * c[0],
+            mt * mt * a[1] + 2.0 * mt * t * b[1] + t * t * c[1],
+        ];
+        line_to(points, p);
+    }
+}
+
+fn cubic_to(points: &mut Vec<Point>, a: Point, b: Point, c: Point, d: Point, tolerance: Scalar) {
+    let dd = len([a[0] - 2.0 * b[0] + c[0], a[1] - 2.0 * b[1] + c[1]])
+        .max(len([b[0] - 2.0 * c[0] + d[0], b[1] - 2.0 * c[1] + d[1]]));
+    let n = segment_count(dd, 0.75, tolerance);
+    for i in 1..n + 1 {
+        let t = i as Scalar / n as Scalar;
+        let mt = 1.0 - t;
+        let (w0, w1, w2, w3) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+        let p = [
+            w0 * a[0] + w1 * b[0] + w2 * c[0] + w3 * d[0],
+            w0 * a[1] + w1 * b[1] + w2 * c[1] + w3 * d[1],
+        ];
+        line_to(points, p);
+    }
+}
+
+// Flatten an elliptical arc, converting from the endpoint to the centre parameterization as
+// described in the implementation notes of the SVG specification.
+fn arc_to(
+    points: &mut Vec<Point>,
+    from: Point,
+    radii: [Scalar; 2],
+    x_axis_rotation: Scalar,
+    (large_arc, sweep): (bool, bool),
+    to: Point,
+    tolerance: Scalar,
+) {
+    let (mut rx, mut ry) = (radii[0].abs(), radii[1].abs());
+    if from == to {
+        return;
+    }
+    if rx == 0.0 || ry == 0.0 {
+        line_to(points, to);
+        return;
+    }
+    let (sin, cos) = x_axis_rotation.to_radians().sin_cos();
+    let (dx, dy) = ((from[0] - to[0]) / 2.0, (from[1] - to[1]) / 2.0);
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    // Scale up the radii if they are too small to span the endpoints.
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coef = sign * (num / den).max(0.0).sqrt();
+    let cx1 = coef * rx * y1 / ry;
+    let cy1 = -coef * ry * x1 / rx;
+    let cx = cos * cx1 - sin * cy1 + (from[0] + to[0]) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from[1] + to[1]) / 2.0;
+
+    let angle = |u: [Scalar; 2], v: [Scalar; 2]| {
+        (u[0] * v[1] - u[1] * v[0]).atan2(u[0] * v[0] + u[1] * v[1])
+    };
+    let u = [(x1 - cx1) / rx, (y1 - cy1) / ry];
+    let v = [(-x1 - cx1) / rx, (-y1 - cy1) / ry];
+    let start_angle = angle([1.0, 0.0], u);
+    let mut sweep_angle = angle(u, v);
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= 2.0 * PI;
+    } else if sweep && sweep_angle < 0.0 {
+        sweep_angle += 2.0 * PI;
+    }
+
+    // Choose the angular step so that the sagitta of each segment is within the tolerance.
+    let r = rx.max(ry);
+    let step = 2.0 * (1.0 - (tolerance / r).min(1.0)).acos();
+    let n = if step > 0.0 {
+        ((sweep_angle.abs() / step).ceil() as usize).max(1)
+    } else {
+        MAX_CURVE_SEGMENTS
+    };
+    let n = n.min(MAX_CURVE_SEGMENTS);
+    for i in 1..n {
+        let t = start_angle + sweep_angle * i as Scalar / n as Scalar;
+        let (t_sin, t_cos) = t.sin_cos();
+        let p = [
+            cx + rx * t_cos * cos - ry * t_sin * sin,
+            cy + rx * t_cos * sin + ry * t_sin * cos,
+        ];
+        line_to(points, p);
+    }
+    line_to(points, to);
+}
@@ -0,0 +1,196 @@
+//! Tessellation of flattened SVG paths into triangles.
+
+use super::path::SubPath;
+use position::{Point, Scalar};
+use std::cmp::Ordering;
+use widget::point_path::{self, Join};
+use widget::triangles::Triangle;
+
+/// The rule used to determine whether a point lies within the fill of a path.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside if the path winds around it a non-zero number of times.
+    NonZero,
+    /// A point is inside if a ray from it crosses the path an odd number of times.
+    EvenOdd,
+}
+
+// A non-horizontal edge of a path, where `top` has the smaller *y*.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    top: Point,
+    bottom: Point,
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: Scalar) -> Scalar {
+        let t = (y - self.top[1]) / (self.bottom[1] - self.top[1]);
+        self.top[0] + (self.bottom[0] - self.top[0]) * t
+    }
+}
+
+impl FillRule {
+    fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+// The *y* at which two edges cross, if they cross strictly between their endpoints.
+fn intersection_y(a: &Edge, b: &Edge) -> Option<Scalar> {
+    let top = a.top[1].max(b.top[1]);
+    let bottom = a.bottom[1].min(b.bottom[1]);
+    if top >= bottom {
+        return None;
+    }
+    let d_top = a.x_at(top) - b.x_at(top);
+    let d_bottom = a.x_at(bottom) - b.x_at(bottom);
+    if d_top * d_bottom >= 0.0 {
+        return None;
+    }
+    Some(top + (bottom - top) * d_top / (d_top - d_bottom))
+}
+
+/// Fill the area within the given sub-paths, each of which is implicitly closed.
+///
+/// The area is divided into horizontal bands at every vertex and edge intersection, within which
+/// the inside spans are emitted as trapezoids. When `max_step` is `Some`, bands and spans are
+/// further divided so that no trapezoid is larger than `max_step` along either axis. This is
+/// useful when colors are to be sampled at each vertex, e.g. for gradients.
+pub fn fill(
+    subpaths: &[SubPath],
+    rule: FillRule,
+    max_step: Option<Scalar>,
+    triangles: &mut Vec<Triangle<Point>>,
+) {
+    let mut edges = Vec::new();
+    for subpath in subpaths {
+        let points = &subpath.points;
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            if a[1] < b[1] {
+                edges.push(Edge {
+                    top: a,
+                    bottom: b,
+                    winding: 1,
+                });
+            } else if a[1] > b[1] {
+                edges.push(Edge {
+                    top: b,
+                    bottom: a,
+                    winding: -1,
+                });
+            }
+        }
+    }
+    if edges.is_empty() {
+        return;
+    }
+    edges.sort_by(|a, b| a.top[1].partial_cmp(&b.top[1]).unwrap_or(Ordering::Equal));
+
+    // Collect the *y* of every vertex and intersection.
+    let mut ys = Vec::with_capacity(edges.len() * 2);
+    for (i, a) in edges.iter().enumerate() {
+        ys.push(a.top[1]);
+        ys.push(a.bottom[1]);
+        for b in &edges[i + 1..] {
+            if b.top[1] >= a.bottom[1] {
+                break;
+            }
+            if let Some(y) = intersection_y(a, b) {
+                ys.push(y);
+            }
+        }
+    }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    ys.dedup();
+
+    let mut active: Vec<(Scalar, Scalar, i32)> = Vec::new();
+    for window in ys.windows(2) {
+        let (band_top, band_bottom) = (window[0], window[1]);
+        let bands = match max_step {
+            Some(step) if step > 0.0 => ((band_bottom - band_top) / step).ceil().max(1.0) as usize,
+            _ => 1,
+        };
+        for band in 0..bands {
+            let y0 = band_top + (band_bottom - band_top) * band as Scalar / bands as Scalar;
+            let y1 = band_top + (band_bottom - band_top) * (band + 1) as Scalar / bands as Scalar;
+
+            // The edges spanning the band, sorted by their *x* at its centre.
+            active.clear();
+            for edge in &edges {
+                if edge.top[1] >= y1 {
+                    break;
+                }
+                if edge.bottom[1] > y0 {
+                    active.push((edge.x_at(y0), edge.x_at(y1), edge.winding));
+                }
+            }
+            active.sort_by(|a, b| {
+                (a.0 + a.1)
+                    .partial_cmp(&(b.0 + b.1))
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            let mut winding = 0;
+            let mut left = None;
+            for &(x0, x1, edge_winding) in &active {
+                let was_inside = rule.is_inside(winding);
+                winding += edge_winding;
+                match (was_inside, rule.is_inside(winding)) {
+                    (false, true) => left = Some((x0, x1)),
+                    (true, false) => {
+                        if let Some(left) = left.take() {
+                            trapezoid(left, (x0, x1), (y0, y1), max_step, triangles);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+// Emit the trapezoid between the `left` and `right` edges, each given by their *x* at the top and
+// bottom of the band.
+fn trapezoid(
+    left: (Scalar, Scalar),
+    right: (Scalar, Scalar),
+    (y0, y1): (Scalar, Scalar),
+    max_step: Option<Scalar>,
+    triangles: &mut Vec<Triangle<Point>>,
+) {
+    let width = (right.0 - left.0).max(right.1 - left.1);
+    let columns = match max_step {
+        Some(step) if step > 0.0 => (width / step).ceil().max(1.0) as usize,
+        _ => 1,
+    };
+    let lerp = |a: Scalar, b: Scalar, i: usize| a + (b - a) * i as Scalar / columns as Scalar;
+    for i in 0..columns {
+        let top_l = [lerp(left.0, right.0, i), y0];
+        let top_r = [lerp(left.0, right.0, i + 1), y0];
+        let bottom_l = [lerp(left.1, right.1, i), y1];
+        let bottom_r = [lerp(left.1, right.1, i + 1), y1];
+        triangles.push(Triangle([top_l, top_r, bottom_l]));
+        triangles.push(Triangle([top_r, bottom_r, bottom_l]));
+    }
+}
+
+/// Stroke each of the given sub-paths with the given thickness and join.
+pub fn stroke(
+    subpaths: &[SubPath],
+    thickness: Scalar,
+    join: Join,
+    triangles: &mut Vec<Triangle<Point>>,
+) {
+    for subpath in subpaths {
+        // Closed sub-paths end where they began, so `point_path` joins their first corner too.
+        let points = subpath.points.iter().cloned();
+        if let Some(tris) = point_path::triangles(points, join, thickness) {
+            triangles.extend(tris);
+        }
+    }
+}
@@ -1,11 +1,18 @@
 //! The primitive widget used for displaying text.
 
-use position::{Dimension, Scalar};
+use copypasta::{ClipboardContext, ClipboardProvider};
+use cursor;
+use event;
+use input;
+use position::{Align, Dimension, Point, Rect, Scalar};
 use std;
+use std::borrow::Cow;
 use text;
+use theme::FontOptions;
+use unicode_segmentation::UnicodeSegmentation;
 use utils;
 use widget;
-use {Color, Colorable, FontSize, Ui, Widget};
+use {Color, Colorable, FontSize, Theme, Ui, Widget};
 
 /// Displays some given text centered within a rectangular area.
 ///
@@ -43,15 +50,49 @@ pub struct Style {
     #[conrod(default = "text::Justify::Left")]
     pub justify: Option<text::Justify>,
     /// The id of the font to use for rendering and layout.
-    #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
-    // /// The line styling for the text.
-    // #[conrod(default = "None")]
-    // pub line: Option<Option<Line>>,
+    /// The font family from which a font is selected when no `font_id` is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the font selected from the `font_family`.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the font selected from the `font_family`.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// Whether or not lines that exceed the width should be truncated with an ellipsis.
+    ///
+    /// When some `Truncate` mode is given, lines are never wrapped.
+    #[conrod(default = "None")]
+    pub maybe_truncate: Option<Option<Truncate>>,
+    /// The line drawn beneath each line of text, if any.
+    #[conrod(default = "None")]
+    pub underline: Option<Option<Underline>>,
+    /// Whether or not a line is drawn through the middle of the text.
+    #[conrod(default = "false")]
+    pub strikethrough: Option<bool>,
+    /// The color with which the area behind each line of text is highlighted, if any.
+    #[conrod(default = "None")]
+    pub background: Option<Option<Color>>,
+    /// The color of the underline and strikethrough. Defaults to the color of the text.
+    #[conrod(default = "None")]
+    pub decoration_color: Option<Option<Color>>,
+    /// The stroke drawn around each glyph, if any.
+    #[conrod(default = "None")]
+    pub outline: Option<Option<Outline>>,
+    /// The shadow drawn beneath the glyphs, if any.
+    #[conrod(default = "None")]
+    pub drop_shadow: Option<Option<DropShadow>>,
+    /// Whether or not the text may be selected with the mouse and keyboard and copied.
+    #[conrod(default = "false")]
+    pub selectable: Option<bool>,
+    /// The color with which selected text is highlighted. Defaults to a translucent highlight of
+    /// the color of the text.
+    #[conrod(default = "None")]
+    pub selection_color: Option<Option<Color>>,
 }
 
 /// The way in which text should wrap around the width.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 pub enum Wrap {
     /// Wrap at the first character that exceeds the width.
     Character,
@@ -59,23 +100,449 @@ pub enum Wrap {
     Whitespace,
 }
 
-// /// Line styling for the **Text**.
-// pub enum Line {
-//     /// Underline the text.
-//     Under,
-//     /// Overline the text.
-//     Over,
-//     /// Strikethrough the text.
-//     Through,
-// }
+/// The way in which lines of text that exceed the width should be shortened with an ellipsis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Truncate {
+    /// Keep the start of the line and replace the end with an ellipsis.
+    End,
+    /// Keep the end of the line and replace the start with an ellipsis.
+    Start,
+    /// Keep both ends of the line and replace the middle with an ellipsis.
+    ///
+    /// Useful for file paths, where both the root and the file name are of interest.
+    Middle,
+}
+
+/// The character used to mark the elided portion of a truncated line.
+pub const ELLIPSIS: &str = "\u{2026}";
+
+/// The style of line drawn beneath underlined text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Underline {
+    /// A single straight line.
+    Single,
+    /// Two parallel straight lines.
+    Double,
+    /// A zig-zagging line, as is commonly used to mark spelling errors.
+    Wavy,
+}
+
+/// A stroke drawn around each glyph so that text remains legible over any background.
+///
+/// The outline is drawn as several passes of the glyphs in the outline's color, each offset
+/// around a ring, beneath the text itself.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Outline {
+    /// The width of the stroke.
+    pub width: Scalar,
+    /// The color of the stroke.
+    pub color: Color,
+}
+
+/// A copy of the glyphs drawn in a single color at some offset beneath the text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DropShadow {
+    /// The offset of the shadow from the text.
+    pub offset: Point,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+/// The placement of text decorations relative to the baseline of a line, derived from the vertical
+/// metrics of a font.
+///
+/// Positive offsets lie above the baseline while negative offsets lie below it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DecorationMetrics {
+    /// The offset of the top of a highlighted background.
+    pub ascent: Scalar,
+    /// The offset of the bottom of a highlighted background.
+    pub descent: Scalar,
+    /// The thickness of underlines.
+    pub thickness: Scalar,
+    /// The offset of the centre of an underline.
+    pub underline: Scalar,
+    /// The offset of the centre of a strikethrough.
+    pub strikethrough: Scalar,
+    /// The thickness of strikethroughs.
+    pub strikethrough_thickness: Scalar,
+}
 
 /// The state to be stored between updates for the **Text**.
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     /// An owned version of the string.
     pub string: String,
-    /// The indices and width for each line of text within the `string`.
+    /// The text as displayed if it had to be truncated to fit the width.
+    ///
+    /// `string` always retains the full text, e.g. for use within a tooltip.
+    pub truncated: Option<String>,
+    /// The indices and width for each line of text within the displayed string.
     pub line_infos: Vec<text::line::Info>,
+    /// The selected range of the displayed string, if the **Text** is selectable.
+    ///
+    /// The first index is the anchor at which the selection began, while the second follows the
+    /// mouse or keyboard and may come before it. The two are equal while nothing is selected.
+    pub selection: Option<(text::cursor::Index, text::cursor::Index)>,
+    /// Whether or not a selection is currently being dragged out with the mouse.
+    pub selecting: bool,
+}
+
+impl Style {
+    /// The id of the font used to display the text.
+    ///
+    /// A specified `font_id` takes precedence, otherwise the font is selected from the
+    /// `font_family` by weight and style. Falls back to the `Theme`'s font as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
+impl State {
+    /// The string as displayed, i.e. the truncated text if any truncation occurred.
+    pub fn displayed(&self) -> &str {
+        match self.truncated {
+            Some(ref truncated) => truncated,
+            None => &self.string,
+        }
+    }
+
+    /// The selected portion of the string, if any text is selected.
+    ///
+    /// The selection is made over the displayed string, however the text is taken from the full
+    /// `string`, so that selecting the ellipsis of truncated text selects the text that it elides.
+    pub fn selected(&self) -> Option<&str> {
+        let (start, end) = self.selection?;
+        selected(&self.string, self.displayed(), &self.line_infos, start, end)
+    }
+}
+
+// The portion of the full `text` between the given cursor indices over its `displayed` text, or
+// `None` if it is empty.
+fn selected<'t>(
+    text: &'t str,
+    displayed: &str,
+    line_infos: &[text::line::Info],
+    start: text::cursor::Index,
+    end: text::cursor::Index,
+) -> Option<&'t str> {
+    let range = selected_range(displayed, line_infos, start, end)?;
+    Some(&text[source_range(text, displayed, range)])
+}
+
+// The byte range of `text` between the given cursor indices, or `None` if it is empty.
+fn selected_range(
+    text: &str,
+    line_infos: &[text::line::Info],
+    start: text::cursor::Index,
+    end: text::cursor::Index,
+) -> Option<std::ops::Range<usize>> {
+    let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
+    let byte = |index| {
+        let char = text::glyph::index_after_cursor(line_infos.iter().cloned(), index)?;
+        Some(
+            text.char_indices()
+                .nth(char)
+                .map_or(text.len(), |(byte, _)| byte),
+        )
+    };
+    let (start, end) = (byte(start)?, byte(end)?);
+    if start == end {
+        return None;
+    }
+    Some(start..end)
+}
+
+// Map the given byte `range` of the `displayed` text to the range of the full `text` from which it
+// was truncated, such that a range spanning an ellipsis spans the graphemes that it elides.
+fn source_range(
+    text: &str,
+    displayed: &str,
+    range: std::ops::Range<usize>,
+) -> std::ops::Range<usize> {
+    // Map a byte offset within a displayed line to the full line.
+    let line_byte = |line: &str, displayed_line: &str, byte: usize| {
+        if line == displayed_line {
+            return byte;
+        }
+        // Find the ellipsis separating the line's kept head from its kept tail.
+        let ellipsis = displayed_line
+            .match_indices(ELLIPSIS)
+            .map(|(i, _)| i)
+            .find(|&i| {
+                line.starts_with(&displayed_line[..i])
+                    && line.ends_with(&displayed_line[i + ELLIPSIS.len()..])
+            });
+        match ellipsis {
+            Some(i) if byte <= i => byte,
+            Some(_) => line.len() - (displayed_line.len() - byte),
+            None => std::cmp::min(byte, line.len()),
+        }
+    };
+    let byte = |byte: usize| {
+        let (mut text_start, mut displayed_start) = (0, 0);
+        for (line, displayed_line) in text.split('\n').zip(displayed.split('\n')) {
+            let displayed_end = displayed_start + displayed_line.len();
+            if byte <= displayed_end {
+                return text_start + line_byte(line, displayed_line, byte - displayed_start);
+            }
+            text_start += line.len() + 1;
+            displayed_start = displayed_end + 1;
+        }
+        text.len()
+    };
+    byte(range.start)..byte(range.end)
+}
+
+impl Outline {
+    /// The number of directions in which the glyphs are offset for each ring of the outline.
+    const DIRECTIONS: usize = 8;
+
+    /// The greatest number of rings drawn, regardless of the width of the outline.
+    const MAX_RINGS: usize = 3;
+
+    /// The number of glyph passes used to draw the outline.
+    ///
+    /// A ring of passes is drawn for every whole pixel of width, so that narrow outlines have no
+    /// gaps, up to three rings spread evenly across the width of wider outlines.
+    pub fn passes(&self) -> usize {
+        self.rings() * Self::DIRECTIONS
+    }
+
+    /// The offset of the glyphs for the given pass, or `None` if `pass` is out of range.
+    ///
+    /// Each ring offsets the glyphs by a whole number of pixels towards each of the eight
+    /// neighbouring pixels, so that every pass samples the glyph cache alike.
+    pub fn offset(&self, pass: usize) -> Option<Point> {
+        if pass >= self.passes() {
+            return None;
+        }
+        let rings = self.rings();
+        let ring = pass / Self::DIRECTIONS;
+        let radius = (self.pixel_width() * (ring + 1) as Scalar / rings as Scalar).round();
+        let direction = (pass % Self::DIRECTIONS) as Scalar;
+        let angle = direction * std::f64::consts::PI * 2.0 / Self::DIRECTIONS as Scalar;
+        Some([angle.cos().round() * radius, angle.sin().round() * radius])
+    }
+
+    // The width of the outline rounded to a whole number of pixels, at least one.
+    fn pixel_width(&self) -> Scalar {
+        self.width.round().max(1.0)
+    }
+
+    // The number of rings of passes, one per pixel of width up to `MAX_RINGS`.
+    fn rings(&self) -> usize {
+        std::cmp::min(self.pixel_width() as usize, Self::MAX_RINGS)
+    }
+}
+
+impl DecorationMetrics {
+    /// The decoration metrics for the given font at the given size.
+    ///
+    /// The underline and strikethrough are placed using ratios of the font's ascent and descent.
+    pub fn new(font: &text::Font, font_size: FontSize) -> Self {
+        Self::from_tables(font, text::font::Decorations::default(), font_size)
+    }
+
+    /// The decoration metrics for the given font at the given size, placing the underline and
+    /// strikethrough as described by the font's `decorations` tables.
+    ///
+    /// Any decoration missing from the tables is placed as by `DecorationMetrics::new`.
+    pub fn from_tables(
+        font: &text::Font,
+        decorations: text::font::Decorations,
+        font_size: FontSize,
+    ) -> Self {
+        let scale = text::Scale::uniform(text::pt_to_px(font_size));
+        let v_metrics = font.v_metrics(scale);
+        let ascent = v_metrics.ascent as Scalar;
+        let descent = v_metrics.descent as Scalar;
+        let fallback_thickness = ((ascent - descent) / 20.0).max(1.0);
+
+        // Font units are scaled in the same way as rusttype scales the vertical metrics.
+        let unscaled = font.v_metrics_unscaled();
+        let units = (unscaled.ascent - unscaled.descent) as Scalar;
+        let px_per_unit = if units > 0.0 {
+            (ascent - descent) / units
+        } else {
+            0.0
+        };
+        // The tables give the top of each stroke, while the metrics describe its centre.
+        let stroke = |(position, thickness): (i16, i16)| {
+            let thickness = (Scalar::from(thickness) * px_per_unit).max(1.0);
+            let top = Scalar::from(position) * px_per_unit;
+            (top - thickness / 2.0, thickness)
+        };
+        let (underline, thickness) = decorations
+            .underline
+            .map(&stroke)
+            .unwrap_or((descent / 2.0, fallback_thickness));
+        // Roughly half of the x-height.
+        let (strikethrough, strikethrough_thickness) = decorations
+            .strikeout
+            .map(&stroke)
+            .unwrap_or((ascent / 4.0, fallback_thickness));
+        DecorationMetrics {
+            ascent: ascent,
+            descent: descent,
+            thickness: thickness,
+            underline: underline,
+            strikethrough: strikethrough,
+            strikethrough_thickness: strikethrough_thickness,
+        }
+    }
+}
+
+/// Extend `triangles` with the highlighted background of each of the given line `Rect`s, where
+/// the bottom of each `Rect` lies on the line's baseline.
+pub fn background_triangles<I>(
+    line_rects: I,
+    metrics: DecorationMetrics,
+    triangles: &mut Vec<widget::triangles::Triangle<Point>>,
+) where
+    I: IntoIterator<Item = Rect>,
+{
+    for rect in line_rects {
+        if rect.w() <= 0.0 {
+            continue;
+        }
+        let (l, r, baseline) = (rect.left(), rect.right(), rect.bottom());
+        let (b, t) = (baseline + metrics.descent, baseline + metrics.ascent);
+        let (a, b) = widget::triangles::from_quad([[l, b], [l, t], [r, t], [r, b]]);
+        triangles.push(a);
+        triangles.push(b);
+    }
+}
+
+/// Extend `triangles` with the underline and strikethrough of each of the given line `Rect`s,
+/// where the bottom of each `Rect` lies on the line's baseline.
+pub fn line_triangles<I>(
+    line_rects: I,
+    metrics: DecorationMetrics,
+    underline: Option<Underline>,
+    strikethrough: bool,
+    triangles: &mut Vec<widget::triangles::Triangle<Point>>,
+) where
+    I: IntoIterator<Item = Rect>,
+{
+    use widget::line::Join;
+
+    let thickness = metrics.thickness;
+    let mut line = |points: &[Point], thickness| {
+        let points = points.iter().cloned();
+        if let Some(tris) = widget::point_path::triangles(points, Join::Bevel, thickness) {
+            triangles.extend(tris);
+        }
+    };
+
+    for rect in line_rects {
+        if rect.w() <= 0.0 {
+            continue;
+        }
+        let (l, r, baseline) = (rect.left(), rect.right(), rect.bottom());
+        let y = baseline + metrics.underline;
+        match underline {
+            None => (),
+            Some(Underline::Single) => line(&[[l, y], [r, y]], thickness),
+            Some(Underline::Double) => {
+                let y = y + thickness;
+                line(&[[l, y], [r, y]], thickness);
+                let y = y - thickness * 2.5;
+                line(&[[l, y], [r, y]], thickness);
+            }
+            Some(Underline::Wavy) => {
+                // Zig-zag between peaks and troughs a thickness either side of the underline.
+                let step = thickness * 2.0;
+                let mut points = vec![];
+                let mut x = l;
+                let mut up = true;
+                while x < r {
+                    let offset = if up { thickness } else { -thickness };
+                    points.push([x, y + offset]);
+                    x += step;
+                    up = !up;
+                }
+                points.push([r, y]);
+                line(&points, thickness);
+            }
+        }
+        if strikethrough {
+            let y = baseline + metrics.strikethrough;
+            line(&[[l, y], [r, y]], metrics.strikethrough_thickness);
+        }
+    }
+}
+
+/// Shorten each line of `text` that is wider than `max_width` by replacing some of its graphemes
+/// with an ellipsis in accordance with the given `Truncate` mode.
+///
+/// Widths are measured with `text::line::width`. Lines that already fit are left untouched and if
+/// no line needed truncating the original text is returned.
+pub fn truncate<'t, 'a, C>(
+    text: &'t str,
+    mode: Truncate,
+    font: C,
+    font_size: FontSize,
+    max_width: Scalar,
+) -> Cow<'t, str>
+where
+    C: Into<text::font::Chain<'a>>,
+{
+    let font = font.into();
+    let fits = |line: &str| text::line::width(line, font, font_size) <= max_width;
+    if text.split('\n').all(&fits) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut truncated = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            truncated.push('\n');
+        }
+        if fits(line) {
+            truncated.push_str(line);
+            continue;
+        }
+
+        // Binary search for the greatest number of graphemes that may be kept.
+        let graphemes: Vec<&str> = line.graphemes(true).collect();
+        let candidate = |keep: usize| {
+            let (head, tail) = match mode {
+                Truncate::End => (keep, 0),
+                Truncate::Start => (0, keep),
+                Truncate::Middle => (keep - keep / 2, keep / 2),
+            };
+            let mut s = String::new();
+            s.extend(graphemes[..head].iter().cloned());
+            let s_len = s.trim_end().len();
+            s.truncate(s_len);
+            s.push_str(ELLIPSIS);
+            let tail: String = graphemes[graphemes.len() - tail..].concat();
+            s.push_str(tail.trim_start());
+            s
+        };
+        let (mut lo, mut hi) = (0, graphemes.len());
+        while lo < hi {
+            let mid = hi - (hi - lo) / 2;
+            if fits(&candidate(mid)) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        truncated.push_str(&candidate(lo));
+    }
+    Cow::Owned(truncated)
 }
 
 impl<'a> Text<'a> {
@@ -106,12 +573,94 @@ impl<'a> Text<'a> {
         self
     }
 
+    /// Truncate lines that exceed the width with an ellipsis rather than wrapping them.
+    pub fn truncate(mut self, truncate: Truncate) -> Self {
+        self.style.maybe_truncate = Some(Some(truncate));
+        self
+    }
+
     /// A method for specifying the `Font` used for displaying the `Text`.
     pub fn font_id(mut self, font_id: text::font::Id) -> Self {
         self.style.font_id = Some(Some(font_id));
         self
     }
 
+    /// Select the font used for displaying the `Text` from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the bold font of the `Text`'s font family.
+    pub fn bold(self) -> Self {
+        self.font_weight(text::font::Weight::BOLD)
+    }
+
+    /// Select the italic font of the `Text`'s font family.
+    pub fn italic(self) -> Self {
+        self.font_style(text::font::Style::Italic)
+    }
+
+    /// Draw a single line beneath each line of the **Text**.
+    pub fn underline(mut self) -> Self {
+        self.style.underline = Some(Some(Underline::Single));
+        self
+    }
+
+    /// Draw two lines beneath each line of the **Text**.
+    pub fn double_underline(mut self) -> Self {
+        self.style.underline = Some(Some(Underline::Double));
+        self
+    }
+
+    /// Draw a wavy line beneath each line of the **Text**.
+    pub fn wavy_underline(mut self) -> Self {
+        self.style.underline = Some(Some(Underline::Wavy));
+        self
+    }
+
+    /// Draw a line through the middle of each line of the **Text**.
+    pub fn strikethrough(mut self) -> Self {
+        self.style.strikethrough = Some(true);
+        self
+    }
+
+    /// Highlight the area behind each line of the **Text** with the given color.
+    pub fn background(mut self, color: Color) -> Self {
+        self.style.background = Some(Some(color));
+        self
+    }
+
+    /// Specify the color of the underline and strikethrough.
+    pub fn decoration_color(mut self, color: Color) -> Self {
+        self.style.decoration_color = Some(Some(color));
+        self
+    }
+
+    /// Draw a stroke of the given width and color around each glyph.
+    pub fn outline(mut self, width: Scalar, color: Color) -> Self {
+        self.style.outline = Some(Some(Outline {
+            width: width,
+            color: color,
+        }));
+        self
+    }
+
+    /// Draw a copy of the glyphs in the given color at the given offset beneath the text.
+    pub fn drop_shadow(mut self, offset: Point, color: Color) -> Self {
+        self.style.drop_shadow = Some(Some(DropShadow {
+            offset: offset,
+            color: color,
+        }));
+        self
+    }
+
+    /// Highlight selected text with the given color.
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.style.selection_color = Some(Some(color));
+        self
+    }
+
     /// Build the **Text** with the given **Style**.
     pub fn with_style(mut self, style: Style) -> Self {
         self.style = style;
@@ -133,10 +682,19 @@ impl<'a> Text<'a> {
         self.justify(text::Justify::Right)
     }
 
+    /// Align wrapped lines of text to both edges of its bounding **Rect**'s *x* axis range by
+    /// adding space between words.
+    pub fn full_justify(self) -> Self {
+        self.justify(text::Justify::Full)
+    }
+
     builder_methods! {
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub font_weight { style.font_weight = Some(text::font::Weight) }
+        pub font_style { style.font_style = Some(text::font::Style) }
+        pub selectable { style.selectable = Some(bool) }
     }
 }
 
@@ -148,7 +706,10 @@ impl<'a> Widget for Text<'a> {
     fn init_state(&self, _: widget::id::Generator) -> Self::State {
         State {
             string: String::new(),
+            truncated: None,
             line_infos: Vec::new(),
+            selection: None,
+            selecting: false,
         }
     }
 
@@ -163,9 +724,8 @@ impl<'a> Widget for Text<'a> {
     fn default_x_dimension(&self, ui: &Ui) -> Dimension {
         let font = match self
             .style
-            .font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
+            .resolve_font_id(&ui.theme, &ui.fonts)
+            .and_then(|id| ui.fonts.chain(id))
         {
             Some(font) => font,
             None => return Dimension::Absolute(0.0),
@@ -187,11 +747,10 @@ impl<'a> Widget for Text<'a> {
     fn default_y_dimension(&self, ui: &Ui) -> Dimension {
         use position::Sizeable;
 
-        let font = match self
+        let (font_id, font) = match self
             .style
-            .font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
+            .resolve_font_id(&ui.theme, &ui.fonts)
+            .and_then(|id| ui.fonts.chain(id).map(|font| (id, font)))
         {
             Some(font) => font,
             None => return Dimension::Absolute(0.0),
@@ -199,18 +758,18 @@ impl<'a> Widget for Text<'a> {
 
         let text = &self.text;
         let font_size = self.style.font_size(&ui.theme);
-        let num_lines = match self.style.maybe_wrap(&ui.theme) {
+        let maybe_wrap = match self.style.maybe_truncate(&ui.theme) {
+            Some(_) => None,
+            None => self.style.maybe_wrap(&ui.theme),
+        };
+        let num_lines = match maybe_wrap {
             None => text.lines().count(),
             Some(wrap) => match self.get_w(ui) {
                 None => text.lines().count(),
-                Some(max_w) => match wrap {
-                    Wrap::Character => text::line::infos(text, font, font_size)
-                        .wrap_by_character(max_w)
-                        .count(),
-                    Wrap::Whitespace => text::line::infos(text, font, font_size)
-                        .wrap_by_whitespace(max_w)
-                        .count(),
-                },
+                Some(max_w) => {
+                    let key = text::layout::Key::new(text, font_id, font_size, Some((wrap, max_w)));
+                    ui.text_layouts().line_infos(key, text, font).len()
+                }
             },
         };
         let line_spacing = self.style.line_spacing(&ui.theme);
@@ -221,6 +780,7 @@ impl<'a> Widget for Text<'a> {
     /// Update the state of the Text.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs {
+            id,
             rect,
             state,
             style,
@@ -229,54 +789,223 @@ impl<'a> Widget for Text<'a> {
         } = args;
         let Text { text, .. } = self;
 
-        let maybe_wrap = style.maybe_wrap(ui.theme());
+        let maybe_truncate = style.maybe_truncate(ui.theme());
+        let maybe_wrap = match maybe_truncate {
+            Some(_) => None,
+            None => style.maybe_wrap(ui.theme()),
+        };
         let font_size = style.font_size(ui.theme());
 
-        let font = match style
-            .font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
+        let (font_id, font) = match style
+            .resolve_font_id(&ui.theme, &ui.fonts)
+            .and_then(|id| ui.fonts.chain(id).map(|font| (id, font)))
         {
             Some(font) => font,
             None => return,
         };
 
-        // Produces an iterator yielding info for each line within the `text`.
-        let new_line_infos = || match maybe_wrap {
-            None => text::line::infos(text, font, font_size),
-            Some(Wrap::Character) => {
-                text::line::infos(text, font, font_size).wrap_by_character(rect.w())
-            }
-            Some(Wrap::Whitespace) => {
-                text::line::infos(text, font, font_size).wrap_by_whitespace(rect.w())
-            }
+        // The text as it is to be displayed.
+        let displayed = match maybe_truncate {
+            None => Cow::Borrowed(text),
+            Some(mode) => truncate(text, mode, font, font_size, rect.w()),
+        };
+        let new_truncated = match displayed {
+            Cow::Borrowed(_) => None,
+            Cow::Owned(ref s) => Some(s),
         };
+        let full_text = text;
+        let text = &displayed[..];
+
+        // The info for each line within the displayed `text`, laid out via the `Ui`'s cache.
+        let wrap = maybe_wrap.map(|wrap| (wrap, rect.w()));
+        let key = text::layout::Key::new(text, font_id, font_size, wrap);
+        let mut text_layouts = ui.text_layouts();
+        let new_line_infos = text_layouts.line_infos(key, text, font);
 
         // If the string is different, we must update both the string and the line breaks.
-        if &state.string[..] != text {
+        if &state.string[..] != full_text || state.truncated.as_ref() != new_truncated {
             state.update(|state| {
-                state.string = text.to_owned();
-                state.line_infos = new_line_infos().collect();
+                state.string = full_text.to_owned();
+                state.truncated = new_truncated.cloned();
+                state.line_infos = new_line_infos.to_vec();
+                state.selection = None;
             });
 
         // Otherwise, we'll check to see if we have to update the line breaks.
-        } else {
-            use std::borrow::Cow;
-            use utils::write_if_different;
-
-            // Compare the line_infos and only collect the new ones if they are different.
-            let maybe_new_line_infos = {
-                let line_infos = &state.line_infos[..];
-                match write_if_different(line_infos, new_line_infos()) {
-                    Cow::Owned(new) => Some(new),
-                    _ => None,
+        } else if state.line_infos[..] != *new_line_infos {
+            state.update(|state| state.line_infos = new_line_infos.to_vec());
+        }
+        drop(text_layouts);
+
+        if !style.selectable(ui.theme()) {
+            if state.selection.is_some() || state.selecting {
+                state.update(|state| {
+                    state.selection = None;
+                    state.selecting = false;
+                });
+            }
+            return;
+        }
+
+        let justify = style.justify(ui.theme());
+        let line_spacing = style.line_spacing(ui.theme());
+        let line_infos = state.line_infos.clone();
+        let lines = || line_infos.iter().cloned();
+
+        // Find the closest cursor index to the given `xy` position, at the start of a grapheme.
+        let closest_cursor_index = |xy: Point| {
+            let xys_per_line = text::cursor::xys_per_line_from_text(
+                text,
+                &line_infos,
+                font,
+                font_size,
+                justify,
+                Align::End,
+                line_spacing,
+                rect,
+            );
+            text::cursor::closest_cursor_index_and_xy(xy, xys_per_line)
+                .map(|(idx, _)| idx.snap_to_grapheme(text, lines()))
+        };
+
+        // Ensure the selection still lies within the text.
+        let mut selection = state
+            .selection
+            .map(|(start, end)| (start.clamp_to_lines(lines()), end.clamp_to_lines(lines())));
+        let mut selecting = state.selecting;
+
+        for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+                event::Widget::Press(press) => match press.button {
+                    // Begin a selection at the mouse, or extend it while SHIFT is held.
+                    event::Button::Mouse(input::MouseButton::Left, rel_xy) => {
+                        let abs_xy = utils::vec2_add(rel_xy, rect.xy());
+                        if let Some(idx) = closest_cursor_index(abs_xy) {
+                            let shift = press
+                                .modifiers
+                                .contains(input::keyboard::ModifierKey::SHIFT);
+                            selection = match selection {
+                                Some((start, _)) if shift => Some((start, idx)),
+                                _ => Some((idx, idx)),
+                            };
+                        }
+                        selecting = true;
+                    }
+
+                    event::Button::Keyboard(key) => {
+                        let ctrl = press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
+                        let shift = press
+                            .modifiers
+                            .contains(input::keyboard::ModifierKey::SHIFT);
+                        match key {
+                            // Select all text on Ctrl+a.
+                            input::Key::A if ctrl => {
+                                let start = text::cursor::Index { line: 0, char: 0 };
+                                let end =
+                                    text::cursor::index_before_char(lines(), text.chars().count())
+                                        .unwrap_or(start);
+                                selection = Some((start, end));
+                            }
+
+                            // Copy the selected text on Ctrl+c.
+                            input::Key::C if ctrl => {
+                                let selected = selection.and_then(|(start, end)| {
+                                    selected(full_text, text, &line_infos, start, end)
+                                });
+                                if let Some(selected) = selected {
+                                    if let Ok(mut clipboard) = ClipboardContext::new() {
+                                        let _ = clipboard.set_contents(selected.to_owned());
+                                    }
+                                }
+                            }
+
+                            // Extend the selection while SHIFT is held.
+                            input::Key::Left
+                            | input::Key::Right
+                            | input::Key::Home
+                            | input::Key::End
+                                if shift =>
+                            {
+                                if let Some((start, end)) = selection {
+                                    let new_end = match (key, ctrl) {
+                                        (input::Key::Left, true) => {
+                                            end.previous_word_start(text, lines())
+                                        }
+                                        (input::Key::Right, true) => {
+                                            end.next_word_end(text, lines())
+                                        }
+                                        (input::Key::Left, false) => end.left(text, lines()),
+                                        (input::Key::Right, false) => end.right(text, lines()),
+                                        (input::Key::Home, _) => Some(text::cursor::Index {
+                                            line: end.line,
+                                            char: 0,
+                                        }),
+                                        _ => {
+                                            lines().nth(end.line).map(|info| text::cursor::Index {
+                                                line: end.line,
+                                                char: info.end_char() - info.start_char,
+                                            })
+                                        }
+                                    }
+                                    .unwrap_or(end);
+                                    selection = Some((start, new_end));
+                                }
+                            }
+
+                            _ => (),
+                        }
+                    }
+
+                    _ => (),
+                },
+
+                // Select the word under the mouse on double-click.
+                event::Widget::DoubleClick(click) => {
+                    if let input::MouseButton::Left = click.button {
+                        let abs_xy = utils::vec2_add(click.xy, rect.xy());
+                        if let Some(idx) = closest_cursor_index(abs_xy) {
+                            selection = idx.word_bounds(text, lines()).or(selection);
+                        }
+                    }
+                }
+
+                // Extend the selection to follow the mouse.
+                event::Widget::Drag(drag)
+                    if selecting && drag.button == input::MouseButton::Left =>
+                {
+                    let abs_xy = utils::vec2_add(drag.to, rect.xy());
+                    if let (Some((start, _)), Some(idx)) = (selection, closest_cursor_index(abs_xy))
+                    {
+                        selection = Some((start, idx));
+                    }
                 }
-            };
 
-            if let Some(new_line_infos) = maybe_new_line_infos {
-                state.update(|state| state.line_infos = new_line_infos);
+                event::Widget::Release(release) => {
+                    if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        selecting = false;
+                    }
+                }
+
+                // Deselect the text once some other widget takes the keyboard.
+                event::Widget::UncapturesInputSource(input::Source::Keyboard) => {
+                    selection = None;
+                    selecting = false;
+                }
+
+                _ => (),
             }
         }
+
+        if ui.widget_input(id).mouse().is_some() {
+            ui.set_mouse_cursor(cursor::MouseCursor::Text);
+        }
+
+        if state.selection != selection || state.selecting != selecting {
+            state.update(|state| {
+                state.selection = selection;
+                state.selecting = selecting;
+            });
+        }
     }
 }
 
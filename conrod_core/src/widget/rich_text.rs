@@ -0,0 +1,506 @@
+//! A widget for displaying text made up of differently styled spans.
+//!
+//! Each `Span` may specify its own font, size, color, underline, strikethrough and background
+//! highlight. All spans are wrapped together by whitespace as a single paragraph, and each
+//! contiguous run of a span's text on a line is drawn by its own `Text` widget. The widget reports
+//! which span is hovered or clicked, allowing spans to be used as links.
+
+use position::{Dimension, Point, Range, Rect, Scalar};
+use std;
+use text;
+use theme::{FontOptions, Theme};
+use widget;
+use {Color, Colorable, FontSize, Positionable, Sizeable, Ui, Widget};
+
+/// Displays a sequence of styled spans of text, wrapped together over multiple lines.
+#[derive(Copy, Clone, Debug, WidgetCommon_)]
+pub struct RichText<'a> {
+    /// Data necessary and common for all widget builder types.
+    #[conrod(common_builder)]
+    pub common: widget::CommonBuilder,
+    /// The styled spans of text in the order in which they are displayed.
+    pub spans: &'a [Span<'a>],
+    /// Unique styling.
+    pub style: Style,
+}
+
+/// A contiguous piece of text with a single style.
+///
+/// Any unspecified fields fall back to the `Style` of the `RichText` widget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Span<'a> {
+    /// The text of the span. Newline characters begin a new line.
+    pub text: &'a str,
+    /// The font used to display the span.
+    pub font_id: Option<text::font::Id>,
+    /// The font size of the span.
+    pub font_size: Option<FontSize>,
+    /// The color of the span's text.
+    pub color: Option<Color>,
+    /// The line drawn beneath the span's text, if any.
+    pub underline: Option<widget::text::Underline>,
+    /// Whether or not a line is drawn through the span's text.
+    pub strikethrough: bool,
+    /// The color with which the area behind the span's text is highlighted.
+    pub background: Option<Color>,
+}
+
+/// Unique styling for the `RichText` widget, used by any spans that do not specify their own.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The font size of the text.
+    #[conrod(default = "theme.font_size_medium")]
+    pub font_size: Option<FontSize>,
+    /// The color of the text.
+    #[conrod(default = "theme.label_color")]
+    pub color: Option<Color>,
+    /// The id of the font to use for rendering and layout.
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the spans' font is selected when no font id is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the spans' font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the spans' font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// The spacing between consecutive lines.
+    #[conrod(default = "1.0")]
+    pub line_spacing: Option<Scalar>,
+    /// Alignment of each line of text along the *x* axis.
+    #[conrod(default = "text::Justify::Left")]
+    pub justify: Option<text::Justify>,
+}
+
+widget_ids! {
+    struct Ids {
+        texts[],
+    }
+}
+
+/// Unique state for the `RichText` widget.
+pub struct State {
+    ids: Ids,
+    runs: Vec<Run>,
+}
+
+/// A contiguous run of a single span's text on a single line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Run {
+    /// The index of the span to which the run belongs.
+    pub span: usize,
+    /// The byte range of the run within the span's text.
+    pub bytes: std::ops::Range<usize>,
+    /// The area occupied by the run, where the bottom of the `Rect` lies on the line's baseline.
+    pub rect: Rect,
+}
+
+/// The events produced by the `RichText` widget.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Event {
+    /// The index of the span that was clicked or tapped, if any.
+    pub clicked: Option<usize>,
+    /// The index of the span over which the mouse is hovering, if any.
+    pub hovered: Option<usize>,
+}
+
+impl<'a> Span<'a> {
+    /// A span displaying the given text in the style of the `RichText` widget.
+    pub fn new(text: &'a str) -> Self {
+        Span {
+            text: text,
+            font_id: None,
+            font_size: None,
+            color: None,
+            underline: None,
+            strikethrough: false,
+            background: None,
+        }
+    }
+
+    /// Display the span using the given font.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.font_id = Some(font_id);
+        self
+    }
+
+    /// Display the span at the given font size.
+    pub fn font_size(mut self, font_size: FontSize) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Underline the span's text.
+    pub fn underline(mut self) -> Self {
+        self.underline = Some(widget::text::Underline::Single);
+        self
+    }
+
+    /// Underline the span's text with two lines.
+    pub fn double_underline(mut self) -> Self {
+        self.underline = Some(widget::text::Underline::Double);
+        self
+    }
+
+    /// Underline the span's text with a wavy line.
+    pub fn wavy_underline(mut self) -> Self {
+        self.underline = Some(widget::text::Underline::Wavy);
+        self
+    }
+
+    /// Draw a line through the span's text.
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Highlight the area behind the span's text with the given color.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+}
+
+impl<'a> Colorable for Span<'a> {
+    fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl<'a> RichText<'a> {
+    /// Construct a new `RichText` widget from the given spans.
+    pub fn new(spans: &'a [Span<'a>]) -> Self {
+        RichText {
+            common: widget::CommonBuilder::default(),
+            spans: spans,
+            style: Style::default(),
+        }
+    }
+
+    /// Specify the font used by spans that do not specify their own.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Select the font used for spans that do not specify their own from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
+    builder_methods! {
+        pub font_size { style.font_size = Some(FontSize) }
+        pub font_weight { style.font_weight = Some(text::font::Weight) }
+        pub font_style { style.font_style = Some(text::font::Style) }
+        pub justify { style.justify = Some(text::Justify) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+    }
+}
+
+impl Style {
+    /// The id of the font used by spans that do not specify their own, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
+impl State {
+    /// The runs of text produced by the most recent layout.
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    /// The index of the span displayed at the given point, if any.
+    pub fn span_at(&self, xy: Point) -> Option<usize> {
+        self.runs
+            .iter()
+            .find(|run| run.rect.is_over(xy))
+            .map(|run| run.span)
+    }
+}
+
+impl<'a> Widget for RichText<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Event;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            runs: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// If no specific width was given, the width of the widest line is used as a default.
+    fn default_x_dimension(&self, ui: &Ui) -> Dimension {
+        let mut runs = Vec::new();
+        let [w, _] = layout(
+            self.spans,
+            &self.style,
+            &ui.theme,
+            &ui.fonts,
+            None,
+            &mut runs,
+        );
+        Dimension::Absolute(w)
+    }
+
+    /// If no specific height was given, the total height of the wrapped text is used as a default.
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        let mut runs = Vec::new();
+        let max_w = self.get_w(ui);
+        let [_, h] = layout(
+            self.spans,
+            &self.style,
+            &ui.theme,
+            &ui.fonts,
+            max_w,
+            &mut runs,
+        );
+        Dimension::Absolute(h)
+    }
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs {
+            id,
+            state,
+            style,
+            rect,
+            ui,
+            ..
+        } = args;
+        let RichText { spans, .. } = self;
+
+        // Lay out the runs relative to the top left corner of the widget.
+        let mut runs = Vec::new();
+        layout(
+            spans,
+            style,
+            &ui.theme,
+            &ui.fonts,
+            Some(rect.w()),
+            &mut runs,
+        );
+        let [left, top] = rect.top_left();
+        for run in &mut runs {
+            run.rect = run.rect.shift([left, top]);
+        }
+        if state.runs != runs {
+            state.update(|state| state.runs = runs);
+        }
+
+        let num_runs = state.runs.len();
+        if state.ids.texts.len() < num_runs {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.texts.resize(num_runs, id_gen));
+        }
+
+        let default_font_id = style.resolve_font_id(&ui.theme, &ui.fonts);
+        for (i, run) in state.runs.iter().enumerate() {
+            let span = &spans[run.span];
+            let font_id = match span.font_id.or(default_font_id) {
+                Some(font_id) => font_id,
+                None => continue,
+            };
+            let font_size = span.font_size.unwrap_or_else(|| style.font_size(&ui.theme));
+            let color = span.color.unwrap_or_else(|| style.color(&ui.theme));
+            let (x, y, w, h) = run.rect.x_y_w_h();
+            widget::Text::new(&span.text[run.bytes.clone()])
+                .font_id(font_id)
+                .font_size(font_size)
+                .color(color)
+                .no_line_wrap()
+                .left_justify()
+                .and_mut(|text| {
+                    text.style.underline = Some(span.underline);
+                    text.style.strikethrough = Some(span.strikethrough);
+                    text.style.background = Some(span.background);
+                })
+                .x_y(x, y)
+                .w_h(w, h)
+                .parent(id)
+                .graphics_for(id)
+                .set(state.ids.texts[i], ui);
+        }
+
+        // Hit-test the spans in the widget's own (untransformed) coordinates.
+        let input = ui.widget_input(id);
+        let centre = rect.xy();
+        let to_abs = |xy: Point| [centre[0] + xy[0], centre[1] + xy[1]];
+        let clicked = input
+            .clicks()
+            .left()
+            .map(|click| click.xy)
+            .chain(input.taps().map(|tap| tap.xy))
+            .filter_map(|xy| state.span_at(to_abs(xy)))
+            .last();
+        let hovered = input
+            .mouse()
+            .filter(|mouse| mouse.is_over())
+            .and_then(|mouse| state.span_at(to_abs(mouse.rel_xy())));
+        Event {
+            clicked: clicked,
+            hovered: hovered,
+        }
+    }
+}
+
+/// Lay out the given spans, wrapping them together by whitespace at `max_width` if given.
+///
+/// The spans are wrapped as a single text via `text::line::Infos`, with the advance of each
+/// character measured using its own span's font and size. A `Run` is produced for each contiguous
+/// piece of a span's text on each line. The `Rect` of each run is relative to the top left corner
+/// of the text, where *y* decreases downwards.
+///
+/// Returns the dimensions of the laid out text. Spans whose font cannot be found are skipped.
+pub fn layout(
+    spans: &[Span],
+    style: &Style,
+    theme: &Theme,
+    fonts: &text::font::Map,
+    max_width: Option<Scalar>,
+    runs: &mut Vec<Run>,
+) -> [Scalar; 2] {
+    runs.clear();
+    let default_font_id = style.resolve_font_id(theme, fonts);
+    let default_font_size = style.font_size(theme);
+
+    // Join the text of the spans, recording the byte at which each begins along with the advance
+    // of each character at the byte at which it begins.
+    let mut joined = String::new();
+    let mut starts: Vec<(usize, usize, FontSize)> = Vec::new();
+    let mut advances: Vec<Scalar> = Vec::new();
+    let mut first_font = None;
+    for (i, span) in spans.iter().enumerate() {
+        let font = match span
+            .font_id
+            .or(default_font_id)
+            .and_then(|id| fonts.chain(id))
+        {
+            Some(font) => font,
+            None => continue,
+        };
+        if span.text.is_empty() {
+            continue;
+        }
+        let font_size = span.font_size.unwrap_or(default_font_size);
+        let scale = text::pt_to_scale(font_size);
+        first_font = first_font.or(Some(font));
+        starts.push((joined.len(), i, font_size));
+        joined.push_str(span.text);
+        for (j, line) in span.text.split('\n').enumerate() {
+            if j > 0 {
+                advances.push(0.0);
+            }
+            let mut line_advances = font.advances(line, scale);
+            for ch in line.chars() {
+                advances.push(line_advances.next().unwrap_or(0.0));
+                advances.resize(advances.len() + ch.len_utf8() - 1, 0.0);
+            }
+        }
+    }
+    let font = match first_font {
+        Some(font) => font,
+        None => return [max_width.unwrap_or(0.0), default_font_size as Scalar],
+    };
+
+    // The index into `starts` of the span containing the given byte.
+    let span_at = |byte: usize| match starts.binary_search_by(|&(start, _, _)| start.cmp(&byte)) {
+        Ok(i) => i,
+        Err(i) => i.saturating_sub(1),
+    };
+
+    let text_len = joined.len();
+    let infos: Vec<_> = text::line::infos_wrapped_by(
+        &joined,
+        font,
+        default_font_size,
+        max_width.unwrap_or(f64::MAX),
+        |line: &str, _: text::font::Chain, _: FontSize, max_width: Scalar| {
+            let start = text_len - line.len();
+            let advances = line.char_indices().map(|(i, _)| advances[start + i]);
+            text::line::break_by_whitespace(line, advances, max_width)
+        },
+    )
+    .collect();
+
+    // Position each line beneath the last, with the baseline at the bottom of the line.
+    let justify = style.justify(theme);
+    let line_spacing = style.line_spacing(theme);
+    let text_w = match max_width {
+        Some(w) => w,
+        None => infos.iter().fold(0.0, |max, info| info.width.max(max)),
+    };
+    let mut bottom = 0.0;
+    for (i, info) in infos.iter().enumerate() {
+        if i > 0 {
+            bottom -= line_spacing;
+        }
+        let bytes = info.byte_range();
+        let line = &joined[bytes.clone()];
+        let font_size = starts[span_at(bytes.start)..=span_at(bytes.end.max(1) - 1)]
+            .iter()
+            .map(|&(_, _, font_size)| font_size)
+            .max()
+            .unwrap_or(default_font_size);
+        bottom -= font_size as Scalar;
+
+        let extra = (text_w - info.width).max(0.0);
+        let mut x = match justify {
+            text::Justify::Left | text::Justify::Full => 0.0,
+            text::Justify::Center => extra / 2.0,
+            text::Justify::Right => extra,
+        };
+        let spacing = match info.end_break {
+            text::line::Break::Wrap { .. } if justify == text::Justify::Full => {
+                Some(text::line::WordSpacing::new(line, info.width, text_w))
+            }
+            _ => None,
+        };
+
+        // Stretched whitespace ends its run so that the glyphs that follow are shifted.
+        let mut extends_run = false;
+        for (char_i, (byte_i, ch)) in line.char_indices().enumerate() {
+            let byte = bytes.start + byte_i;
+            let (span_start, span, font_size) = starts[span_at(byte)];
+            let run_bytes = byte - span_start..byte - span_start + ch.len_utf8();
+            let stretch = spacing.map_or(0.0, |spacing| spacing.after(char_i, ch));
+            let width = advances[byte] + stretch;
+            let rect = Rect {
+                x: Range::new(x, x + width),
+                y: Range::new(bottom, bottom + font_size as Scalar),
+            };
+            x += width;
+            match runs.last_mut() {
+                Some(run) if extends_run && run.span == span => {
+                    run.bytes.end = run_bytes.end;
+                    run.rect.x.end = rect.x.end;
+                }
+                _ => runs.push(Run {
+                    span: span,
+                    bytes: run_bytes,
+                    rect: rect,
+                }),
+            }
+            extends_run = stretch == 0.0;
+        }
+    }
+
+    [text_w, -bottom]
+}
@@ -3,9 +3,10 @@
 use num::{Float, NumCast, ToPrimitive};
 use position::{Padding, Range, Rect, Scalar};
 use text;
+use theme::FontOptions;
 use widget;
 use widget::triangles::Triangle;
-use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Widget};
+use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Theme, Widget};
 
 /// Linear value selection.
 ///
@@ -54,8 +55,19 @@ pub struct Style {
     #[conrod(default = "theme.font_size_medium")]
     pub label_font_size: Option<FontSize>,
     /// The ID of the font used to display the label.
-    #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the label's font is selected when no font id is given.
+    pub label_font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub label_font_weight: Option<text::font::Weight>,
+    /// The style of the label's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub label_font_style: Option<text::font::Style>,
+    /// Whether or not the label may be selected and copied, in which case it receives its own
+    /// input rather than passing it to the Slider.
+    #[conrod(default = "false")]
+    pub label_selectable: Option<bool>,
 }
 
 widget_ids! {
@@ -91,12 +103,55 @@ impl<'a, T> Slider<'a, T> {
         self
     }
 
+    /// Select the label's font from the given family.
+    pub fn label_font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.label_font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the label's font within its family.
+    pub fn label_font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.label_font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the label's font within its family.
+    pub fn label_font_style(mut self, style: text::font::Style) -> Self {
+        self.style.label_font_style = Some(style);
+        self
+    }
+
+    /// Allow the label to be selected and copied.
+    ///
+    /// A selectable label receives its own input rather than passing it to the Slider.
+    pub fn label_selectable(mut self, selectable: bool) -> Self {
+        self.style.label_selectable = Some(selectable);
+        self
+    }
+
     builder_methods! {
         pub skew { skew = f32 }
         pub enabled { enabled = bool }
     }
 }
 
+impl Style {
+    /// The id of the font used to display the label's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_label_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.label_font_id,
+            family: style.label_font_family,
+            weight: style.label_font_weight,
+            style: style.label_font_style,
+        })
+    }
+}
+
 impl<'a, T> Widget for Slider<'a, T>
 where
     T: Float + NumCast + ToPrimitive,
@@ -267,8 +322,9 @@ where
         if let Some(label) = maybe_label {
             let label_color = style.label_color(ui.theme());
             let font_size = style.label_font_size(ui.theme());
-            let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+            let font_id = style.resolve_label_font_id(&ui.theme, &ui.fonts);
             //const TEXT_PADDING: f64 = 10.0;
+            let selectable = style.label_selectable(ui.theme());
             widget::Text::new(label)
                 .and_then(font_id, widget::Text::font_id)
                 .and(|text| {
@@ -278,7 +334,8 @@ where
                         text.mid_bottom_of(id)
                     }
                 })
-                .graphics_for(id)
+                .and_if(!selectable, |text| text.graphics_for(id))
+                .selectable(selectable)
                 .color(label_color)
                 .font_size(font_size)
                 .set(state.ids.label, ui);
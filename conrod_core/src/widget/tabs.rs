@@ -2,9 +2,10 @@
 
 use std;
 use text;
+use theme::FontOptions;
 use utils;
 use widget;
-use {Color, Dimensions, FontSize, Point, Rect, Scalar, Widget};
+use {Color, Dimensions, FontSize, Point, Rect, Scalar, Theme, Widget};
 
 /// A wrapper around a list of `Canvas`ses that displays them as a list of selectable tabs.
 #[derive(WidgetCommon_)]
@@ -51,9 +52,19 @@ pub struct Style {
     /// Font size of the number dialer's label.
     #[conrod(default = "theme.font_size_medium")]
     pub label_font_size: Option<FontSize>,
-    /// The `font::Id` of the number dialer's font.
-    #[conrod(default = "None")]
+    /// The `font::Id` of the tab labels' font.
     pub font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the tab labels' font is selected when no font id is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the tab labels' font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the tab labels' font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// The way in which tab labels should be truncated if they are wider than their tabs.
+    #[conrod(default = "None")]
+    pub label_truncate: Option<Option<widget::text::Truncate>>,
     /// The styling for each `Canvas`.
     #[conrod(default = "widget::canvas::Style::default()")]
     pub canvas: Option<widget::canvas::Style>,
@@ -169,6 +180,36 @@ impl<'a> Tabs<'a> {
         self
     }
 
+    /// Truncate tab labels with an ellipsis if they are wider than their tabs.
+    pub fn label_truncate(mut self, truncate: widget::text::Truncate) -> Self {
+        self.style.label_truncate = Some(Some(truncate));
+        self
+    }
+
+    /// Specify the font used for displaying the tab labels.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Select the tab labels' font from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the tab labels' font within its family.
+    pub fn font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the tab labels' font within its family.
+    pub fn font_style(mut self, style: text::font::Style) -> Self {
+        self.style.font_style = Some(style);
+        self
+    }
+
     builder_methods! {
         pub starting_tab_idx { maybe_starting_tab_idx = Some(usize) }
         pub label_color { style.label_color = Some(Color) }
@@ -176,6 +217,23 @@ impl<'a> Tabs<'a> {
     }
 }
 
+impl Style {
+    /// The id of the font used to display the tab labels' text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
 impl<'a> Widget for Tabs<'a> {
     type State = State;
     type Style = Style;
@@ -213,9 +271,8 @@ impl<'a> Widget for Tabs<'a> {
             }
             Layout::Vertical => {
                 let max_text_width = style
-                    .font_id(theme)
-                    .or(fonts.ids().next())
-                    .and_then(|id| fonts.get(id))
+                    .resolve_font_id(theme, fonts)
+                    .and_then(|id| fonts.chain(id))
                     .map(|font| max_text_width(self.tabs.iter(), font_size, font))
                     .unwrap_or(0.0);
                 let tab_bar_w = vertical_tab_bar_w(bar_thickness, max_text_width as Scalar);
@@ -246,9 +303,8 @@ impl<'a> Widget for Tabs<'a> {
         let font_size = style.label_font_size(&ui.theme);
         let canvas_style = style.canvas(&ui.theme);
         let max_text_width = style
-            .font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
+            .resolve_font_id(&ui.theme, &ui.fonts)
+            .and_then(|id| ui.fonts.chain(id))
             .map(|font| max_text_width(self.tabs.iter(), font_size, font))
             .unwrap_or(0.0);
 
@@ -294,6 +350,8 @@ impl<'a> Widget for Tabs<'a> {
             let border = canvas_style.border(&ui.theme);
             let border_color = canvas_style.border_color(ui.theme());
             let label_color = style.label_color(ui.theme());
+            let label_truncate = style.label_truncate(ui.theme());
+            let font_id = style.resolve_font_id(&ui.theme, &ui.fonts);
             let mut maybe_selected_tab_idx = state
                 .maybe_selected_tab_idx
                 .or(maybe_starting_tab_idx)
@@ -314,6 +372,8 @@ impl<'a> Widget for Tabs<'a> {
                     .border_color(border_color)
                     .label(label)
                     .label_color(label_color)
+                    .and_then(label_truncate, widget::Button::label_truncate)
+                    .and_then(font_id, widget::Button::label_font_id)
                     .parent(id)
                     .set(tab.button_id, &mut ui)
                     .was_clicked()
@@ -346,7 +406,7 @@ impl<'a> Widget for Tabs<'a> {
 }
 
 /// Calculate the max text width yielded by a string in the tabs slice.
-fn max_text_width<'a, I>(tabs: I, font_size: FontSize, font: &text::Font) -> Scalar
+fn max_text_width<'a, I>(tabs: I, font_size: FontSize, font: text::font::Chain) -> Scalar
 where
     I: Iterator<Item = &'a (widget::Id, &'a str)>,
 {
@@ -4,8 +4,9 @@ use event;
 use input;
 use position::{Range, Rect, Scalar};
 use text;
+use theme::FontOptions;
 use widget;
-use {Borderable, Color, Colorable, FontSize, Positionable, Sizeable, Widget};
+use {Borderable, Color, Colorable, FontSize, Positionable, Sizeable, Theme, Widget};
 
 /// A widget for displaying and mutating a small, one-line field of text, given by the user in the
 /// form of a `String`.
@@ -17,6 +18,7 @@ pub struct TextBox<'a> {
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    history: Option<widget::text_edit::History>,
 }
 
 /// Unique graphical styling for the TextBox.
@@ -47,8 +49,18 @@ pub struct Style {
     #[conrod(default = "text::Justify::Left")]
     pub justify: Option<text::Justify>,
     /// The font used for the `Text`.
-    #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the text's font is selected when no font id is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the text's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the text's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// Whether or not edits may be undone and redone, as for the `TextEdit` widget.
+    #[conrod(default = "false")]
+    pub undo_history: Option<bool>,
 }
 
 widget_ids! {
@@ -63,6 +75,14 @@ pub struct State {
     ids: Ids,
 }
 
+impl State {
+    /// The `Id` of the `TextEdit` within the `TextBox`, whose `State` retains the undo and redo
+    /// history of the text.
+    pub fn text_edit(&self) -> widget::Id {
+        self.ids.text_edit
+    }
+}
+
 impl<'a> TextBox<'a> {
     /// Construct a TextBox widget.
     pub fn new(text: &'a str) -> Self {
@@ -70,9 +90,23 @@ impl<'a> TextBox<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            history: None,
         }
     }
 
+    /// Replace the undo and redo history of the text with the given `History`.
+    ///
+    /// See `TextEdit::history`.
+    pub fn history(mut self, history: widget::text_edit::History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Forget the undo and redo history of the text.
+    pub fn clear_history(self) -> Self {
+        self.history(widget::text_edit::History::new())
+    }
+
     /// Align the text to the left of its bounding **Rect**'s *x* axis range.
     pub fn left_justify(self) -> Self {
         self.justify(text::Justify::Left)
@@ -94,11 +128,20 @@ impl<'a> TextBox<'a> {
         self
     }
 
+    /// Select the font used for displaying the text from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
     builder_methods! {
         pub text_color { style.text_color = Some(Color) }
+        pub font_weight { style.font_weight = Some(text::font::Weight) }
+        pub font_style { style.font_style = Some(text::font::Style) }
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
         pub pad_text { style.text_padding = Some(Scalar) }
+        pub undo_history { style.undo_history = Some(bool) }
     }
 }
 
@@ -111,6 +154,23 @@ pub enum Event {
     Enter,
 }
 
+impl Style {
+    /// The id of the font used to display the text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
 impl<'a> Widget for TextBox<'a> {
     type State = State;
     type Style = Style;
@@ -136,7 +196,7 @@ impl<'a> Widget for TextBox<'a> {
             ui,
             ..
         } = args;
-        let TextBox { text, .. } = self;
+        let TextBox { text, history, .. } = self;
 
         let font_size = style.font_size(ui.theme());
         let border = style.border(ui.theme());
@@ -165,7 +225,7 @@ impl<'a> Widget for TextBox<'a> {
         let mut events = Vec::new();
 
         let text_color = style.text_color(ui.theme());
-        let font_id = style.font_id(&ui.theme).or(ui.fonts.ids().next());
+        let font_id = style.resolve_font_id(&ui.theme, &ui.fonts);
         if let Some(new_string) = widget::TextEdit::new(text)
             .and_then(font_id, widget::TextEdit::font_id)
             .wh(text_rect.dim())
@@ -173,6 +233,8 @@ impl<'a> Widget for TextBox<'a> {
             .font_size(font_size)
             .color(text_color)
             .justify(justify)
+            .undo_history(style.undo_history(ui.theme()))
+            .and_then(history, widget::TextEdit::history)
             .parent(id)
             .set(state.ids.text_edit, ui)
         {
@@ -6,22 +6,26 @@ use event;
 use input;
 use position::{Align, Dimension, Point, Range, Rect, Scalar};
 use std;
+use std::sync::atomic::{self, AtomicUsize};
 use text;
+use theme::FontOptions;
 use utils;
 use widget;
 use widget::primitive::text::Wrap;
-use {Color, Colorable, FontSize, Positionable, Sizeable, Ui, Widget};
+use {Color, Colorable, FontSize, Positionable, Sizeable, Theme, Ui, Widget};
 
 /// A widget for displaying and mutating multi-line text, given as a `String`.
 ///
-/// By default the text is wrapped via the first whitespace before the line exceeds the
-/// `TextEdit`'s width, however a user may change this using the `.wrap_by_character` method.
+/// By default the text is wrapped via the last line break opportunity (e.g. whitespace) before the
+/// line exceeds the `TextEdit`'s width, however a user may change this using the
+/// `.wrap_by_character` method.
 #[derive(WidgetCommon_)]
 pub struct TextEdit<'a> {
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    history: Option<History>,
 }
 
 /// Unique graphical styling for the TextEdit.
@@ -49,8 +53,19 @@ pub struct Style {
     #[conrod(default = "true")]
     pub restrict_to_height: Option<bool>,
     /// The font used for the `Text`.
-    #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the text's font is selected when no font id is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the text's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the text's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// Whether or not edits are recorded so that they may be undone and redone via `Ctrl+Z`,
+    /// `Ctrl+Shift+Z` and `Ctrl+Y`.
+    #[conrod(default = "false")]
+    pub undo_history: Option<bool>,
 }
 
 widget_ids! {
@@ -68,9 +83,52 @@ pub struct State {
     drag: Option<Drag>,
     /// Information about each line of text.
     line_infos: Vec<text::line::Info>,
+    history: History,
+    // The revision and capacity of the history given via `TextEdit::history` on the last update.
+    given_history: Option<(usize, usize)>,
+    // The text left by the last edit while the undo history is enabled.
+    edited_text: Option<String>,
     ids: Ids,
 }
 
+/// The number of edits retained by a `History` constructed via `History::new`.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+/// The undo and redo history of the text within a `TextEdit`.
+///
+/// Each edit is recorded as a `Snapshot` of the text and cursor from before it, so that undoing
+/// the edit restores both. Consecutive typed characters are coalesced into a single edit.
+#[derive(Clone, Debug)]
+pub struct History {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    capacity: usize,
+    // The cursor following the last typed text, while typing at it extends the same edit.
+    typing: Option<Cursor>,
+    // Identifies the state of the history. Each modification produces a new, unique revision,
+    // while an empty history that was never modified has revision `0`.
+    revision: usize,
+}
+
+// The source of unique `History` revisions.
+static NEXT_HISTORY_REVISION: AtomicUsize = AtomicUsize::new(1);
+
+/// The text and cursor of a `TextEdit` at some point within its `History`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    /// The text.
+    pub text: String,
+    /// The cursor over the text.
+    pub cursor: Cursor,
+}
+
+// The kinds of edit recorded within a `History`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Edit {
+    Typing,
+    Other,
+}
+
 /// Track whether some sort of dragging is currently occurring.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Drag {
@@ -98,6 +156,152 @@ pub enum Cursor {
     },
 }
 
+impl State {
+    /// The undo and redo history of the text.
+    ///
+    /// The history may be cloned in order to restore it later via `TextEdit::history`.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+}
+
+impl History {
+    /// Construct an empty `History` retaining up to `DEFAULT_HISTORY_CAPACITY` edits.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_HISTORY_CAPACITY)
+    }
+
+    /// Construct an empty `History` retaining up to `capacity` edits.
+    pub fn with_capacity(capacity: usize) -> Self {
+        History {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            capacity: capacity,
+            typing: None,
+            revision: 0,
+        }
+    }
+
+    /// The maximum number of edits that may be undone.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The snapshots restored by undoing each edit, from the oldest to the most recent.
+    pub fn undo_snapshots(&self) -> &[Snapshot] {
+        &self.undo
+    }
+
+    /// The snapshots restored by redoing each undone edit, from the last to the next.
+    pub fn redo_snapshots(&self) -> &[Snapshot] {
+        &self.redo
+    }
+
+    /// Whether or not there is some edit that may be undone.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether or not there is some undone edit that may be redone.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all edits.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = None;
+        self.revise();
+    }
+
+    // Mark the history as modified.
+    fn revise(&mut self) {
+        self.revision = NEXT_HISTORY_REVISION.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+
+    // Record the `text` and `cursor` from before an edit that leaves the cursor at `new_cursor`.
+    fn record(&mut self, text: &str, cursor: Cursor, new_cursor: Cursor, edit: Edit) {
+        self.revise();
+        self.redo.clear();
+        let coalesce = edit == Edit::Typing && self.typing == Some(cursor) && self.can_undo();
+        self.typing = match edit {
+            Edit::Typing => Some(new_cursor),
+            Edit::Other => None,
+        };
+        if coalesce || self.capacity == 0 {
+            return;
+        }
+        if self.undo.len() >= self.capacity {
+            let excess = self.undo.len() + 1 - self.capacity;
+            self.undo.drain(..excess);
+        }
+        let snapshot = Snapshot {
+            text: text.to_owned(),
+            cursor: cursor,
+        };
+        self.undo.push(snapshot);
+    }
+
+    // Step back to the snapshot from before the last edit, retaining the current `text` and
+    // `cursor` so that the edit may be redone.
+    fn undo(&mut self, text: &str, cursor: Cursor) -> Option<Snapshot> {
+        let snapshot = self.undo.pop()?;
+        self.typing = None;
+        self.revise();
+        self.redo.push(Snapshot {
+            text: text.to_owned(),
+            cursor: cursor,
+        });
+        Some(snapshot)
+    }
+
+    // Step forward to the snapshot from before the last undo, retaining the current `text` and
+    // `cursor` so that it may be undone again.
+    fn redo(&mut self, text: &str, cursor: Cursor) -> Option<Snapshot> {
+        let snapshot = self.redo.pop()?;
+        self.typing = None;
+        self.revise();
+        self.undo.push(Snapshot {
+            text: text.to_owned(),
+            cursor: cursor,
+        });
+        Some(snapshot)
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for History {
+    fn eq(&self, other: &Self) -> bool {
+        self.undo == other.undo
+            && self.redo == other.redo
+            && self.capacity == other.capacity
+            && self.typing == other.typing
+    }
+}
+
+impl Style {
+    /// The id of the font used to display the text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
 impl<'a> TextEdit<'a> {
     /// Construct a TextEdit widget.
     pub fn new(text: &'a str) -> Self {
@@ -105,19 +309,39 @@ impl<'a> TextEdit<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            history: None,
         }
     }
 
-    /// The `TextEdit` will wrap text via the whitespace that precedes the first width-exceeding
-    /// character.
+    /// Replace the undo and redo history of the text with the given `History`, e.g. one that was
+    /// previously cloned from the `State`.
+    ///
+    /// The history is only replaced when it is not the one given on the previous update, so the
+    /// same `History` may be given on every update without losing the edits made since. Only the
+    /// history's revision is compared, so this is cheap regardless of the history's size.
+    pub fn history(mut self, history: History) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Forget the undo and redo history of the text.
+    ///
+    /// As with `history`, the history is only cleared on the first of consecutive updates that
+    /// call this method.
+    pub fn clear_history(self) -> Self {
+        self.history(History::new())
+    }
+
+    /// The `TextEdit` will wrap text via the line break opportunity (e.g. whitespace) that precedes
+    /// the first width-exceeding character.
     ///
     /// This is the default setting.
     pub fn wrap_by_whitespace(self) -> Self {
         self.line_wrap(Wrap::Whitespace)
     }
 
-    /// By default, the `TextEdit` will wrap text via the whitespace that precedes the first
-    /// width-exceeding character.
+    /// By default, the `TextEdit` will wrap text via the line break opportunity that precedes the
+    /// first width-exceeding character.
     ///
     /// Calling this method causes the `TextEdit` to wrap text at the first exceeding character.
     pub fn wrap_by_character(self) -> Self {
@@ -139,6 +363,12 @@ impl<'a> TextEdit<'a> {
         self.justify(text::Justify::Right)
     }
 
+    /// Align wrapped lines of text to both edges of its bounding **Rect**'s *x* axis range by
+    /// adding space between words.
+    pub fn full_justify(self) -> Self {
+        self.justify(text::Justify::Full)
+    }
+
     /// Align the text to the left of its bounding **Rect**'s *y* axis range.
     pub fn align_text_bottom(self) -> Self {
         self.y_align_text(Align::Start)
@@ -165,13 +395,22 @@ impl<'a> TextEdit<'a> {
         self
     }
 
+    /// Select the font used for displaying the text from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
     builder_methods! {
         pub font_size { style.font_size = Some(FontSize) }
+        pub font_weight { style.font_weight = Some(text::font::Weight) }
+        pub font_style { style.font_style = Some(text::font::Style) }
         pub justify { style.justify = Some(text::Justify) }
         pub y_align_text { style.y_align = Some(Align) }
         pub line_wrap { style.line_wrap = Some(Wrap) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
         pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub undo_history { style.undo_history = Some(bool) }
     }
 }
 
@@ -189,6 +428,9 @@ impl<'a> Widget for TextEdit<'a> {
             cursor: Cursor::Idx(text::cursor::Index { line: 0, char: 0 }),
             drag: None,
             line_infos: Vec::new(),
+            history: History::new(),
+            given_history: None,
+            edited_text: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -206,11 +448,10 @@ impl<'a> Widget for TextEdit<'a> {
 
         // Otherwise the height is unrestricted, and we should infer the height as the total height
         // of the fully styled, wrapped text.
-        let font = match self
+        let (font_id, font) = match self
             .style
-            .font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id))
+            .resolve_font_id(&ui.theme, &ui.fonts)
+            .and_then(|id| ui.fonts.chain(id).map(|font| (id, font)))
         {
             Some(font) => font,
             None => return Dimension::Absolute(0.0),
@@ -220,14 +461,11 @@ impl<'a> Widget for TextEdit<'a> {
         let font_size = self.style.font_size(&ui.theme);
         let num_lines = match self.get_w(ui) {
             None => text.lines().count(),
-            Some(max_w) => match self.style.line_wrap(&ui.theme) {
-                Wrap::Character => text::line::infos(text, font, font_size)
-                    .wrap_by_character(max_w)
-                    .count(),
-                Wrap::Whitespace => text::line::infos(text, font, font_size)
-                    .wrap_by_whitespace(max_w)
-                    .count(),
-            },
+            Some(max_w) => {
+                let wrap = Some((self.style.line_wrap(&ui.theme), max_w));
+                let key = text::layout::Key::new(text, font_id, font_size, wrap);
+                ui.text_layouts().line_infos(key, text, font).len()
+            }
         };
         let line_spacing = self.style.line_spacing(&ui.theme);
         let height = text::height(std::cmp::max(num_lines, 1), font_size, line_spacing);
@@ -244,16 +482,40 @@ impl<'a> Widget for TextEdit<'a> {
             ui,
             ..
         } = args;
-        let TextEdit { text, .. } = self;
+        let TextEdit { text, history, .. } = self;
         let mut text = std::borrow::Cow::Borrowed(text);
 
+        // If the text differs from the text left by the last edit, e.g. because the user cleared
+        // it, the recorded edits no longer apply to it.
+        let text_was_replaced = match state.edited_text {
+            Some(ref edited) => *edited != *text,
+            None => false,
+        };
+        if text_was_replaced {
+            state.update(|state| {
+                state.history.clear();
+                state.edited_text = None;
+            });
+        }
+
+        let given_history = history
+            .as_ref()
+            .map(|history| (history.revision, history.capacity));
+        if given_history != state.given_history {
+            state.update(|state| {
+                if let Some(history) = history {
+                    state.history = history;
+                }
+                state.given_history = given_history;
+            });
+        }
+
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
         //
         // If we've no font to use for text logic, bail out without updating.
         let font_id = match style
-            .font_id(&ui.theme)
-            .or(ui.fonts.ids().next())
-            .and_then(|id| ui.fonts.get(id).map(|_| id))
+            .resolve_font_id(&ui.theme, &ui.fonts)
+            .and_then(|id| ui.fonts.chain(id).map(|_| id))
         {
             Some(font_id) => font_id,
             None => return None,
@@ -265,13 +527,14 @@ impl<'a> Widget for TextEdit<'a> {
         let y_align = style.y_align(ui.theme());
         let line_spacing = style.line_spacing(ui.theme());
         let restrict_to_height = style.restrict_to_height(ui.theme());
+        let undo_history = style.undo_history(ui.theme());
 
         /// Returns an iterator yielding the `text::line::Info` for each line in the given text
         /// with the given styling.
         type LineInfos<'a> = text::line::Infos<'a, text::line::NextBreakFnPtr>;
         fn line_infos<'a>(
             text: &'a str,
-            font: &'a text::Font,
+            font: text::font::Chain<'a>,
             font_size: FontSize,
             line_wrap: Wrap,
             max_width: Scalar,
@@ -285,18 +548,13 @@ impl<'a> Widget for TextEdit<'a> {
 
         // Check to see if the given text has changed since the last time the widget was updated.
         {
-            let maybe_new_line_infos = {
-                let line_info_slice = &state.line_infos[..];
-                let font = ui.fonts.get(font_id).unwrap();
-                let new_line_infos = line_infos(&text, font, font_size, line_wrap, rect.w());
-                match utils::write_if_different(line_info_slice, new_line_infos) {
-                    std::borrow::Cow::Owned(new) => Some(new),
-                    _ => None,
-                }
-            };
-
-            if let Some(new_line_infos) = maybe_new_line_infos {
-                state.update(|state| state.line_infos = new_line_infos);
+            let font = ui.fonts.chain(font_id).unwrap();
+            let wrap = Some((line_wrap, rect.w()));
+            let key = text::layout::Key::new(&text, font_id, font_size, wrap);
+            let mut text_layouts = ui.text_layouts();
+            let new_line_infos = text_layouts.line_infos(key, &text, font);
+            if state.line_infos[..] != *new_line_infos {
+                state.update(|state| state.line_infos = new_line_infos.to_vec());
             }
         }
 
@@ -326,7 +584,7 @@ impl<'a> Widget for TextEdit<'a> {
         let cursor_xy_at = |cursor_idx: text::cursor::Index,
                             text: &str,
                             line_infos: &[text::line::Info],
-                            font: &text::Font|
+                            font: text::font::Chain|
          -> Option<(Scalar, Range)> {
             let xys_per_line = text::cursor::xys_per_line_from_text(
                 text,
@@ -341,14 +599,14 @@ impl<'a> Widget for TextEdit<'a> {
             text::cursor::xy_at(xys_per_line, cursor_idx)
         };
 
-        // Find the closest cursor index to the given `xy` position.
+        // Find the closest cursor index to the given `xy` position, at the start of a grapheme.
         //
         // Returns `None` if the given `text` is empty.
-        let closest_cursor_index_and_xy = |xy: Point,
-                                           text: &str,
-                                           line_infos: &[text::line::Info],
-                                           font: &text::Font|
-         -> Option<(text::cursor::Index, Point)> {
+        let closest_cursor_index = |xy: Point,
+                                    text: &str,
+                                    line_infos: &[text::line::Info],
+                                    font: text::font::Chain|
+         -> Option<text::cursor::Index> {
             let xys_per_line = text::cursor::xys_per_line_from_text(
                 text,
                 line_infos,
@@ -360,6 +618,7 @@ impl<'a> Widget for TextEdit<'a> {
                 rect,
             );
             text::cursor::closest_cursor_index_and_xy(xy, xys_per_line)
+                .map(|(idx, _)| idx.snap_to_grapheme(text, line_infos.iter().cloned()))
         };
 
         // Find the closest cursor index to the given `x` position over the given line.
@@ -367,7 +626,7 @@ impl<'a> Widget for TextEdit<'a> {
                                             line_idx: usize,
                                             text: &str,
                                             line_infos: &[text::line::Info],
-                                            font: &text::Font|
+                                            font: text::font::Chain|
          -> Option<text::cursor::Index> {
             let mut xys_per_line = text::cursor::xys_per_line_from_text(
                 text,
@@ -381,10 +640,11 @@ impl<'a> Widget for TextEdit<'a> {
             );
             xys_per_line.nth(line_idx).and_then(|(line_xs, _)| {
                 let (char_idx, _) = text::cursor::closest_cursor_index_on_line(x_pos, line_xs);
-                Some(text::cursor::Index {
+                let idx = text::cursor::Index {
                     line: line_idx,
                     char: char_idx,
-                })
+                };
+                Some(idx.snap_to_grapheme(text, line_infos.iter().cloned()))
             })
         };
 
@@ -400,7 +660,7 @@ impl<'a> Widget for TextEdit<'a> {
                            cursor: Cursor,
                            text: &str,
                            infos: &[text::line::Info],
-                           font: &text::Font|
+                           font: text::font::Chain|
          -> Option<(String, Cursor, std::vec::Vec<text::line::Info>)> {
             let string_char_count = string.chars().count();
 
@@ -471,21 +731,14 @@ impl<'a> Widget for TextEdit<'a> {
                     if let input::MouseButton::Left = click.button {
                         let abs_xy = utils::vec2_add(click.xy, rect.xy());
                         let infos = &state.line_infos;
-                        let font = ui.fonts.get(font_id).unwrap();
-                        let closest = closest_cursor_index_and_xy(abs_xy, &text, infos, font);
+                        let font = ui.fonts.chain(font_id).unwrap();
+                        let closest = closest_cursor_index(abs_xy, &text, infos, font);
 
-                        if let Some((cursor_idx, _)) = closest {
+                        if let Some(cursor_idx) = closest {
                             let line_infos = state.line_infos.iter().cloned();
-
-                            let (start, end) = (
-                                cursor_idx
-                                    .previous_word_start(&text, line_infos.clone())
-                                    .unwrap_or(cursor_idx), // account for the first position of the text
-                                cursor_idx
-                                    .next_word_end(&text, line_infos)
-                                    .unwrap_or(cursor_idx), // account for the last position of the text
-                            );
-
+                            let (start, end) = cursor_idx
+                                .word_bounds(&text, line_infos)
+                                .unwrap_or((cursor_idx, cursor_idx));
                             cursor = Cursor::Selection { start, end };
                         }
                     }
@@ -496,10 +749,10 @@ impl<'a> Widget for TextEdit<'a> {
                     event::Button::Mouse(input::MouseButton::Left, rel_xy) => {
                         let abs_xy = utils::vec2_add(rel_xy, rect.xy());
                         let infos = &state.line_infos;
-                        let font = ui.fonts.get(font_id).unwrap();
-                        let closest = closest_cursor_index_and_xy(abs_xy, &text, infos, font);
+                        let font = ui.fonts.chain(font_id).unwrap();
+                        let closest = closest_cursor_index(abs_xy, &text, infos, font);
 
-                        if let Some((closest_cursor, _)) = closest {
+                        if let Some(closest_cursor) = closest {
                             // We may be handling a range selection if the SHIFT key is held while left clicking
                             if press
                                 .modifiers
@@ -538,12 +791,14 @@ impl<'a> Widget for TextEdit<'a> {
 
                                     let end = match (key, delete_word) {
                                         (input::Key::Backspace, false) => {
-                                            cursor_idx.previous(line_infos)
+                                            cursor_idx.previous_grapheme(&text, line_infos)
                                         }
                                         (input::Key::Backspace, true) => {
                                             cursor_idx.previous_word_start(&text, line_infos)
                                         }
-                                        (input::Key::Delete, false) => cursor_idx.next(line_infos),
+                                        (input::Key::Delete, false) => {
+                                            cursor_idx.next_grapheme(&text, line_infos)
+                                        }
                                         (input::Key::Delete, true) => {
                                             cursor_idx.next_word_end(&text, line_infos)
                                         }
@@ -576,14 +831,20 @@ impl<'a> Widget for TextEdit<'a> {
                                     text::cursor::index_before_char(line_infos, new_cursor_char_idx)
                                         .expect("char index was out of range")
                                 };
-                                cursor = Cursor::Idx(new_cursor_idx);
+                                let new_cursor = Cursor::Idx(new_cursor_idx);
+                                if undo_history && start_idx != end_idx {
+                                    state.update(|state| {
+                                        state.history.record(&text, cursor, new_cursor, Edit::Other)
+                                    });
+                                }
+                                cursor = new_cursor;
                                 *text.to_mut() = text
                                     .chars()
                                     .take(start_idx)
                                     .chain(text.chars().skip(end_idx))
                                     .collect();
                                 state.update(|state| {
-                                    let font = ui.fonts.get(font_id).unwrap();
+                                    let font = ui.fonts.chain(font_id).unwrap();
                                     let w = rect.w();
                                     state.line_infos =
                                         line_infos(&text, font, font_size, line_wrap, w).collect();
@@ -595,7 +856,7 @@ impl<'a> Widget for TextEdit<'a> {
                         | input::Key::Right
                         | input::Key::Up
                         | input::Key::Down => {
-                            let font = ui.fonts.get(font_id).unwrap();
+                            let font = ui.fonts.chain(font_id).unwrap();
                             let move_word =
                                 press.modifiers.contains(input::keyboard::ModifierKey::CTRL);
                             let select = press
@@ -616,8 +877,10 @@ impl<'a> Widget for TextEdit<'a> {
                                     (input::Key::Right, true) => {
                                         cursor_idx.next_word_end(&text, line_infos)
                                     }
-                                    (input::Key::Left, false) => cursor_idx.previous(line_infos),
-                                    (input::Key::Right, false) => cursor_idx.next(line_infos),
+                                    (input::Key::Left, false) => cursor_idx.left(&text, line_infos),
+                                    (input::Key::Right, false) => {
+                                        cursor_idx.right(&text, line_infos)
+                                    }
 
                                     // Up/Down movement
                                     _ => cursor_xy_at(cursor_idx, &text, &state.line_infos, font)
@@ -746,6 +1009,42 @@ impl<'a> Widget for TextEdit<'a> {
                             }
                         }
 
+                        // Undo on Ctrl+z and redo on Ctrl+Shift+z or Ctrl+y.
+                        input::Key::Z | input::Key::Y
+                            if undo_history
+                                && press.modifiers.contains(input::keyboard::ModifierKey::CTRL) =>
+                        {
+                            let redo = key == input::Key::Y
+                                || press
+                                    .modifiers
+                                    .contains(input::keyboard::ModifierKey::SHIFT);
+                            let mut snapshot = None;
+                            state.update(|state| {
+                                snapshot = if redo {
+                                    state.history.redo(&text, cursor)
+                                } else {
+                                    state.history.undo(&text, cursor)
+                                };
+                            });
+                            if let Some(snapshot) = snapshot {
+                                *text.to_mut() = snapshot.text;
+                                state.update(|state| {
+                                    let font = ui.fonts.chain(font_id).unwrap();
+                                    let w = rect.w();
+                                    state.line_infos =
+                                        line_infos(&text, font, font_size, line_wrap, w).collect();
+                                });
+                                let lines = || state.line_infos.iter().cloned();
+                                cursor = match snapshot.cursor {
+                                    Cursor::Idx(idx) => Cursor::Idx(idx.clamp_to_lines(lines())),
+                                    Cursor::Selection { start, end } => Cursor::Selection {
+                                        start: start.clamp_to_lines(lines()),
+                                        end: end.clamp_to_lines(lines()),
+                                    },
+                                };
+                            }
+                        }
+
                         input::Key::E => {
                             // move cursor to end.
                             if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
@@ -770,7 +1069,7 @@ impl<'a> Widget for TextEdit<'a> {
                             if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
                                 let mut clipboard: ClipboardContext =
                                     ClipboardContext::new().unwrap();
-                                let font = ui.fonts.get(font_id).unwrap();
+                                let font = ui.fonts.chain(font_id).unwrap();
                                 let content = &clipboard.get_contents().unwrap_or(String::from(""));
 
                                 if content.len() > 0 {
@@ -782,9 +1081,19 @@ impl<'a> Widget for TextEdit<'a> {
                                         font,
                                     ) {
                                         Some((new_text, new_cursor, new_line_infos)) => {
+                                            state.update(|state| {
+                                                if undo_history {
+                                                    state.history.record(
+                                                        &text,
+                                                        cursor,
+                                                        new_cursor,
+                                                        Edit::Other,
+                                                    );
+                                                }
+                                                state.line_infos = new_line_infos;
+                                            });
                                             *text.to_mut() = new_text;
                                             cursor = new_cursor;
-                                            state.update(|state| state.line_infos = new_line_infos);
                                         }
                                         _ => (),
                                     }
@@ -827,12 +1136,22 @@ impl<'a> Widget for TextEdit<'a> {
                         }
 
                         input::Key::Return => {
-                            let font = ui.fonts.get(font_id).unwrap();
+                            let font = ui.fonts.chain(font_id).unwrap();
                             match insert_text("\n", cursor, &text, &state.line_infos, font) {
                                 Some((new_text, new_cursor, new_line_infos)) => {
+                                    state.update(|state| {
+                                        if undo_history {
+                                            state.history.record(
+                                                &text,
+                                                cursor,
+                                                new_cursor,
+                                                Edit::Other,
+                                            );
+                                        }
+                                        state.line_infos = new_line_infos;
+                                    });
                                     *text.to_mut() = new_text;
                                     cursor = new_cursor;
-                                    state.update(|state| state.line_infos = new_line_infos);
                                 }
                                 _ => (),
                             }
@@ -870,12 +1189,19 @@ impl<'a> Widget for TextEdit<'a> {
                         _ => (),
                     }
 
-                    let font = ui.fonts.get(font_id).unwrap();
+                    let font = ui.fonts.chain(font_id).unwrap();
                     match insert_text(&string, cursor, &text, &state.line_infos, font) {
                         Some((new_text, new_cursor, new_line_infos)) => {
+                            state.update(|state| {
+                                if undo_history {
+                                    state
+                                        .history
+                                        .record(&text, cursor, new_cursor, Edit::Typing);
+                                }
+                                state.line_infos = new_line_infos;
+                            });
                             *text.to_mut() = new_text;
                             cursor = new_cursor;
-                            state.update(|state| state.line_infos = new_line_infos);
                         }
                         _ => (),
                     }
@@ -893,9 +1219,9 @@ impl<'a> Widget for TextEdit<'a> {
                             };
                             let abs_xy = utils::vec2_add(drag_event.to, rect.xy());
                             let infos = &state.line_infos;
-                            let font = ui.fonts.get(font_id).unwrap();
-                            match closest_cursor_index_and_xy(abs_xy, &text, infos, font) {
-                                Some((end_cursor_idx, _)) => {
+                            let font = ui.fonts.chain(font_id).unwrap();
+                            match closest_cursor_index(abs_xy, &text, infos, font) {
+                                Some(end_cursor_idx) => {
                                     cursor = Cursor::Selection {
                                         start: start_cursor_idx,
                                         end: end_cursor_idx,
@@ -931,6 +1257,13 @@ impl<'a> Widget for TextEdit<'a> {
             state.update(|state| state.drag = drag);
         }
 
+        // Remember the text left by any edit so that later changes to it by the user are noticed.
+        if let std::borrow::Cow::Owned(ref edited) = text {
+            if undo_history {
+                state.update(|state| state.edited_text = Some(edited.clone()));
+            }
+        }
+
         // Takes the `String` from the `Cow` if the `Cow` is `Owned`.
         fn take_if_owned(text: std::borrow::Cow<str>) -> Option<String> {
             match text {
@@ -976,7 +1309,7 @@ impl<'a> Widget for TextEdit<'a> {
         }
 
         let (cursor_x, cursor_y_range) = {
-            let font = ui.fonts.get(font_id).unwrap();
+            let font = ui.fonts.chain(font_id).unwrap();
             cursor_xy_at(cursor_idx, &text, &state.line_infos, font).unwrap_or_else(|| {
                 let x = rect.left();
                 let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
@@ -1034,7 +1367,7 @@ impl<'a> Widget for TextEdit<'a> {
                     line_spacing,
                 );
                 let lines_with_rects = lines.zip(line_rects.clone());
-                let font = ui.fonts.get(font_id).unwrap();
+                let font = ui.fonts.chain(font_id).unwrap();
                 text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
             };
 
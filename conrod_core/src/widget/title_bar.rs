@@ -1,9 +1,10 @@
 //! A simple title bar widget that automatically sizes itself to the top of some other widget.
 
-use position::{self, Align, Dimension, Scalar};
+use position::{self, Align, Dimension, Point, Scalar};
 use text;
+use theme::FontOptions;
 use widget::{self, Widget};
-use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Ui};
+use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Sizeable, Theme, Ui};
 
 /// A simple title bar widget that automatically sizes itself to the top of some other widget.
 #[derive(Clone, WidgetCommon_)]
@@ -25,6 +26,7 @@ pub struct State {
 widget_ids! {
     struct Ids {
         rectangle,
+        skin,
         label,
     }
 }
@@ -50,6 +52,9 @@ pub struct Style {
     /// The way in which the title bar's text should wrap.
     #[conrod(default = "Some(widget::text::Wrap::Whitespace)")]
     pub maybe_wrap: Option<Option<widget::text::Wrap>>,
+    /// The way in which the title bar's text should be truncated if it exceeds the width.
+    #[conrod(default = "None")]
+    pub maybe_truncate: Option<Option<widget::text::Truncate>>,
     /// The distance between lines for multi-line title bar text.
     #[conrod(default = "1.0")]
     pub line_spacing: Option<Scalar>,
@@ -62,9 +67,29 @@ pub struct Style {
     /// The position of the title bar's `Label` widget over the *y* axis.
     #[conrod(default = "position::Relative::Align(Align::Middle)")]
     pub label_y: Option<position::Relative>,
+    /// The stroke drawn around the title bar's text, if any.
+    #[conrod(default = "None")]
+    pub outline: Option<Option<widget::text::Outline>>,
+    /// The shadow drawn beneath the title bar's text, if any.
+    #[conrod(default = "None")]
+    pub drop_shadow: Option<Option<widget::text::DropShadow>>,
     /// The font used for the `Text`.
-    #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the title's font is selected when no font id is given.
+    pub font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the title's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub font_weight: Option<text::font::Weight>,
+    /// The style of the title's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub font_style: Option<text::font::Style>,
+    /// Whether or not the label may be selected and copied, in which case it receives its own
+    /// input rather than passing it to the TitleBar.
+    #[conrod(default = "false")]
+    pub label_selectable: Option<bool>,
+    /// If `Some`, the image drawn as the background in place of the bordered rectangle.
+    #[conrod(default = "None")]
+    pub skin: Option<Option<widget::nine_slice::Skin>>,
 }
 
 /// The padding between the edge of the title bar and the title bar's label.
@@ -114,12 +139,68 @@ impl<'a> TitleBar<'a> {
         self
     }
 
+    /// Truncate the label with an ellipsis rather than wrapping it if it exceeds the width.
+    pub fn truncate_label(mut self, truncate: widget::text::Truncate) -> Self {
+        self.style.maybe_truncate = Some(Some(truncate));
+        self
+    }
+
+    /// Draw a stroke of the given width and color around the label's glyphs.
+    pub fn label_outline(mut self, width: Scalar, color: Color) -> Self {
+        self.style.outline = Some(Some(widget::text::Outline {
+            width: width,
+            color: color,
+        }));
+        self
+    }
+
+    /// Draw a copy of the label in the given color at the given offset beneath it.
+    pub fn label_drop_shadow(mut self, offset: Point, color: Color) -> Self {
+        self.style.drop_shadow = Some(Some(widget::text::DropShadow {
+            offset: offset,
+            color: color,
+        }));
+        self
+    }
+
     /// Specify the font used for displaying the text.
     pub fn font_id(mut self, font_id: text::font::Id) -> Self {
         self.style.font_id = Some(Some(font_id));
         self
     }
 
+    /// Select the title's font from the given family.
+    pub fn font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the title's font within its family.
+    pub fn font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the title's font within its family.
+    pub fn font_style(mut self, style: text::font::Style) -> Self {
+        self.style.font_style = Some(style);
+        self
+    }
+
+    /// Allow the label to be selected and copied.
+    ///
+    /// A selectable label receives its own input rather than passing it to the TitleBar.
+    pub fn label_selectable(mut self, selectable: bool) -> Self {
+        self.style.label_selectable = Some(selectable);
+        self
+    }
+
+    /// Draw the given nine-slice image as the background in place of the bordered rectangle.
+    pub fn skin(mut self, skin: widget::nine_slice::Skin) -> Self {
+        self.style.skin = Some(Some(skin));
+        self
+    }
+
     builder_methods! {
         pub line_spacing { style.line_spacing = Some(Scalar) }
     }
@@ -130,6 +211,23 @@ pub fn calc_height(font_size: FontSize) -> Scalar {
     font_size as Scalar + LABEL_PADDING * 2.0
 }
 
+impl Style {
+    /// The id of the font used to display the title's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.font_id,
+            family: style.font_family,
+            weight: style.font_weight,
+            style: style.font_style,
+        })
+    }
+}
+
 impl<'a> Widget for TitleBar<'a> {
     type State = State;
     type Style = Style;
@@ -162,18 +260,31 @@ impl<'a> Widget for TitleBar<'a> {
         } = args;
         let TitleBar { label, .. } = self;
 
-        // BorderedRectangle widget.
+        // BorderedRectangle widget, or the skin if there is one.
         let dim = rect.dim();
-        let color = style.color(ui.theme());
         let border = style.border(ui.theme());
-        let border_color = style.border_color(ui.theme());
-        widget::BorderedRectangle::new(dim)
-            .color(color)
-            .border(border)
-            .border_color(border_color)
-            .middle_of(id)
-            .graphics_for(id)
-            .set(state.ids.rectangle, ui);
+        let background = match style.skin(ui.theme()) {
+            Some(skin) => {
+                widget::NineSlice::from_skin(skin)
+                    .wh(dim)
+                    .middle_of(id)
+                    .graphics_for(id)
+                    .set(state.ids.skin, ui);
+                state.ids.skin
+            }
+            None => {
+                let color = style.color(ui.theme());
+                let border_color = style.border_color(ui.theme());
+                widget::BorderedRectangle::new(dim)
+                    .color(color)
+                    .border(border)
+                    .border_color(border_color)
+                    .middle_of(id)
+                    .graphics_for(id)
+                    .set(state.ids.rectangle, ui);
+                state.ids.rectangle
+            }
+        };
 
         // Label widget.
         let text_color = style.text_color(ui.theme());
@@ -181,22 +292,30 @@ impl<'a> Widget for TitleBar<'a> {
         let font_size = style.font_size(ui.theme());
         let line_spacing = style.line_spacing(ui.theme());
         let maybe_wrap = style.maybe_wrap(ui.theme());
-        let font_id = style.font_id(&ui.theme).or(ui.fonts.ids().next());
+        let maybe_truncate = style.maybe_truncate(ui.theme());
+        let outline = style.outline(ui.theme());
+        let drop_shadow = style.drop_shadow(ui.theme());
+        let font_id = style.resolve_font_id(&ui.theme, &ui.fonts);
         let label_x = style.label_x(&ui.theme);
         let label_y = style.label_y(&ui.theme);
+        let selectable = style.label_selectable(ui.theme());
         widget::Text::new(label)
             .and_mut(|text| {
                 text.style.maybe_wrap = Some(maybe_wrap);
+                text.style.maybe_truncate = Some(maybe_truncate);
+                text.style.outline = Some(outline);
+                text.style.drop_shadow = Some(drop_shadow);
                 text.style.justify = Some(justify);
             })
             .and_then(font_id, widget::Text::font_id)
-            .padded_w_of(state.ids.rectangle, border)
-            .x_position_relative_to(state.ids.rectangle, label_x)
-            .y_position_relative_to(state.ids.rectangle, label_y)
+            .padded_w_of(background, border)
+            .x_position_relative_to(background, label_x)
+            .y_position_relative_to(background, label_y)
             .color(text_color)
             .font_size(font_size)
             .line_spacing(line_spacing)
-            .graphics_for(id)
+            .and_if(!selectable, |text| text.graphics_for(id))
+            .selectable(selectable)
             .set(state.ids.label, ui);
     }
 }
@@ -2,8 +2,9 @@
 
 use position::{self, Align};
 use text;
+use theme::FontOptions;
 use widget;
-use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Scalar, Widget};
+use {Borderable, Color, Colorable, FontSize, Labelable, Positionable, Scalar, Theme, Widget};
 
 /// A pressable widget for toggling the state of a bool.
 ///
@@ -42,8 +43,19 @@ pub struct Style {
     #[conrod(default = "theme.font_size_medium")]
     pub label_font_size: Option<FontSize>,
     /// The ID of the font used to display the label.
-    #[conrod(default = "theme.font_id")]
     pub label_font_id: Option<Option<text::font::Id>>,
+    /// The font family from which the label's font is selected when no font id is given.
+    pub label_font_family: Option<Option<text::font::FamilyId>>,
+    /// The weight of the label's font within its family.
+    #[conrod(default = "text::font::Weight::NORMAL")]
+    pub label_font_weight: Option<text::font::Weight>,
+    /// The style of the label's font within its family.
+    #[conrod(default = "text::font::Style::Normal")]
+    pub label_font_style: Option<text::font::Style>,
+    /// Whether or not the label may be selected and copied, in which case it receives its own
+    /// input rather than passing it to the Toggle.
+    #[conrod(default = "false")]
+    pub label_selectable: Option<bool>,
     /// The position of the title bar's `Label` widget over the *x* axis.
     #[conrod(default = "position::Relative::Align(Align::Middle)")]
     pub label_x: Option<position::Relative>,
@@ -106,6 +118,32 @@ impl<'a> Toggle<'a> {
         self
     }
 
+    /// Select the label's font from the given family.
+    pub fn label_font_family(mut self, family: text::font::FamilyId) -> Self {
+        self.style.label_font_family = Some(Some(family));
+        self
+    }
+
+    /// Select the weight of the label's font within its family.
+    pub fn label_font_weight(mut self, weight: text::font::Weight) -> Self {
+        self.style.label_font_weight = Some(weight);
+        self
+    }
+
+    /// Select the style of the label's font within its family.
+    pub fn label_font_style(mut self, style: text::font::Style) -> Self {
+        self.style.label_font_style = Some(style);
+        self
+    }
+
+    /// Allow the label to be selected and copied.
+    ///
+    /// A selectable label receives its own input rather than passing it to the Toggle.
+    pub fn label_selectable(mut self, selectable: bool) -> Self {
+        self.style.label_selectable = Some(selectable);
+        self
+    }
+
     /// Specify the label's position relatively to `Toggle` along the *x* axis.
     pub fn label_x(mut self, x: position::Relative) -> Self {
         self.style.label_x = Some(x);
@@ -123,6 +161,23 @@ impl<'a> Toggle<'a> {
     }
 }
 
+impl Style {
+    /// The id of the font used to display the label's text, selected as described by
+    /// `Theme::select_font_id`.
+    pub fn resolve_label_font_id(
+        &self,
+        theme: &Theme,
+        fonts: &text::font::Map,
+    ) -> Option<text::font::Id> {
+        theme.resolve_font_id(fonts, self, |style| FontOptions {
+            font_id: style.label_font_id,
+            family: style.label_font_family,
+            weight: style.label_font_weight,
+            style: style.label_font_style,
+        })
+    }
+}
+
 impl<'a> Widget for Toggle<'a> {
     type State = State;
     type Style = Style;
@@ -200,14 +255,16 @@ impl<'a> Widget for Toggle<'a> {
         if let Some(label) = maybe_label {
             let color = style.label_color(ui.theme());
             let font_size = style.label_font_size(ui.theme());
-            let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+            let font_id = style.resolve_label_font_id(&ui.theme, &ui.fonts);
             let x = style.label_x(&ui.theme);
             let y = style.label_y(&ui.theme);
+            let selectable = style.label_selectable(ui.theme());
             widget::Text::new(label)
                 .and_then(font_id, widget::Text::font_id)
                 .x_position_relative_to(id, x)
                 .y_position_relative_to(id, y)
-                .graphics_for(id)
+                .and_if(!selectable, |text| text.graphics_for(id))
+                .selectable(selectable)
                 .color(color)
                 .font_size(font_size)
                 .set(state.ids.label, ui);